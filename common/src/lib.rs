@@ -45,9 +45,11 @@ pub const MIN_EXECUTION_FEE: U256 = uint!(1000_U256);
 pub const MIN_PURCHASE_TOKEN_AMOUNT_US: U256 = PRICE_PRECISION;
 
 // pub const OLP_MANAGER_COOLDOWN: U256 = uint!(900_U256); // 15 minutes
+/// default cooldown between adding and removing liquidity
 pub const OLP_MANAGER_COOLDOWN: U256 = uint!(86400_U256); // 24 hours
-pub const OLP_MANAGER_AUM_ADDITION: U256 = uint!(0_U256);
-pub const OLP_MANAGER_AUM_DEDUCTION: U256 = uint!(0_U256);
+pub const OLP_MANAGER_MAX_COOLDOWN: U256 = uint!(172800_U256); // 48 hours
+/// max usd value of the aum addition and deduction
+pub const OLP_MANAGER_MAX_AUM_ADJUSTMENT: U256 = uint!(10000000000000000000000000000000000000_U256); // 10M USD
 
 pub const BONUS_DURATION: U256 = YEAR;
 
@@ -35,6 +35,27 @@ pub fn safe_mul_ratio(a: U256, b: U256, c: U256) -> Result<U256, Vec<u8>> {
     Ok(U256::from(res))
 }
 
+/// same as [`safe_mul_ratio`] but rounds the result up
+pub fn safe_mul_ratio_up(a: U256, b: U256, c: U256) -> Result<U256, Vec<u8>> {
+    let product = U512::from(a)
+        .checked_mul(U512::from(b))
+        .ok_or(MulRatioError::MultiplyOverflow)?;
+    let res = product
+        .checked_div(U512::from(c))
+        .ok_or(MulRatioError::DivideByZero)?;
+    let res = if product % U512::from(c) == U512::ZERO {
+        res
+    } else {
+        res + U512::from(1)
+    };
+
+    if res > U512::from(U256::MAX) {
+        return Err(MulRatioError::ResultOverflow.into());
+    }
+
+    Ok(U256::from(res))
+}
+
 pub fn safe_mul<T>(a: U256, b: T) -> Result<U256, Vec<u8>>
 where
     U256: UintTryFrom<T>,
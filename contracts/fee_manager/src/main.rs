@@ -247,6 +247,28 @@ impl FeeManager {
         safe_mul_ratio(size, funding_rate, FUNDING_RATE_PRECISION)
     }
 
+    /// funding fee the position would pay if the funding rate was updated now
+    pub fn get_next_funding_fee(
+        &self,
+        collateral_token: Address,
+        size: U256,
+        entry_funding_rate: U256,
+    ) -> Result<U256, Vec<u8>> {
+        if size == U256::ZERO {
+            return Ok(U256::ZERO);
+        }
+
+        let next_cumulative_funding_rate =
+            IFundingRateManager::new(self.funding_rate_manager.get())
+                .get_next_cumulative_funding_rate(self, collateral_token)?;
+        let funding_rate = safe_sub(next_cumulative_funding_rate, entry_funding_rate)?;
+        if funding_rate == U256::ZERO {
+            return Ok(U256::ZERO);
+        }
+
+        safe_mul_ratio(size, funding_rate, FUNDING_RATE_PRECISION)
+    }
+
     pub fn collect_margin_fees(
         &mut self,
         collateral_token: Address,
@@ -106,17 +106,28 @@ impl FundingRateManager {
         )
     }
 
+    /// cumulative funding rate the next `update_cumulative_funding_rate` would produce
+    pub fn get_next_cumulative_funding_rate(&self, token: Address) -> Result<U256, Vec<u8>> {
+        let cumulative_funding_rate = self.cumulative_funding_rates.get(token);
+        if self.last_funding_times.get(token) == U256::ZERO {
+            return Ok(cumulative_funding_rate);
+        }
+
+        safe_add(cumulative_funding_rate, self.get_next_funding_rate(token)?)
+    }
+
+    /// returns the cumulative funding rate after the update
     pub fn update_cumulative_funding_rate(
         &mut self,
         collateral_token: Address,
-    ) -> Result<(), Vec<u8>> {
+    ) -> Result<U256, Vec<u8>> {
         self.only_initialized()?;
 
         let time = U256::from(block::timestamp());
         if self.last_funding_times.get(collateral_token) == U256::ZERO {
             self.last_funding_times
                 .insert(collateral_token, time / FUNDING_INTERVAL * FUNDING_INTERVAL);
-            return Ok(());
+            return Ok(self.cumulative_funding_rates.get(collateral_token));
         }
 
         if safe_add(
@@ -124,7 +135,7 @@ impl FundingRateManager {
             FUNDING_INTERVAL,
         )? > time
         {
-            return Ok(());
+            return Ok(self.cumulative_funding_rates.get(collateral_token));
         }
 
         let funding_rate = self.get_next_funding_rate(collateral_token)?;
@@ -143,6 +154,6 @@ impl FundingRateManager {
             funding_rate: self.cumulative_funding_rates.get(collateral_token),
         });
 
-        Ok(())
+        Ok(self.cumulative_funding_rates.get(collateral_token))
     }
 }
@@ -7,11 +7,15 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 use alloy_primitives::{Address, U256};
 use omx_common::{
     call_context::GetCallContext, safe_add, safe_mul_ratio, safe_sub, OLP_MANAGER_COOLDOWN,
+    OLP_MANAGER_MAX_COOLDOWN,
 };
 use omx_interfaces::{
     base_token::IBaseToken,
     erc20::{safe_transfer_from, IErc20},
-    olp_manager::{AddLiquidity, IOlpManagerUtils, OlpManagerError, RemoveLiquidity},
+    olp_manager::{
+        AddLiquidity, AddLiquidityBasket, IOlpManagerUtils, OlpManagerError, RemoveLiquidity,
+        RemoveLiquidityBasket,
+    },
     vault::ISwapManager,
 };
 use stylus_sdk::{block, contract, evm, msg, prelude::*};
@@ -36,6 +40,11 @@ sol_storage! {
         mapping (address => uint256) last_added_at;
 
         mapping (address => bool) is_handler;
+
+        uint256 cooldown_duration;
+        /// accounts that enforce their own lockups on the olp they hold, like the olp vault,
+        /// their removals skip the cooldown whichever handler they go through
+        mapping (address => bool) is_cooldown_exempt;
     }
 }
 
@@ -64,33 +73,34 @@ impl OlpManager {
         Ok(())
     }
 
-    fn get_aum_in_usdo(&self) -> Result<U256, Vec<u8>> {
-        Ok(IOlpManagerUtils::new(self.utils.get()).get_aum_in_usdo(self)?)
+    fn get_aum_in_usdo(&self, maximise: bool) -> Result<U256, Vec<u8>> {
+        Ok(IOlpManagerUtils::new(self.utils.get()).get_aum_in_usdo(self, maximise)?)
     }
 
-    fn remove_liquidity_internal(
+    /// burns olp of the account and provides the usdo it is worth on this contract,
+    /// returns aum_in_usdo, olp_supply and usdo_amount
+    fn redeem_olp(
         &mut self,
         account: Address,
-        token_out: Address,
         olp_amount: U256,
-        min_amount: U256,
-        recipient: Address,
-    ) -> Result<U256, Vec<u8>> {
+    ) -> Result<(U256, U256, U256), Vec<u8>> {
         if olp_amount == U256::ZERO {
             return Err(OlpManagerError::OlpZeroAmount.into());
         }
-        if safe_add(self.last_added_at.get(account), OLP_MANAGER_COOLDOWN)?
-            > U256::from(block::timestamp())
+        if !self.is_cooldown_exempt.get(account)
+            && safe_add(
+                self.last_added_at.get(account),
+                self.cooldown_duration.get(),
+            )? > U256::from(block::timestamp())
         {
             return Err(OlpManagerError::CooldownNotPassed.into());
         }
 
         let olp = IBaseToken::new(self.olp.get());
         let usdo = IBaseToken::new(self.usdo.get());
-        let vault = self.vault.get();
 
-        // calculate aum before sell_usdo
-        let aum_in_usdo = self.get_aum_in_usdo()?;
+        // calculate aum before sell_usdo, redemptions are priced with the minimised aum
+        let aum_in_usdo = self.get_aum_in_usdo(false)?;
         let olp_supply = olp.total_supply(self.ctx())?;
 
         let usdo_amount = safe_mul_ratio(olp_amount, aum_in_usdo, olp_supply)?;
@@ -106,6 +116,59 @@ impl OlpManager {
 
         olp.burn(self.ctx(), account, olp_amount)?;
 
+        Ok((aum_in_usdo, olp_supply, usdo_amount))
+    }
+
+    /// mints olp for the usdo bought by the deposit, returns the minted amount
+    fn mint_olp(
+        &mut self,
+        account: Address,
+        aum_in_usdo: U256,
+        olp_supply: U256,
+        usdo_amount: U256,
+        min_usdo: U256,
+        min_olp: U256,
+    ) -> Result<U256, Vec<u8>> {
+        if usdo_amount < min_usdo {
+            return Err(OlpManagerError::InsufficientUsdoOutput {
+                amount_out: usdo_amount,
+            }
+            .into());
+        }
+
+        let mint_amount = if aum_in_usdo == U256::ZERO {
+            usdo_amount
+        } else {
+            safe_mul_ratio(usdo_amount, olp_supply, aum_in_usdo)?
+        };
+
+        if mint_amount < min_olp {
+            return Err(OlpManagerError::InsufficientOlpOutput {
+                amount_out: mint_amount,
+            }
+            .into());
+        }
+
+        IBaseToken::new(self.olp.get()).mint(self.ctx(), account, mint_amount)?;
+
+        self.last_added_at
+            .insert(account, U256::from(block::timestamp()));
+
+        Ok(mint_amount)
+    }
+
+    fn remove_liquidity_internal(
+        &mut self,
+        account: Address,
+        token_out: Address,
+        olp_amount: U256,
+        min_amount: U256,
+        recipient: Address,
+    ) -> Result<U256, Vec<u8>> {
+        let (aum_in_usdo, olp_supply, usdo_amount) = self.redeem_olp(account, olp_amount)?;
+
+        let vault = self.vault.get();
+        let usdo = IBaseToken::new(self.usdo.get());
         usdo.transfer(self.ctx(), vault, usdo_amount)?;
 
         let swap_manager = ISwapManager::new(self.swap_manager.get());
@@ -128,6 +191,56 @@ impl OlpManager {
         Ok(amount_out)
     }
 
+    /// redeems olp pro-rata to the available liquidity of every whitelisted token without swap fees,
+    /// `min_amounts` and the returned amounts follow the order of `Vault::all_whitelisted_tokens`
+    fn remove_liquidity_basket_internal(
+        &mut self,
+        account: Address,
+        olp_amount: U256,
+        tokens: Vec<Address>,
+        min_amounts: Vec<U256>,
+        recipient: Address,
+    ) -> Result<Vec<U256>, Vec<u8>> {
+        if tokens.len() != min_amounts.len() {
+            return Err(OlpManagerError::ArrayLengthMismatch.into());
+        }
+
+        let (aum_in_usdo, olp_supply, usdo_amount) = self.redeem_olp(account, olp_amount)?;
+
+        let vault = self.vault.get();
+        let usdo = IBaseToken::new(self.usdo.get());
+        usdo.transfer(self.ctx(), vault, usdo_amount)?;
+
+        let swap_manager = ISwapManager::new(self.swap_manager.get());
+        let (basket_tokens, amounts_out) = swap_manager.sell_usdo_basket(self.ctx(), recipient)?;
+
+        // the min amounts are only meaningful in the order the caller expects
+        if basket_tokens != tokens {
+            return Err(OlpManagerError::TokenListMismatch.into());
+        }
+
+        for (amount_out, min_amount) in amounts_out.iter().zip(min_amounts) {
+            if *amount_out < min_amount {
+                return Err(OlpManagerError::InsufficientOutput {
+                    amount_out: *amount_out,
+                }
+                .into());
+            }
+        }
+
+        evm::log(RemoveLiquidityBasket {
+            account,
+            olp_amount,
+            aum_in_usdo,
+            olp_supply,
+            usdo_amount,
+            tokens,
+            amounts_out: amounts_out.clone(),
+        });
+
+        Ok(amounts_out)
+    }
+
     pub fn add_liquidity_internal(
         &mut self,
         funding_account: Address,
@@ -146,8 +259,8 @@ impl OlpManager {
         let swap_manager = ISwapManager::new(self.swap_manager.get());
         let vault = self.vault.get();
 
-        // calculate aum before buy_usdo
-        let aum_in_usdo = self.get_aum_in_usdo()?;
+        // calculate aum before buy_usdo, mints are priced with the maximised aum
+        let aum_in_usdo = self.get_aum_in_usdo(true)?;
 
         let olp_supply = olp.total_supply(self.ctx())?;
 
@@ -155,35 +268,78 @@ impl OlpManager {
 
         let usdo_amount = swap_manager.buy_usdo(self.ctx(), token.address, contract::address())?;
 
-        if usdo_amount < min_usdo {
-            return Err(OlpManagerError::InsufficientUsdoOutput {
-                amount_out: usdo_amount,
-            }
-            .into());
+        let mint_amount = self.mint_olp(
+            account,
+            aum_in_usdo,
+            olp_supply,
+            usdo_amount,
+            min_usdo,
+            min_olp,
+        )?;
+
+        evm::log(AddLiquidity {
+            account,
+            token: token.address,
+            amount,
+            aum_in_usdo,
+            olp_supply,
+            usdo_amount,
+            mint_amount,
+        });
+
+        Ok(mint_amount)
+    }
+
+    /// deposits every token of the basket and mints olp for the sum of the bought usdo
+    pub fn add_liquidity_basket_internal(
+        &mut self,
+        funding_account: Address,
+        account: Address,
+        tokens: Vec<Address>,
+        amounts: Vec<U256>,
+        min_usdo: U256,
+        min_olp: U256,
+    ) -> Result<U256, Vec<u8>> {
+        if tokens.is_empty() || tokens.len() != amounts.len() {
+            return Err(OlpManagerError::ArrayLengthMismatch.into());
         }
 
-        let mint_amount = if aum_in_usdo == U256::ZERO {
-            usdo_amount
-        } else {
-            safe_mul_ratio(usdo_amount, olp_supply, aum_in_usdo)?
-        };
+        let olp = IBaseToken::new(self.olp.get());
+        let swap_manager = ISwapManager::new(self.swap_manager.get());
+        let vault = self.vault.get();
 
-        if mint_amount < min_olp {
-            return Err(OlpManagerError::InsufficientOlpOutput {
-                amount_out: mint_amount,
+        // calculate aum before buy_usdo, mints are priced with the maximised aum
+        let aum_in_usdo = self.get_aum_in_usdo(true)?;
+
+        let olp_supply = olp.total_supply(self.ctx())?;
+
+        let mut usdo_amount = U256::ZERO;
+        for (token, amount) in tokens.iter().zip(amounts.iter()) {
+            if *amount == U256::ZERO {
+                return Err(OlpManagerError::TokenZeroAmount.into());
             }
-            .into());
-        }
 
-        olp.mint(self.ctx(), account, mint_amount)?;
+            safe_transfer_from(self.ctx(), *token, funding_account, vault, *amount)?;
 
-        self.last_added_at
-            .insert(account, U256::from(block::timestamp()));
+            usdo_amount = safe_add(
+                usdo_amount,
+                swap_manager.buy_usdo(self.ctx(), *token, contract::address())?,
+            )?;
+        }
 
-        evm::log(AddLiquidity {
+        let mint_amount = self.mint_olp(
             account,
-            token: token.address,
-            amount,
+            aum_in_usdo,
+            olp_supply,
+            usdo_amount,
+            min_usdo,
+            min_olp,
+        )?;
+
+        evm::log(AddLiquidityBasket {
+            account,
+            tokens,
+            amounts,
             aum_in_usdo,
             olp_supply,
             usdo_amount,
@@ -222,6 +378,8 @@ impl OlpManager {
         self.usdo.set(usdo);
         self.olp.set(olp);
 
+        self.cooldown_duration.set(OLP_MANAGER_COOLDOWN);
+
         self.initialized.set(true);
 
         Ok(())
@@ -255,6 +413,45 @@ impl OlpManager {
         Ok(())
     }
 
+    pub fn set_cooldown_duration(&mut self, cooldown_duration: U256) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        if cooldown_duration > OLP_MANAGER_MAX_COOLDOWN {
+            return Err(OlpManagerError::InvalidCooldownDuration {
+                max_duration: OLP_MANAGER_MAX_COOLDOWN,
+            }
+            .into());
+        }
+
+        self.cooldown_duration.set(cooldown_duration);
+
+        Ok(())
+    }
+
+    pub fn cooldown_duration(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.cooldown_duration.get())
+    }
+
+    pub fn last_added_at(&self, account: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.last_added_at.get(account))
+    }
+
+    pub fn set_cooldown_exempt(
+        &mut self,
+        account: Address,
+        is_exempt: bool,
+    ) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.is_cooldown_exempt.insert(account, is_exempt);
+
+        Ok(())
+    }
+
+    pub fn is_cooldown_exempt(&self, account: Address) -> Result<bool, Vec<u8>> {
+        Ok(self.is_cooldown_exempt.get(account))
+    }
+
     pub fn add_liquidity(
         &mut self,
         token: Address,
@@ -292,6 +489,50 @@ impl OlpManager {
         self.add_liquidity_internal(funding_account, account, token, amount, min_usdo, min_olp)
     }
 
+    pub fn add_liquidity_basket(
+        &mut self,
+        tokens: Vec<Address>,
+        amounts: Vec<U256>,
+        min_usdo: U256,
+        min_olp: U256,
+    ) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        if self.in_private_mode.get() {
+            return Err(OlpManagerError::ActionNotEnabled.into());
+        }
+
+        self.add_liquidity_basket_internal(
+            msg::sender(),
+            msg::sender(),
+            tokens,
+            amounts,
+            min_usdo,
+            min_olp,
+        )
+    }
+
+    pub fn add_liquidity_basket_for_account(
+        &mut self,
+        funding_account: Address,
+        account: Address,
+        tokens: Vec<Address>,
+        amounts: Vec<U256>,
+        min_usdo: U256,
+        min_olp: U256,
+    ) -> Result<U256, Vec<u8>> {
+        self.only_handler()?;
+
+        self.add_liquidity_basket_internal(
+            funding_account,
+            account,
+            tokens,
+            amounts,
+            min_usdo,
+            min_olp,
+        )
+    }
+
     pub fn remove_liquidity(
         &mut self,
         token_out: Address,
@@ -320,4 +561,39 @@ impl OlpManager {
 
         self.remove_liquidity_internal(account, token_out, olp_amount, min_amount, recipient)
     }
+
+    pub fn remove_liquidity_basket(
+        &mut self,
+        olp_amount: U256,
+        tokens: Vec<Address>,
+        min_amounts: Vec<U256>,
+        recipient: Address,
+    ) -> Result<Vec<U256>, Vec<u8>> {
+        self.only_initialized()?;
+
+        if self.in_private_mode.get() {
+            return Err(OlpManagerError::ActionNotEnabled.into());
+        }
+
+        self.remove_liquidity_basket_internal(
+            msg::sender(),
+            olp_amount,
+            tokens,
+            min_amounts,
+            recipient,
+        )
+    }
+
+    pub fn remove_liquidity_basket_for_account(
+        &mut self,
+        account: Address,
+        olp_amount: U256,
+        tokens: Vec<Address>,
+        min_amounts: Vec<U256>,
+        recipient: Address,
+    ) -> Result<Vec<U256>, Vec<u8>> {
+        self.only_handler()?;
+
+        self.remove_liquidity_basket_internal(account, olp_amount, tokens, min_amounts, recipient)
+    }
 }
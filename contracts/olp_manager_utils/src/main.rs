@@ -7,8 +7,7 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 use alloy_primitives::{Address, U256};
 use omx_common::{
     safe_add, safe_div, safe_mul, safe_mul_ratio, safe_sub, BASIS_POINTS_DIVISOR,
-    OLP_MANAGER_AUM_ADDITION, OLP_MANAGER_AUM_DEDUCTION, OLP_PRECISION, PRICE_PRECISION,
-    USDO_DECIMALS,
+    OLP_MANAGER_MAX_AUM_ADJUSTMENT, OLP_PRECISION, PRICE_PRECISION, USDO_DECIMALS,
 };
 use omx_interfaces::{
     base_token::IBaseToken,
@@ -30,6 +29,13 @@ sol_storage! {
         address shorts_tracker;
 
         uint256 shorts_tracker_average_price_weight;
+
+        /// usd added to and deducted from the aum
+        uint256 aum_addition;
+        uint256 aum_deduction;
+
+        address usdo;
+        address olp_manager;
     }
 }
 
@@ -45,21 +51,29 @@ impl OlpManagerUtils {
 
 #[external]
 impl OlpManagerUtils {
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
         &mut self,
+        gov: Address,
         vault: Address,
         positions_manager: Address,
         shorts_tracker: Address,
         olp: Address,
+        usdo: Address,
+        olp_manager: Address,
     ) -> Result<(), Vec<u8>> {
         if self.initialized.get() {
             return Err(OlpManagerError::AlreadyInitialized.into());
         }
 
+        self.gov.set(gov);
+
         self.vault.set(vault);
         self.positions_manager.set(positions_manager);
         self.shorts_tracker.set(shorts_tracker);
         self.olp.set(olp);
+        self.usdo.set(usdo);
+        self.olp_manager.set(olp_manager);
 
         self.initialized.set(true);
 
@@ -81,27 +95,81 @@ impl OlpManagerUtils {
         Ok(())
     }
 
-    pub fn get_price(&self) -> Result<U256, Vec<u8>> {
-        let aum = self.get_aum()?;
+    pub fn set_aum_adjustment(
+        &mut self,
+        aum_addition: U256,
+        aum_deduction: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        if aum_addition > OLP_MANAGER_MAX_AUM_ADJUSTMENT
+            || aum_deduction > OLP_MANAGER_MAX_AUM_ADJUSTMENT
+        {
+            return Err(OlpManagerError::InvalidAumAdjustment {
+                max_adjustment: OLP_MANAGER_MAX_AUM_ADJUSTMENT,
+            }
+            .into());
+        }
+
+        self.aum_addition.set(aum_addition);
+        self.aum_deduction.set(aum_deduction);
+
+        Ok(())
+    }
+
+    pub fn aum_addition(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.aum_addition.get())
+    }
+
+    pub fn aum_deduction(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.aum_deduction.get())
+    }
+
+    /// olp price, minted against the maximised aum and redeemed against the minimised one
+    pub fn get_price(&self, maximise: bool) -> Result<U256, Vec<u8>> {
+        let aum = self.get_aum(maximise)?;
         let olp = IBaseToken::new(self.olp.get());
         let supply = olp.total_supply(self)?;
 
         safe_mul_ratio(aum, OLP_PRECISION, supply)
     }
 
-    pub fn get_aum_in_usdo(&self) -> Result<U256, Vec<u8>> {
-        let aum = self.get_aum()?;
+    /// returns maximised and minimised aum
+    pub fn get_aums(&self) -> Result<(U256, U256), Vec<u8>> {
+        Ok((self.get_aum(true)?, self.get_aum(false)?))
+    }
+
+    pub fn get_aum_in_usdo(&self, maximise: bool) -> Result<U256, Vec<u8>> {
+        let aum = self.get_aum(maximise)?;
         let scale = U256::from(10).pow(U256::from(USDO_DECIMALS));
 
         safe_mul_ratio(aum, scale, PRICE_PRECISION)
     }
 
-    pub fn get_aum(&self) -> Result<U256, Vec<u8>> {
+    /// usd owed to the usdo holders outside of the olp manager, usdo minted by
+    /// `SwapManager::buy_usdo` adds its collateral to the pool and stays redeemable
+    /// for one usd of it
+    pub fn get_usdo_liability(&self) -> Result<U256, Vec<u8>> {
+        let usdo = IBaseToken::new(self.usdo.get());
+        let supply = usdo.total_supply(self)?;
+        let olp_manager_balance = usdo.balance_of(self, self.olp_manager.get())?;
+
+        let external_supply = supply
+            .checked_sub(olp_manager_balance)
+            .unwrap_or(U256::ZERO);
+        let scale = U256::from(10).pow(U256::from(USDO_DECIMALS));
+
+        safe_mul_ratio(external_supply, PRICE_PRECISION, scale)
+    }
+
+    /// every token, including the pnl of global shorts, is valued at the max price
+    /// if `maximise` is set and at the min price otherwise, the usdo liability is deducted
+    pub fn get_aum(&self, maximise: bool) -> Result<U256, Vec<u8>> {
         let vault = IVault::new(self.vault.get());
 
         let length: u64 = vault.all_whitelisted_tokens_length(self)?.to();
 
-        let mut aum = OLP_MANAGER_AUM_ADDITION;
+        let mut aum = self.aum_addition.get();
         let mut short_profits = U256::ZERO;
 
         for i in 0..length {
@@ -112,7 +180,11 @@ impl OlpManagerUtils {
                 continue;
             }
 
-            let price = vault.get_price(self, token)?;
+            let price = if maximise {
+                vault.get_max_price(self, token)?
+            } else {
+                vault.get_min_price(self, token)?
+            };
             let pool_amount = vault.pool_amount(self, token)?;
             let decimals = vault.get_token_decimals(self, token)?;
             let token_scale = U256::from(10).pow(U256::from(decimals));
@@ -145,9 +217,12 @@ impl OlpManagerUtils {
         }
 
         let aum = aum.checked_sub(short_profits).unwrap_or(U256::ZERO);
+        let aum = aum
+            .checked_sub(self.get_usdo_liability()?)
+            .unwrap_or(U256::ZERO);
 
         Ok(aum
-            .checked_sub(OLP_MANAGER_AUM_DEDUCTION)
+            .checked_sub(self.aum_deduction.get())
             .unwrap_or(U256::ZERO))
     }
 
@@ -168,19 +243,22 @@ impl OlpManagerUtils {
     }
 
     pub fn get_global_short_average_price(&self, token: Address) -> Result<U256, Vec<u8>> {
+        let positions_manager = IPositionsManager::new(self.positions_manager.get());
         let shorts_tracker = IShortsTracker::new(self.shorts_tracker.get());
-        if self.shorts_tracker.get().is_zero() || shorts_tracker.is_global_short_data_ready(self)? {
-            return self.get_global_short_average_price(token);
+        if self.shorts_tracker.get().is_zero()
+            || !shorts_tracker.is_global_short_data_ready(self)?
+        {
+            return Ok(positions_manager.global_short_average_price(self, token)?);
         }
 
         let shorts_tracker_average_price_weight = self.shorts_tracker_average_price_weight.get();
         if shorts_tracker_average_price_weight == U256::ZERO {
-            return self.get_global_short_average_price(token);
-        } else if shorts_tracker_average_price_weight == PRICE_PRECISION {
+            return Ok(positions_manager.global_short_average_price(self, token)?);
+        } else if shorts_tracker_average_price_weight == BASIS_POINTS_DIVISOR {
             return Ok(shorts_tracker.global_short_average_prices(self, token)?);
         }
 
-        let vault_average_price = self.get_global_short_average_price(token)?;
+        let vault_average_price = positions_manager.global_short_average_price(self, token)?;
         let shorts_tracker_average_price =
             shorts_tracker.global_short_average_prices(self, token)?;
 
@@ -0,0 +1,228 @@
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+use alloy_primitives::{uint, Address, U256};
+use omx_common::{call_context::GetCallContext, safe_add, safe_mul, safe_sub, HOUR};
+use omx_interfaces::{
+    olp_manager::IOlpManagerUtils,
+    olp_oracle::{Checkpoint, OlpOracleError},
+};
+use stylus_sdk::{block, evm, prelude::*};
+
+/// one week of hourly snapshots
+pub const MAX_SNAPSHOTS: u64 = 168;
+
+/// snapshots are taken hourly, a missed checkpoint is tolerated
+pub const MAX_SNAPSHOT_AGE: U256 = uint!(7200_U256);
+
+sol_storage! {
+    #[entrypoint]
+    pub struct OlpOracle {
+        bool initialized;
+
+        address olp_manager_utils;
+
+        /// total number of recorded snapshots, only the last `MAX_SNAPSHOTS` are kept
+        uint256 snapshots_count;
+
+        /// ring buffer slot => snapshot
+        mapping (uint256 => uint256) snapshot_timestamps;
+        mapping (uint256 => uint256) snapshot_max_prices;
+        mapping (uint256 => uint256) snapshot_min_prices;
+    }
+}
+
+impl OlpOracle {
+    fn only_initialized(&self) -> Result<(), OlpOracleError> {
+        if !self.initialized.get() {
+            return Err(OlpOracleError::NotInitialized);
+        }
+
+        Ok(())
+    }
+
+    /// index of the oldest snapshot still kept in the ring buffer
+    fn first_snapshot_index(&self) -> u64 {
+        let count: u64 = self.snapshots_count.get().to();
+
+        count.saturating_sub(MAX_SNAPSHOTS)
+    }
+
+    fn snapshot_timestamp(&self, index: u64) -> U256 {
+        self.snapshot_timestamps
+            .get(U256::from(index % MAX_SNAPSHOTS))
+    }
+
+    fn snapshot_price(&self, index: u64, maximise: bool) -> U256 {
+        let slot = U256::from(index % MAX_SNAPSHOTS);
+        if maximise {
+            self.snapshot_max_prices.get(slot)
+        } else {
+            self.snapshot_min_prices.get(slot)
+        }
+    }
+
+    /// the prices can not be read when nobody checkpointed for `MAX_SNAPSHOT_AGE`
+    fn validate_latest_snapshot(&self) -> Result<(), Vec<u8>> {
+        let count: u64 = self.snapshots_count.get().to();
+        if count == 0 {
+            return Err(OlpOracleError::SnapshotNotFound.into());
+        }
+
+        let latest_timestamp = self.snapshot_timestamp(count - 1);
+        if safe_add(latest_timestamp, MAX_SNAPSHOT_AGE)? < U256::from(block::timestamp()) {
+            return Err(OlpOracleError::StaleSnapshot { latest_timestamp }.into());
+        }
+
+        Ok(())
+    }
+
+    /// index of the latest kept snapshot recorded at or before `timestamp`
+    fn find_snapshot(&self, timestamp: U256) -> Result<u64, OlpOracleError> {
+        let count: u64 = self.snapshots_count.get().to();
+        let first = self.first_snapshot_index();
+
+        if count == 0 || self.snapshot_timestamp(first) > timestamp {
+            return Err(OlpOracleError::SnapshotNotFound);
+        }
+
+        // snapshots are ordered by timestamp
+        let mut low = first;
+        let mut high = count - 1;
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if self.snapshot_timestamp(mid) <= timestamp {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        Ok(low)
+    }
+}
+
+#[external]
+impl OlpOracle {
+    pub fn init(&mut self, olp_manager_utils: Address) -> Result<(), Vec<u8>> {
+        if self.initialized.get() {
+            return Err(OlpOracleError::AlreadyInitialized.into());
+        }
+
+        self.olp_manager_utils.set(olp_manager_utils);
+
+        self.initialized.set(true);
+
+        Ok(())
+    }
+
+    pub fn get_price(&self, maximise: bool) -> Result<U256, Vec<u8>> {
+        let utils = IOlpManagerUtils::new(self.olp_manager_utils.get());
+
+        Ok(utils.get_price(self, maximise)?)
+    }
+
+    /// records the current olp price, at most once per hour, returns the snapshot index
+    pub fn checkpoint(&mut self) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        let timestamp = U256::from(block::timestamp());
+        let count = self.snapshots_count.get();
+
+        if count > U256::ZERO {
+            let last_timestamp = self.snapshot_timestamp(count.to::<u64>() - 1);
+            let next_checkpoint_at = safe_mul(safe_add(last_timestamp / HOUR, 1)?, HOUR)?;
+            if timestamp < next_checkpoint_at {
+                return Err(OlpOracleError::CheckpointTooEarly { next_checkpoint_at }.into());
+            }
+        }
+
+        let utils = IOlpManagerUtils::new(self.olp_manager_utils.get());
+        let max_price = utils.get_price(self.ctx(), true)?;
+        let min_price = utils.get_price(self.ctx(), false)?;
+
+        let slot = count % U256::from(MAX_SNAPSHOTS);
+        self.snapshot_timestamps.insert(slot, timestamp);
+        self.snapshot_max_prices.insert(slot, max_price);
+        self.snapshot_min_prices.insert(slot, min_price);
+
+        self.snapshots_count.set(safe_add(count, 1)?);
+
+        evm::log(Checkpoint {
+            index: count,
+            timestamp,
+            max_price,
+            min_price,
+        });
+
+        Ok(count)
+    }
+
+    pub fn snapshots_count(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.snapshots_count.get())
+    }
+
+    /// returns timestamp, max_price and min_price of a kept snapshot
+    pub fn get_snapshot(&self, index: U256) -> Result<(U256, U256, U256), Vec<u8>> {
+        if index >= self.snapshots_count.get() || index < U256::from(self.first_snapshot_index()) {
+            return Err(OlpOracleError::SnapshotNotFound.into());
+        }
+
+        let index = index.to::<u64>();
+
+        Ok((
+            self.snapshot_timestamp(index),
+            self.snapshot_price(index, true),
+            self.snapshot_price(index, false),
+        ))
+    }
+
+    /// price of the latest snapshot recorded at or before `timestamp`
+    pub fn price_at(&self, timestamp: U256, maximise: bool) -> Result<U256, Vec<u8>> {
+        self.validate_latest_snapshot()?;
+
+        let index = self.find_snapshot(timestamp)?;
+
+        Ok(self.snapshot_price(index, maximise))
+    }
+
+    /// time weighted average of the snapshot prices over the last `period` seconds,
+    /// every snapshot price is held until the next snapshot
+    pub fn get_twap(&self, period: U256, maximise: bool) -> Result<U256, Vec<u8>> {
+        let timestamp = U256::from(block::timestamp());
+        if period == U256::ZERO || period > timestamp {
+            return Err(OlpOracleError::InvalidPeriod.into());
+        }
+
+        // the latest price is held until now, it must be recent
+        self.validate_latest_snapshot()?;
+
+        let start = safe_sub(timestamp, period)?;
+        let first = self.find_snapshot(start)?;
+        let count: u64 = self.snapshots_count.get().to();
+
+        let mut last_timestamp = start;
+        let mut last_price = self.snapshot_price(first, maximise);
+        let mut cumulative_price = U256::ZERO;
+        for index in first + 1..count {
+            let snapshot_timestamp = self.snapshot_timestamp(index);
+
+            cumulative_price = safe_add(
+                cumulative_price,
+                safe_mul(last_price, safe_sub(snapshot_timestamp, last_timestamp)?)?,
+            )?;
+
+            last_timestamp = snapshot_timestamp;
+            last_price = self.snapshot_price(index, maximise);
+        }
+        cumulative_price = safe_add(
+            cumulative_price,
+            safe_mul(last_price, safe_sub(timestamp, last_timestamp)?)?,
+        )?;
+
+        Ok(cumulative_price / period)
+    }
+}
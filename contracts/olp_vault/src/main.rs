@@ -0,0 +1,528 @@
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+use alloy_primitives::{uint, Address, U256};
+use omx_common::{
+    call_context::GetCallContext, safe_add, safe_mul_ratio, safe_mul_ratio_up, safe_sub,
+    BASIS_POINTS_DIVISOR, OLP_PRECISION,
+};
+use omx_interfaces::{
+    erc20::{safe_transfer, safe_transfer_from, IErc20},
+    olp_manager::{IOlpManager, IOlpManagerUtils},
+    olp_vault::{Approval, Deposit, Harvest, OlpVaultError, Transfer, Withdraw},
+    reward_router::IRewardRouter,
+    reward_tracker::IRewardTrackerStaking,
+    vault::IVault,
+};
+use stylus_sdk::{block, contract, evm, msg, prelude::*};
+
+pub const NAME: &str = "Compounding OLP";
+pub const SYMBOL: &str = "cOLP";
+pub const DECIMALS: u8 = 18;
+
+/// max shortfall of a harvest against the oracle value of the weth
+pub const HARVEST_MAX_SLIPPAGE_BPS: U256 = uint!(100_U256);
+
+sol_storage! {
+    #[entrypoint]
+    pub struct OlpVault {
+        bool initialized;
+
+        address gov;
+
+        address olp;
+        address weth;
+        address vault;
+        address olp_manager;
+        address olp_manager_utils;
+        address reward_router;
+        address staked_olp_tracker_staking;
+
+        uint256 total_supply;
+
+        mapping (address => uint256) balances;
+        mapping (address => mapping (address => uint256)) allowances;
+    }
+}
+
+impl OlpVault {
+    fn only_gov(&self) -> Result<(), OlpVaultError> {
+        if self.gov.get() != msg::sender() {
+            return Err(OlpVaultError::Forbidden);
+        }
+
+        Ok(())
+    }
+
+    fn only_initialized(&self) -> Result<(), OlpVaultError> {
+        if !self.initialized.get() {
+            return Err(OlpVaultError::NotInitialized);
+        }
+
+        Ok(())
+    }
+
+    fn transfer_internal(
+        &mut self,
+        sender: Address,
+        recipient: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        if sender.is_zero() {
+            return Err(OlpVaultError::TransferFromZeroAddress.into());
+        }
+        if recipient.is_zero() {
+            return Err(OlpVaultError::TransferToZeroAddress.into());
+        }
+
+        let new_sender_balance = safe_sub(self.balances.get(sender), amount)
+            .map_err(|_| OlpVaultError::InsufficientBalance)?;
+        self.balances.insert(sender, new_sender_balance);
+
+        let new_recipient_balance = safe_add(self.balances.get(recipient), amount)?;
+        self.balances.insert(recipient, new_recipient_balance);
+
+        evm::log(Transfer {
+            from: sender,
+            to: recipient,
+            value: amount,
+        });
+
+        Ok(())
+    }
+
+    fn mint_internal(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        if account.is_zero() {
+            return Err(OlpVaultError::TransferToZeroAddress.into());
+        }
+
+        self.total_supply
+            .set(safe_add(self.total_supply.get(), amount)?);
+
+        let new_account_balance = safe_add(self.balances.get(account), amount)?;
+        self.balances.insert(account, new_account_balance);
+
+        evm::log(Transfer {
+            from: Address::ZERO,
+            to: account,
+            value: amount,
+        });
+
+        Ok(())
+    }
+
+    fn burn_internal(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        let new_account_balance = safe_sub(self.balances.get(account), amount)
+            .map_err(|_| OlpVaultError::InsufficientBalance)?;
+        self.balances.insert(account, new_account_balance);
+
+        self.total_supply
+            .set(safe_sub(self.total_supply.get(), amount)?);
+
+        evm::log(Transfer {
+            from: account,
+            to: Address::ZERO,
+            value: amount,
+        });
+
+        Ok(())
+    }
+
+    fn spend_allowance(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        let allowance = self.allowances.getter(owner).get(spender);
+        if allowance == U256::MAX {
+            return Ok(());
+        }
+
+        let new_allowance =
+            safe_sub(allowance, amount).map_err(|_| OlpVaultError::InsufficientAllowance)?;
+        self.allowances.setter(owner).insert(spender, new_allowance);
+
+        Ok(())
+    }
+
+    fn convert_to_shares_internal(&self, assets: U256, round_up: bool) -> Result<U256, Vec<u8>> {
+        let total_supply = self.total_supply.get();
+        if total_supply == U256::ZERO {
+            return Ok(assets);
+        }
+
+        let total_assets = self.total_assets()?;
+        if round_up {
+            safe_mul_ratio_up(assets, total_supply, total_assets)
+        } else {
+            safe_mul_ratio(assets, total_supply, total_assets)
+        }
+    }
+
+    fn convert_to_assets_internal(&self, shares: U256, round_up: bool) -> Result<U256, Vec<u8>> {
+        let total_supply = self.total_supply.get();
+        if total_supply == U256::ZERO {
+            return Ok(shares);
+        }
+
+        let total_assets = self.total_assets()?;
+        if round_up {
+            safe_mul_ratio_up(shares, total_assets, total_supply)
+        } else {
+            safe_mul_ratio(shares, total_assets, total_supply)
+        }
+    }
+
+    /// least olp accepted for `weth_amount`, priced with the vault and olp manager utils
+    fn get_min_harvest_olp(&self, weth: Address, weth_amount: U256) -> Result<U256, Vec<u8>> {
+        let vault = IVault::new(self.vault.get());
+        let weth_price = vault.get_min_price(self, weth)?;
+        let weth_decimals = vault.get_token_decimals(self, weth)?;
+        let usd_amount = safe_mul_ratio(
+            weth_amount,
+            weth_price,
+            U256::from(10).pow(U256::from(weth_decimals)),
+        )?;
+
+        // minting is priced at the max aum
+        let olp_price =
+            IOlpManagerUtils::new(self.olp_manager_utils.get()).get_price(self, true)?;
+        let olp_amount = safe_mul_ratio(usd_amount, OLP_PRECISION, olp_price)?;
+
+        safe_mul_ratio(
+            olp_amount,
+            safe_sub(BASIS_POINTS_DIVISOR, HARVEST_MAX_SLIPPAGE_BPS)?,
+            BASIS_POINTS_DIVISOR,
+        )
+    }
+
+    /// compounds the rewards of the vault into staked olp, returns the amount of minted olp
+    fn harvest_internal(&mut self) -> Result<U256, Vec<u8>> {
+        let reward_router = IRewardRouter::new(self.reward_router.get());
+
+        // esOMX is not redeemable for olp, it is staked and earns weth fees for the vault
+        reward_router.compound(self.ctx())?;
+        reward_router.claim_fees(self.ctx())?;
+
+        let weth = IErc20::new(self.weth.get());
+        let weth_amount = weth.balance_of(self.ctx(), contract::address())?;
+        if weth_amount == U256::ZERO {
+            return Ok(U256::ZERO);
+        }
+
+        // dust is left for a later harvest
+        let min_olp = self.get_min_harvest_olp(weth.address, weth_amount)?;
+        if min_olp == U256::ZERO {
+            return Ok(U256::ZERO);
+        }
+
+        let olp_manager = self.olp_manager.get();
+        weth.approve(self.ctx(), olp_manager, weth_amount)?;
+        let olp_amount = reward_router.mint_and_stake_olp(
+            self.ctx(),
+            weth.address,
+            weth_amount,
+            U256::ZERO,
+            min_olp,
+        )?;
+
+        evm::log(Harvest {
+            caller: msg::sender(),
+            weth_amount,
+            olp_amount,
+        });
+
+        Ok(olp_amount)
+    }
+
+    /// pulls olp of the sender and stakes it for this contract
+    fn deposit_internal(
+        &mut self,
+        receiver: Address,
+        assets: U256,
+        shares: U256,
+    ) -> Result<(), Vec<u8>> {
+        if assets == U256::ZERO || shares == U256::ZERO {
+            return Err(OlpVaultError::ZeroAmount.into());
+        }
+
+        // olp moved through the vault must not skip the redemption cooldown of its minter
+        let olp_manager = IOlpManager::new(self.olp_manager.get());
+        let last_added_at = olp_manager.last_added_at(self.ctx(), msg::sender())?;
+        let cooldown_duration = olp_manager.cooldown_duration(self.ctx())?;
+        if safe_add(last_added_at, cooldown_duration)? > U256::from(block::timestamp()) {
+            return Err(OlpVaultError::CooldownNotPassed.into());
+        }
+
+        let olp = self.olp.get();
+        safe_transfer_from(self.ctx(), olp, msg::sender(), contract::address(), assets)?;
+
+        IRewardRouter::new(self.reward_router.get()).stake_olp(self.ctx(), assets)?;
+
+        self.mint_internal(receiver, shares)?;
+
+        evm::log(Deposit {
+            sender: msg::sender(),
+            owner: receiver,
+            assets,
+            shares,
+        });
+
+        Ok(())
+    }
+
+    /// burns shares of the owner and sends the unstaked olp to the receiver
+    fn withdraw_internal(
+        &mut self,
+        receiver: Address,
+        owner: Address,
+        assets: U256,
+        shares: U256,
+    ) -> Result<(), Vec<u8>> {
+        if assets == U256::ZERO || shares == U256::ZERO {
+            return Err(OlpVaultError::ZeroAmount.into());
+        }
+
+        if msg::sender() != owner {
+            self.spend_allowance(owner, msg::sender(), shares)?;
+        }
+
+        self.burn_internal(owner, shares)?;
+
+        IRewardRouter::new(self.reward_router.get()).unstake_olp(self.ctx(), assets)?;
+
+        let olp = self.olp.get();
+        safe_transfer(self.ctx(), olp, receiver, assets)?;
+
+        evm::log(Withdraw {
+            sender: msg::sender(),
+            receiver,
+            owner,
+            assets,
+            shares,
+        });
+
+        Ok(())
+    }
+}
+
+#[external]
+impl OlpVault {
+    #[allow(clippy::too_many_arguments)]
+    pub fn init(
+        &mut self,
+        gov: Address,
+        olp: Address,
+        weth: Address,
+        vault: Address,
+        olp_manager: Address,
+        olp_manager_utils: Address,
+        reward_router: Address,
+        staked_olp_tracker_staking: Address,
+    ) -> Result<(), Vec<u8>> {
+        if self.initialized.get() {
+            return Err(OlpVaultError::AlreadyInitialized.into());
+        }
+
+        self.gov.set(gov);
+
+        self.olp.set(olp);
+        self.weth.set(weth);
+        self.vault.set(vault);
+        self.olp_manager.set(olp_manager);
+        self.olp_manager_utils.set(olp_manager_utils);
+        self.reward_router.set(reward_router);
+        self.staked_olp_tracker_staking
+            .set(staked_olp_tracker_staking);
+
+        self.initialized.set(true);
+
+        Ok(())
+    }
+
+    pub fn set_gov(&mut self, gov: Address) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.gov.set(gov);
+
+        Ok(())
+    }
+
+    pub fn name(&self) -> Result<String, Vec<u8>> {
+        Ok(NAME.into())
+    }
+
+    pub fn symbol(&self) -> Result<String, Vec<u8>> {
+        Ok(SYMBOL.into())
+    }
+
+    pub fn decimals(&self) -> Result<u8, Vec<u8>> {
+        Ok(DECIMALS)
+    }
+
+    pub fn total_supply(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.total_supply.get())
+    }
+
+    pub fn balance_of(&self, account: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.balances.get(account))
+    }
+
+    pub fn allowance(&self, owner: Address, spender: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.allowances.getter(owner).get(spender))
+    }
+
+    pub fn approve(&mut self, spender: Address, amount: U256) -> Result<bool, Vec<u8>> {
+        if spender.is_zero() {
+            return Err(OlpVaultError::TransferToZeroAddress.into());
+        }
+
+        self.allowances
+            .setter(msg::sender())
+            .insert(spender, amount);
+
+        evm::log(Approval {
+            owner: msg::sender(),
+            spender,
+            value: amount,
+        });
+
+        Ok(true)
+    }
+
+    pub fn transfer(&mut self, recipient: Address, amount: U256) -> Result<bool, Vec<u8>> {
+        self.transfer_internal(msg::sender(), recipient, amount)?;
+
+        Ok(true)
+    }
+
+    pub fn transfer_from(
+        &mut self,
+        sender: Address,
+        recipient: Address,
+        amount: U256,
+    ) -> Result<bool, Vec<u8>> {
+        self.spend_allowance(sender, msg::sender(), amount)?;
+        self.transfer_internal(sender, recipient, amount)?;
+
+        Ok(true)
+    }
+
+    pub fn asset(&self) -> Result<Address, Vec<u8>> {
+        Ok(self.olp.get())
+    }
+
+    /// olp staked by this contract, rewards are included once harvested
+    pub fn total_assets(&self) -> Result<U256, Vec<u8>> {
+        let staked_olp_tracker = IRewardTrackerStaking::new(self.staked_olp_tracker_staking.get());
+
+        Ok(staked_olp_tracker.staked_amount(self, contract::address())?)
+    }
+
+    pub fn convert_to_shares(&self, assets: U256) -> Result<U256, Vec<u8>> {
+        self.convert_to_shares_internal(assets, false)
+    }
+
+    pub fn convert_to_assets(&self, shares: U256) -> Result<U256, Vec<u8>> {
+        self.convert_to_assets_internal(shares, false)
+    }
+
+    pub fn max_deposit(&self, _receiver: Address) -> Result<U256, Vec<u8>> {
+        Ok(U256::MAX)
+    }
+
+    pub fn max_mint(&self, _receiver: Address) -> Result<U256, Vec<u8>> {
+        Ok(U256::MAX)
+    }
+
+    pub fn max_withdraw(&self, owner: Address) -> Result<U256, Vec<u8>> {
+        self.convert_to_assets_internal(self.balances.get(owner), false)
+    }
+
+    pub fn max_redeem(&self, owner: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.balances.get(owner))
+    }
+
+    pub fn preview_deposit(&self, assets: U256) -> Result<U256, Vec<u8>> {
+        self.convert_to_shares_internal(assets, false)
+    }
+
+    pub fn preview_mint(&self, shares: U256) -> Result<U256, Vec<u8>> {
+        self.convert_to_assets_internal(shares, true)
+    }
+
+    pub fn preview_withdraw(&self, assets: U256) -> Result<U256, Vec<u8>> {
+        self.convert_to_shares_internal(assets, true)
+    }
+
+    pub fn preview_redeem(&self, shares: U256) -> Result<U256, Vec<u8>> {
+        self.convert_to_assets_internal(shares, false)
+    }
+
+    pub fn deposit(&mut self, assets: U256, receiver: Address) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        // shares are priced with the pending rewards compounded
+        self.harvest_internal()?;
+
+        let shares = self.convert_to_shares_internal(assets, false)?;
+        self.deposit_internal(receiver, assets, shares)?;
+
+        Ok(shares)
+    }
+
+    pub fn mint(&mut self, shares: U256, receiver: Address) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        self.harvest_internal()?;
+
+        let assets = self.convert_to_assets_internal(shares, true)?;
+        self.deposit_internal(receiver, assets, shares)?;
+
+        Ok(assets)
+    }
+
+    pub fn withdraw(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        self.harvest_internal()?;
+
+        let shares = self.convert_to_shares_internal(assets, true)?;
+        self.withdraw_internal(receiver, owner, assets, shares)?;
+
+        Ok(shares)
+    }
+
+    pub fn redeem(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        self.harvest_internal()?;
+
+        let assets = self.convert_to_assets_internal(shares, false)?;
+        self.withdraw_internal(receiver, owner, assets, shares)?;
+
+        Ok(assets)
+    }
+
+    /// compounds the rewards of the vault, returns the amount of minted olp.
+    /// Anyone can harvest, weth is converted at most `HARVEST_MAX_SLIPPAGE_BPS` below its oracle value
+    pub fn harvest(&mut self) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        self.harvest_internal()
+    }
+}
@@ -9,6 +9,7 @@ use omx_common::{call_context::GetCallContext, safe_add, MIN_EXECUTION_FEE};
 use omx_interfaces::{
     erc20::{safe_transfer, safe_transfer_from},
     orderbook::{CancelIncreaseOrder, CreateIncreaseOrder, OrderbookError, RawIncreaseOrder},
+    vault::IPositionsManager,
 };
 use stylus_sdk::{call::transfer_eth, contract, evm, msg, prelude::*};
 
@@ -30,6 +31,8 @@ sol_storage! {
         mapping (address => mapping(uint256 =>  uint256)) order_execution_fee;
 
         mapping (address => uint256) orders_index;
+
+        address positions_manager;
     }
 }
 
@@ -42,6 +45,34 @@ impl OrderbookIncrease {
         Ok(())
     }
 
+    fn only_gov(&self) -> Result<(), OrderbookError> {
+        if self.gov.get() != msg::sender() {
+            return Err(OrderbookError::Forbidden);
+        }
+
+        Ok(())
+    }
+
+    /// orders that can not fit into the open interest cap of the market would never execute
+    fn validate_open_interest(
+        &self,
+        index_token: Address,
+        size_delta: U256,
+        is_long: bool,
+    ) -> Result<(), Vec<u8>> {
+        if self.positions_manager.get().is_zero() {
+            return Err(OrderbookError::PositionsManagerNotSet.into());
+        }
+
+        let available = IPositionsManager::new(self.positions_manager.get())
+            .available_open_interest(self, index_token, is_long)?;
+        if size_delta > available {
+            return Err(OrderbookError::MaxOpenInterestExceeded { available }.into());
+        }
+
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn create_order_internal(
         &mut self,
@@ -106,19 +137,33 @@ impl OrderbookIncrease {
 
 #[external]
 impl OrderbookIncrease {
-    pub fn init(&mut self, gov: Address, increase_router: Address) -> Result<(), Vec<u8>> {
+    pub fn init(
+        &mut self,
+        gov: Address,
+        increase_router: Address,
+        positions_manager: Address,
+    ) -> Result<(), Vec<u8>> {
         if self.initialized.get() {
             return Err(OrderbookError::AlreadyInitialized.into());
         }
 
         self.gov.set(gov);
         self.increase_router.set(increase_router);
+        self.positions_manager.set(positions_manager);
 
         self.initialized.set(true);
 
         Ok(())
     }
 
+    pub fn set_positions_manager(&mut self, positions_manager: Address) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.positions_manager.set(positions_manager);
+
+        Ok(())
+    }
+
     pub fn get_current_index(&self, account: Address) -> Result<U256, Vec<u8>> {
         Ok(self.orders_index.get(account))
     }
@@ -142,6 +187,8 @@ impl OrderbookIncrease {
             return Err(OrderbookError::ZeroCollateralAmount.into());
         }
 
+        self.validate_open_interest(index_token, size_delta, is_long)?;
+
         if execution_fee < MIN_EXECUTION_FEE {
             return Err(OrderbookError::InsufficientExecutionFee {
                 min_execution_fee: MIN_EXECUTION_FEE,
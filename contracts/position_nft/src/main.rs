@@ -0,0 +1,482 @@
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use omx_common::{call_context::GetCallContext, safe_add, safe_sub};
+use omx_interfaces::{
+    position_nft::{
+        Approval, ApprovalForAll, IErc721Receiver, PositionNftError, Transfer, UnwrapPosition,
+        WrapPosition,
+    },
+    vault::{get_position_key, position::Position, IPositionsManager, IVaultUtils},
+};
+use stylus_sdk::{abi::Bytes, contract, evm, msg, prelude::*};
+
+pub const NAME: &str = "OMX Position";
+pub const SYMBOL: &str = "OMXP";
+
+/// `bytes4(keccak256("onERC721Received(address,address,uint256,bytes)"))`
+pub const ERC721_RECEIVED: [u8; 4] = [0x15, 0x0b, 0x7a, 0x02];
+
+/// ERC-165 interface ids of ERC-165, ERC-721 and the ERC-721 metadata extension
+pub const INTERFACE_ID_ERC165: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+pub const INTERFACE_ID_ERC721: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+pub const INTERFACE_ID_ERC721_METADATA: [u8; 4] = [0x5b, 0x5e, 0x13, 0x9f];
+
+/// `keccak256("")`, the code hash of accounts without code
+pub const EMPTY_CODE_HASH: [u8; 32] = [
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+    0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+];
+
+sol_storage! {
+    #[entrypoint]
+    pub struct PositionNft {
+        bool initialized;
+
+        address gov;
+        address positions_manager;
+        address vault_utils;
+
+        string base_uri;
+
+        uint256 last_token_id;
+        uint256 total_supply;
+
+        mapping (uint256 => address) owners;
+        mapping (address => uint256) balances;
+        mapping (uint256 => address) token_approvals;
+        mapping (address => mapping (address => bool)) operator_approvals;
+
+        /// collateral_token, index_token and is_long of the wrapped position,
+        /// the position key is derived from the current token owner
+        mapping (uint256 => address) token_collateral_tokens;
+        mapping (uint256 => address) token_index_tokens;
+        mapping (uint256 => bool) token_is_long;
+
+        /// token id by position key, zero if the position is not wrapped
+        mapping (bytes32 => uint256) position_tokens;
+    }
+}
+
+impl PositionNft {
+    fn only_initialized(&self) -> Result<(), PositionNftError> {
+        if !self.initialized.get() {
+            return Err(PositionNftError::NotInitialized);
+        }
+
+        Ok(())
+    }
+
+    fn only_gov(&self) -> Result<(), PositionNftError> {
+        if self.gov.get() != msg::sender() {
+            return Err(PositionNftError::Forbidden);
+        }
+
+        Ok(())
+    }
+
+    fn owner_of_internal(&self, token_id: U256) -> Result<Address, PositionNftError> {
+        let owner = self.owners.get(token_id);
+        if owner.is_zero() {
+            return Err(PositionNftError::TokenNotFound);
+        }
+
+        Ok(owner)
+    }
+
+    fn token_key(&self, token_id: U256) -> Result<FixedBytes<32>, PositionNftError> {
+        Ok(get_position_key(
+            self.owner_of_internal(token_id)?,
+            self.token_collateral_tokens.get(token_id),
+            self.token_index_tokens.get(token_id),
+            self.token_is_long.get(token_id),
+        ))
+    }
+
+    fn position_of_token(&self, token_id: U256) -> Result<Position, Vec<u8>> {
+        let key = self.token_key(token_id)?;
+
+        Ok(IPositionsManager::new(self.positions_manager.get())
+            .position_by_key(self, key)?
+            .into())
+    }
+
+    fn is_approved_or_owner(&self, spender: Address, token_id: U256) -> Result<bool, Vec<u8>> {
+        let owner = self.owner_of_internal(token_id)?;
+
+        Ok(spender == owner
+            || self.token_approvals.get(token_id) == spender
+            || self.operator_approvals.getter(owner).get(spender))
+    }
+
+    fn transfer_internal(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_initialized()?;
+
+        if self.owner_of_internal(token_id)? != from {
+            return Err(PositionNftError::NotTokenOwner.into());
+        }
+        if to.is_zero() {
+            return Err(PositionNftError::TransferToZeroAddress.into());
+        }
+        if !self.is_approved_or_owner(msg::sender(), token_id)? {
+            return Err(PositionNftError::Forbidden.into());
+        }
+
+        // the position follows the token
+        let key = self.token_key(token_id)?;
+        let new_key = IPositionsManager::new(self.positions_manager.get()).transfer_position_from(
+            self.ctx(),
+            from,
+            key,
+            to,
+        )?;
+
+        self.position_tokens.delete(key);
+        self.position_tokens.insert(new_key, token_id);
+
+        self.token_approvals.delete(token_id);
+        self.balances
+            .insert(from, safe_sub(self.balances.get(from), U256::from(1))?);
+        self.balances
+            .insert(to, safe_add(self.balances.get(to), U256::from(1))?);
+        self.owners.insert(token_id, to);
+
+        evm::log(Transfer { from, to, token_id });
+
+        Ok(())
+    }
+
+    fn check_on_erc721_received(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        let code_hash = to.codehash();
+        if code_hash.is_zero() || code_hash == FixedBytes::from(EMPTY_CODE_HASH) {
+            return Ok(());
+        }
+
+        let operator = msg::sender();
+        let selector = IErc721Receiver::new(to)
+            .on_erc_721_received(self.ctx(), operator, from, token_id, data.0)
+            .map_err(|_| PositionNftError::InvalidReceiver)?;
+        if selector != FixedBytes::from(ERC721_RECEIVED) {
+            return Err(PositionNftError::InvalidReceiver.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[external]
+impl PositionNft {
+    pub fn init(
+        &mut self,
+        gov: Address,
+        positions_manager: Address,
+        vault_utils: Address,
+    ) -> Result<(), Vec<u8>> {
+        if self.initialized.get() {
+            return Err(PositionNftError::AlreadyInitialized.into());
+        }
+
+        self.gov.set(gov);
+        self.positions_manager.set(positions_manager);
+        self.vault_utils.set(vault_utils);
+
+        self.initialized.set(true);
+
+        Ok(())
+    }
+
+    pub fn set_gov(&mut self, gov: Address) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.gov.set(gov);
+
+        Ok(())
+    }
+
+    pub fn set_base_uri(&mut self, base_uri: String) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.base_uri.set_str(&base_uri);
+
+        Ok(())
+    }
+
+    pub fn name(&self) -> Result<String, Vec<u8>> {
+        Ok(NAME.into())
+    }
+
+    pub fn symbol(&self) -> Result<String, Vec<u8>> {
+        Ok(SYMBOL.into())
+    }
+
+    #[selector(name = "tokenURI")]
+    pub fn token_uri(&self, token_id: U256) -> Result<String, Vec<u8>> {
+        self.owner_of_internal(token_id)?;
+
+        let bytes = self.base_uri.0.get_bytes();
+        let base_uri = String::from_utf8_lossy(&bytes);
+
+        Ok(format!("{base_uri}{token_id}"))
+    }
+
+    pub fn total_supply(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.total_supply.get())
+    }
+
+    pub fn balance_of(&self, owner: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.balances.get(owner))
+    }
+
+    pub fn owner_of(&self, token_id: U256) -> Result<Address, Vec<u8>> {
+        Ok(self.owner_of_internal(token_id)?)
+    }
+
+    pub fn get_approved(&self, token_id: U256) -> Result<Address, Vec<u8>> {
+        self.owner_of_internal(token_id)?;
+
+        Ok(self.token_approvals.get(token_id))
+    }
+
+    pub fn is_approved_for_all(&self, owner: Address, operator: Address) -> Result<bool, Vec<u8>> {
+        Ok(self.operator_approvals.getter(owner).get(operator))
+    }
+
+    pub fn approve(&mut self, approved: Address, token_id: U256) -> Result<(), Vec<u8>> {
+        let owner = self.owner_of_internal(token_id)?;
+        if msg::sender() != owner && !self.operator_approvals.getter(owner).get(msg::sender()) {
+            return Err(PositionNftError::Forbidden.into());
+        }
+
+        self.token_approvals.insert(token_id, approved);
+
+        evm::log(Approval {
+            owner,
+            approved,
+            token_id,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Vec<u8>> {
+        let owner = msg::sender();
+
+        self.operator_approvals
+            .setter(owner)
+            .insert(operator, approved);
+
+        evm::log(ApprovalForAll {
+            owner,
+            operator,
+            approved,
+        });
+
+        Ok(())
+    }
+
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.transfer_internal(from, to, token_id)
+    }
+
+    pub fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.transfer_internal(from, to, token_id)?;
+        self.check_on_erc721_received(from, to, token_id, data)?;
+
+        Ok(())
+    }
+
+    /// `safeTransferFrom` overload without data
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from_without_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.safe_transfer_from(from, to, token_id, Bytes(vec![]))
+    }
+
+    pub fn supports_interface(&self, interface_id: FixedBytes<4>) -> Result<bool, Vec<u8>> {
+        Ok([
+            INTERFACE_ID_ERC165,
+            INTERFACE_ID_ERC721,
+            INTERFACE_ID_ERC721_METADATA,
+        ]
+        .contains(&interface_id.0))
+    }
+
+    /// mint a token for the position of the sender, the sender must approve this contract
+    /// for the position with `PositionsManager::approve_position` beforehand,
+    /// the position is locked to this contract until the token is burned
+    pub fn wrap(
+        &mut self,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        let account = msg::sender();
+        let positions_manager = IPositionsManager::new(self.positions_manager.get());
+
+        let key = get_position_key(account, collateral_token, index_token, is_long);
+        let position: Position = positions_manager.position_by_key(self.ctx(), key)?.into();
+        if position.size == U256::ZERO {
+            return Err(PositionNftError::PositionNotFound.into());
+        }
+        if self.position_tokens.get(key) != U256::ZERO {
+            return Err(PositionNftError::PositionAlreadyWrapped.into());
+        }
+        if positions_manager.position_approval(self.ctx(), key)? != contract::address() {
+            return Err(PositionNftError::OperatorNotApproved.into());
+        }
+        positions_manager.lock_position(self.ctx(), key)?;
+
+        let token_id = safe_add(self.last_token_id.get(), U256::from(1))?;
+        self.last_token_id.set(token_id);
+
+        self.token_collateral_tokens
+            .insert(token_id, collateral_token);
+        self.token_index_tokens.insert(token_id, index_token);
+        self.token_is_long.insert(token_id, is_long);
+        self.position_tokens.insert(key, token_id);
+
+        self.owners.insert(token_id, account);
+        self.balances.insert(
+            account,
+            safe_add(self.balances.get(account), U256::from(1))?,
+        );
+        self.total_supply
+            .set(safe_add(self.total_supply.get(), U256::from(1))?);
+
+        evm::log(Transfer {
+            from: Address::ZERO,
+            to: account,
+            token_id,
+        });
+        evm::log(WrapPosition {
+            account,
+            token_id,
+            collateral_token,
+            index_token,
+            is_long,
+        });
+
+        Ok(token_id)
+    }
+
+    /// burn the token, the position stays with the token owner and is unlocked,
+    /// which also revokes the approval of this contract for the position
+    pub fn unwrap(&mut self, token_id: U256) -> Result<(), Vec<u8>> {
+        let owner = self.owner_of_internal(token_id)?;
+        if owner != msg::sender() {
+            return Err(PositionNftError::NotTokenOwner.into());
+        }
+
+        let key = self.token_key(token_id)?;
+        if self.position_tokens.get(key) == token_id {
+            self.position_tokens.delete(key);
+            IPositionsManager::new(self.positions_manager.get())
+                .unlock_position(self.ctx(), key)?;
+        }
+
+        self.token_collateral_tokens.delete(token_id);
+        self.token_index_tokens.delete(token_id);
+        self.token_is_long.delete(token_id);
+        self.token_approvals.delete(token_id);
+
+        self.owners.delete(token_id);
+        self.balances
+            .insert(owner, safe_sub(self.balances.get(owner), U256::from(1))?);
+        self.total_supply
+            .set(safe_sub(self.total_supply.get(), U256::from(1))?);
+
+        evm::log(Transfer {
+            from: owner,
+            to: Address::ZERO,
+            token_id,
+        });
+        evm::log(UnwrapPosition {
+            account: owner,
+            token_id,
+        });
+
+        Ok(())
+    }
+
+    pub fn token_of_position(&self, key: FixedBytes<32>) -> Result<U256, Vec<u8>> {
+        Ok(self.position_tokens.get(key))
+    }
+
+    /// returns position key, collateral_token, index_token and is_long of the token
+    pub fn token_position(
+        &self,
+        token_id: U256,
+    ) -> Result<(FixedBytes<32>, Address, Address, bool), Vec<u8>> {
+        Ok((
+            self.token_key(token_id)?,
+            self.token_collateral_tokens.get(token_id),
+            self.token_index_tokens.get(token_id),
+            self.token_is_long.get(token_id),
+        ))
+    }
+
+    pub fn position_size(&self, token_id: U256) -> Result<U256, Vec<u8>> {
+        Ok(self.position_of_token(token_id)?.size)
+    }
+
+    pub fn position_collateral(&self, token_id: U256) -> Result<U256, Vec<u8>> {
+        Ok(self.position_of_token(token_id)?.collateral)
+    }
+
+    pub fn position_entry_price(&self, token_id: U256) -> Result<U256, Vec<u8>> {
+        Ok(self.position_of_token(token_id)?.average_price)
+    }
+
+    /// returns has_profit and the unrealised pnl of the position in usd
+    pub fn position_pnl(&self, token_id: U256) -> Result<(bool, U256), Vec<u8>> {
+        let position = self.position_of_token(token_id)?;
+        if position.size == U256::ZERO {
+            return Ok((false, U256::ZERO));
+        }
+
+        Ok(IVaultUtils::new(self.vault_utils.get()).get_delta(
+            self,
+            self.token_index_tokens.get(token_id),
+            position.size,
+            position.average_price,
+            self.token_is_long.get(token_id),
+            position.last_increased_time,
+        )?)
+    }
+}
@@ -5,11 +5,18 @@ extern crate alloc;
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 use alloy_primitives::{Address, I256, U256};
-use omx_common::{call_context::GetCallContext, safe_mul_ratio, safe_sub, safe_sub_to_int};
+use omx_common::{
+    call_context::GetCallContext, safe_add, safe_mul_ratio, safe_sub, safe_sub_to_int,
+};
 use omx_interfaces::vault::{
-    position::{validate_position, Position},
+    get_position_key,
+    position::{
+        get_decrease_size_delta, get_execution_price, validate_min_position, validate_position,
+        Position,
+    },
     validate, ClosePosition, DecreasePosition, IFundingRateManager, IPositionsManager,
-    IPositionsManagerUtils, IVault, UpdatePosition, VaultError,
+    IPositionsManagerUtils, IVault, TokenInfo, UpdatePosition, VaultError, WithdrawCollateral,
+    WithdrawMargin,
 };
 use stylus_sdk::{evm, msg, prelude::*};
 
@@ -54,9 +61,43 @@ impl PositionsDecreaseManager {
             return Ok(());
         }
 
+        let is_operator = IPositionsManager::new(self.positions_manager.get())
+            .is_position_operator(self, account, msg::sender())?;
+        validate(is_operator, VaultError::Forbidden)?;
+
         Ok(())
     }
 
+    /// besides the operators of the account, the operator approved for the position can manage it
+    fn validate_position_router(
+        &self,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<(), Vec<u8>> {
+        let key = get_position_key(account, collateral_token, index_token, is_long);
+        let approved =
+            IPositionsManager::new(self.positions_manager.get()).position_approval(self, key)?;
+        if approved != Address::ZERO && approved == msg::sender() {
+            return Ok(());
+        }
+
+        self.validate_router(account)
+    }
+
+    /// operators can not redirect the proceeds, they are always sent to the owner
+    fn validate_receiver(&self, account: Address, receiver: Address) -> Address {
+        if msg::sender() == account
+            || msg::sender() == self.decrease_router.get()
+            || msg::sender() == self.positions_liquidation_manager.get()
+        {
+            return receiver;
+        }
+
+        account
+    }
+
     fn update_guaranteed_usd(&mut self, token: Address, value: I256) -> Result<(), Vec<u8>> {
         Ok(IPositionsManager::new(self.positions_manager.get())
             .update_guaranteed_usd(self, token, value)?)
@@ -66,28 +107,26 @@ impl PositionsDecreaseManager {
         Ok(IVault::new(self.vault.get()).get_price(self, token)?)
     }
 
-    fn token_decimals(&self, token: Address) -> Result<u8, Vec<u8>> {
-        Ok(IVault::new(self.vault.get()).get_token_decimals(self, token)?)
+    fn token_info(&self, token: Address) -> Result<TokenInfo, Vec<u8>> {
+        Ok(IVault::new(self.vault.get())
+            .get_token_info(self, token)?
+            .into())
     }
 
-    fn cumulative_funding_rate(&self, token: Address) -> Result<U256, Vec<u8>> {
+    /// returns the updated cumulative funding rate of the token
+    fn update_cumulative_funding_rate(&mut self, token: Address) -> Result<U256, Vec<u8>> {
         Ok(IFundingRateManager::new(self.funding_rate_manager.get())
-            .cumulative_funding_rate(self, token)?)
+            .update_cumulative_funding_rate(self, token)?)
     }
 
-    fn usd_to_token(&self, token: Address, usd_amount: U256) -> Result<U256, Vec<u8>> {
-        if usd_amount == U256::ZERO {
-            return Ok(U256::ZERO);
-        }
-
-        let price = self.get_price(token)?;
-        let decimals = self.token_decimals(token)?;
+    fn decrease_pool_amount(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
+        IVault::new(self.vault.get()).decrease_pool_amount(self, token, amount)?;
 
-        safe_mul_ratio(usd_amount, U256::from(10).pow(U256::from(decimals)), price)
+        Ok(())
     }
 
-    fn decrease_pool_amount(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
-        IVault::new(self.vault.get()).decrease_pool_amount(self, token, amount)?;
+    fn increase_pool_amount(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
+        IVault::new(self.vault.get()).increase_pool_amount(self, token, amount)?;
 
         Ok(())
     }
@@ -196,38 +235,40 @@ impl PositionsDecreaseManager {
     ) -> Result<U256, Vec<u8>> {
         self.only_initialized()?;
 
-        self.validate_router(account)
+        self.validate_position_router(account, collateral_token, index_token, is_long)
             .or_else(|_| self.only_liquidation_manager())?;
+        let receiver = self.validate_receiver(account, receiver);
 
-        let positions_manager = IPositionsManager::new(self.positions_manager.get());
-
-        let (reserve_amount, reserve_delta, collateral) = {
-            let position = self.position(account, collateral_token, index_token, is_long)?;
-            validate(position.size > U256::ZERO, VaultError::ZeroSize)?;
-            validate(position.size >= size_delta, VaultError::SizeLessThenDelta)?;
-            validate(
-                position.collateral >= collateral_delta,
-                VaultError::CollateralLessThenDelta,
-            )?;
-
-            let collateral = position.collateral;
-            let reserve_delta = safe_mul_ratio(position.reserve_amount, size_delta, position.size)?;
+        let entry_funding_rate = self.update_cumulative_funding_rate(collateral_token)?;
 
-            let reserve_amount = safe_sub(position.reserve_amount, reserve_delta)?;
-
-            (reserve_amount, reserve_delta, collateral)
-        };
+        let positions_manager = IPositionsManager::new(self.positions_manager.get());
 
         let mut position = self.position(account, collateral_token, index_token, is_long)?;
-        position.reserve_amount = reserve_amount;
+        validate(position.size > U256::ZERO, VaultError::ZeroSize)?;
+        validate(position.size >= size_delta, VaultError::SizeLessThenDelta)?;
+        validate(
+            position.collateral >= collateral_delta,
+            VaultError::CollateralLessThenDelta,
+        )?;
 
-        self.position_update(account, collateral_token, index_token, is_long, position)?;
+        let (min_size, min_collateral) = positions_manager.min_position(self.ctx(), index_token)?;
+        let size_delta = get_decrease_size_delta(
+            &position,
+            collateral_delta,
+            size_delta,
+            min_size,
+            min_collateral,
+        );
+
+        let reserve_delta = safe_mul_ratio(position.reserve_amount, size_delta, position.size)?;
+        let collateral = position.collateral;
 
         self.decrease_reserved_amount(collateral_token, reserve_delta)?;
 
         let positions_manager_utils =
             IPositionsManagerUtils::new(self.positions_manager_utils.get());
-        let (usd_out, usd_out_after_fee) = positions_manager_utils.reduce_collateral(
+        let (usd_out, usd_out_after_fee, next_collateral, realised_pnl) = positions_manager_utils
+            .reduce_collateral(
             self.ctx(),
             account,
             collateral_token,
@@ -237,37 +278,69 @@ impl PositionsDecreaseManager {
             is_long,
         )?;
 
-        let mut position = self.position(account, collateral_token, index_token, is_long)?;
+        // reduce_collateral only returns the settled values, the position is written once below
+        position.collateral = next_collateral;
+        position.realised_pnl = realised_pnl;
+        position.reserve_amount = safe_sub(position.reserve_amount, reserve_delta)?;
+
+        let collateral_info = self.token_info(collateral_token)?;
+        let price = if index_token == collateral_token {
+            collateral_info.price
+        } else {
+            self.get_price(index_token)?
+        };
+
+        // the price impact is settled with the payout, so the charge can not exceed it
+        let price_impact = positions_manager.apply_price_impact(
+            self.ctx(),
+            index_token,
+            collateral_token,
+            is_long,
+            size_delta,
+            false,
+            usd_out_after_fee,
+            collateral_info.price,
+            collateral_info.decimals,
+        )?;
+        let (usd_out, usd_out_after_fee) = if price_impact.is_negative() {
+            (
+                safe_add(usd_out, price_impact.unsigned_abs())?,
+                safe_add(usd_out_after_fee, price_impact.unsigned_abs())?,
+            )
+        } else {
+            (
+                safe_sub(usd_out, price_impact.unsigned_abs())?,
+                safe_sub(usd_out_after_fee, price_impact.unsigned_abs())?,
+            )
+        };
+        let execution_price = get_execution_price(price, size_delta, price_impact, !is_long)?;
 
         if position.size != size_delta {
-            position.entry_funding_rate = self.cumulative_funding_rate(collateral_token)?;
+            position.entry_funding_rate = entry_funding_rate;
             position.size = safe_sub(position.size, size_delta)?;
 
-            self.position_update(account, collateral_token, index_token, is_long, position)?;
+            validate_position(position.size, position.collateral)?;
+            // the estimate of get_decrease_size_delta ignores fees and losses,
+            // they can still push the settled collateral below the minimum
+            validate_min_position(position.size, position.collateral, min_size, min_collateral)?;
 
-            let collateral = {
-                let position = self.position(account, collateral_token, index_token, is_long)?;
-                let collateral = position.collateral;
-                validate_position(position.size, collateral)?;
-                positions_manager_utils.validate_liquidation(
-                    self.ctx(),
-                    account,
-                    collateral_token,
-                    index_token,
-                    is_long,
-                    true,
-                )?;
+            self.position_update(account, collateral_token, index_token, is_long, position)?;
 
-                collateral
-            };
+            positions_manager_utils.validate_liquidation(
+                self.ctx(),
+                account,
+                collateral_token,
+                index_token,
+                is_long,
+                true,
+            )?;
 
             if is_long {
-                let value = safe_sub_to_int(safe_sub(collateral, collateral)?, size_delta)?;
+                let value =
+                    safe_sub_to_int(safe_sub(collateral, position.collateral)?, size_delta)?;
                 self.update_guaranteed_usd(collateral_token, value)?;
             }
-            let position = self.position(account, collateral_token, index_token, is_long)?;
 
-            let price = self.get_price(index_token)?;
             evm::log(DecreasePosition {
                 account,
                 collateral_token,
@@ -278,6 +351,8 @@ impl PositionsDecreaseManager {
                 price,
                 fee: safe_sub(usd_out, usd_out_after_fee)?,
                 usd_out,
+                price_impact,
+                execution_price,
             });
             evm::log(UpdatePosition {
                 size: position.size,
@@ -296,8 +371,6 @@ impl PositionsDecreaseManager {
                 )?;
             }
 
-            let price = self.get_price(index_token)?;
-
             evm::log(DecreasePosition {
                 account,
                 collateral_token,
@@ -308,6 +381,8 @@ impl PositionsDecreaseManager {
                 price,
                 fee: safe_sub(usd_out, usd_out_after_fee)?,
                 usd_out,
+                price_impact,
+                execution_price,
             });
             evm::log(ClosePosition {
                 size: position.size,
@@ -327,7 +402,9 @@ impl PositionsDecreaseManager {
             )?;
         }
 
-        if !is_long {
+        if is_long {
+            positions_manager.decrease_global_long_size(self.ctx(), index_token, size_delta)?;
+        } else {
             positions_manager.decrease_global_short_size(self.ctx(), index_token, size_delta)?;
         }
 
@@ -335,14 +412,168 @@ impl PositionsDecreaseManager {
             if is_long {
                 self.decrease_pool_amount(
                     collateral_token,
-                    self.usd_to_token(collateral_token, usd_out)?,
+                    collateral_info.usd_to_token(usd_out)?,
+                )?;
+            } else if price_impact.is_negative() {
+                self.decrease_pool_amount(
+                    collateral_token,
+                    collateral_info.usd_to_token(price_impact.unsigned_abs())?,
+                )?;
+            } else if price_impact > I256::ZERO {
+                // the charged impact of shorts stays in the vault as part of the pool
+                self.increase_pool_amount(
+                    collateral_token,
+                    collateral_info.usd_to_token(price_impact.unsigned_abs())?,
                 )?;
             }
-            let amount_out_after_fees = self.usd_to_token(collateral_token, usd_out_after_fee)?;
+            let amount_out_after_fees = collateral_info.usd_to_token(usd_out_after_fee)?;
             self.transfer_out(collateral_token, amount_out_after_fees, receiver)?;
             return Ok(amount_out_after_fees);
         }
 
         Ok(U256::ZERO)
     }
+
+    /// withdraw collateral from a position without changing its size,
+    /// only the accrued funding fee is charged
+    pub fn withdraw_collateral(
+        &mut self,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        collateral_delta: U256,
+        is_long: bool,
+        receiver: Address,
+    ) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        self.validate_position_router(account, collateral_token, index_token, is_long)?;
+        let receiver = self.validate_receiver(account, receiver);
+
+        let entry_funding_rate = self.update_cumulative_funding_rate(collateral_token)?;
+
+        let mut position = self.position(account, collateral_token, index_token, is_long)?;
+        validate(position.size > U256::ZERO, VaultError::ZeroSize)?;
+        validate(collateral_delta > U256::ZERO, VaultError::ZeroAmount)?;
+        validate(
+            position.collateral >= collateral_delta,
+            VaultError::CollateralLessThenDelta,
+        )?;
+        let collateral = position.collateral;
+
+        let positions_manager_utils =
+            IPositionsManagerUtils::new(self.positions_manager_utils.get());
+        let (usd_out, usd_out_after_fee, next_collateral, realised_pnl) = positions_manager_utils
+            .reduce_collateral(
+            self.ctx(),
+            account,
+            collateral_token,
+            index_token,
+            collateral_delta,
+            U256::ZERO,
+            is_long,
+        )?;
+
+        position.collateral = next_collateral;
+        position.realised_pnl = realised_pnl;
+        position.entry_funding_rate = entry_funding_rate;
+
+        validate_position(position.size, position.collateral)?;
+
+        let (min_size, min_collateral) = IPositionsManager::new(self.positions_manager.get())
+            .min_position(self.ctx(), index_token)?;
+        validate_min_position(position.size, position.collateral, min_size, min_collateral)?;
+
+        self.position_update(account, collateral_token, index_token, is_long, position)?;
+
+        positions_manager_utils.validate_liquidation(
+            self.ctx(),
+            account,
+            collateral_token,
+            index_token,
+            is_long,
+            true,
+        )?;
+
+        let collateral_info = self.token_info(collateral_token)?;
+        if is_long {
+            // `position.size - position.collateral` increases by the reduced collateral
+            self.update_guaranteed_usd(
+                collateral_token,
+                safe_sub_to_int(collateral, position.collateral)?,
+            )?;
+            self.decrease_pool_amount(collateral_token, collateral_info.usd_to_token(usd_out)?)?;
+        }
+
+        let price = if index_token == collateral_token {
+            collateral_info.price
+        } else {
+            self.get_price(index_token)?
+        };
+        evm::log(WithdrawCollateral {
+            account,
+            collateral_token,
+            index_token,
+            is_long,
+            collateral_delta,
+            fee: safe_sub(usd_out, usd_out_after_fee)?,
+        });
+        evm::log(UpdatePosition {
+            size: position.size,
+            collateral: position.collateral,
+            average_price: position.average_price,
+            entry_funding_rate: position.entry_funding_rate,
+            reserve_amount: position.reserve_amount,
+            realised_pnl: position.realised_pnl,
+            mark_price: price,
+        });
+
+        let amount_out_after_fees = collateral_info.usd_to_token(usd_out_after_fee)?;
+        self.transfer_out(collateral_token, amount_out_after_fees, receiver)?;
+
+        Ok(amount_out_after_fees)
+    }
+
+    /// withdraw `amount` of collateral tokens from the cross-margin account of `account`,
+    /// the remaining account equity must still satisfy the maintenance margin
+    pub fn withdraw_margin(
+        &mut self,
+        account: Address,
+        collateral_token: Address,
+        amount: U256,
+        receiver: Address,
+    ) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        self.validate_router(account)?;
+        let receiver = self.validate_receiver(account, receiver);
+
+        validate(amount > U256::ZERO, VaultError::ZeroAmount)?;
+
+        self.update_cumulative_funding_rate(collateral_token)?;
+
+        IPositionsManager::new(self.positions_manager.get()).decrease_cross_margin_balance(
+            self.ctx(),
+            account,
+            collateral_token,
+            amount,
+        )?;
+
+        IPositionsManagerUtils::new(self.positions_manager_utils.get()).validate_cross_margin(
+            self.ctx(),
+            account,
+            collateral_token,
+            true,
+        )?;
+
+        self.transfer_out(collateral_token, amount, receiver)?;
+
+        evm::log(WithdrawMargin {
+            account,
+            collateral_token,
+            amount,
+        });
+
+        Ok(amount)
+    }
 }
@@ -74,6 +74,31 @@ impl PositionsDecreaseRouter {
         Ok(amount_out)
     }
 
+    fn withdraw_collateral_internal(
+        &mut self,
+        collateral_token: Address,
+        index_token: Address,
+        collateral_delta: U256,
+        is_long: bool,
+        receiver: Address,
+    ) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        let decrease_manager =
+            IPositionsDecreaseManager::new(self.positions_decrease_manager.get());
+        let amount_out = decrease_manager.withdraw_collateral(
+            self.ctx(),
+            msg::sender(),
+            collateral_token,
+            index_token,
+            collateral_delta,
+            is_long,
+            receiver,
+        )?;
+
+        Ok(amount_out)
+    }
+
     fn transfer_out_eth(&mut self, amount_out: U256, receiver: Address) -> Result<(), Vec<u8>> {
         let weth = self.weth.get();
         IWeth::new(weth).withdraw(self, receiver, amount_out)?;
@@ -153,4 +178,63 @@ impl PositionsDecreaseRouter {
 
         Ok(amount_out)
     }
+
+    pub fn withdraw_collateral(
+        &mut self,
+        collateral_token: Address,
+        index_token: Address,
+        collateral_delta: U256,
+        is_long: bool,
+        receiver: Address,
+    ) -> Result<U256, Vec<u8>> {
+        self.withdraw_collateral_internal(
+            collateral_token,
+            index_token,
+            collateral_delta,
+            is_long,
+            receiver,
+        )
+    }
+
+    pub fn withdraw_collateral_eth(
+        &mut self,
+        collateral_token: Address,
+        index_token: Address,
+        collateral_delta: U256,
+        is_long: bool,
+        receiver: Address,
+    ) -> Result<U256, Vec<u8>> {
+        let amount_out = self.withdraw_collateral_internal(
+            collateral_token,
+            index_token,
+            collateral_delta,
+            is_long,
+            contract::address(),
+        )?;
+
+        self.transfer_out_eth(amount_out, receiver)?;
+
+        Ok(amount_out)
+    }
+
+    pub fn withdraw_margin(
+        &mut self,
+        collateral_token: Address,
+        amount: U256,
+        receiver: Address,
+    ) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        let decrease_manager =
+            IPositionsDecreaseManager::new(self.positions_decrease_manager.get());
+        let amount_out = decrease_manager.withdraw_margin(
+            self.ctx(),
+            msg::sender(),
+            collateral_token,
+            amount,
+            receiver,
+        )?;
+
+        Ok(amount_out)
+    }
 }
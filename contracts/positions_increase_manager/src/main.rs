@@ -7,9 +7,11 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 use alloy_primitives::{Address, I256, U256};
 use omx_common::{call_context::GetCallContext, safe_add, safe_sub, safe_sub_to_int};
 use omx_interfaces::vault::{
-    position::{validate_position, Position},
-    validate, IFeeManager, IFundingRateManager, IPositionsManager, IPositionsManagerUtils, IVault,
-    IVaultUtils, IncreasePosition, UpdatePosition, VaultError,
+    get_position_key,
+    position::{get_execution_price, validate_min_position, validate_position, Position},
+    validate, DepositCollateral, DepositMargin, IFeeManager, IFundingRateManager,
+    IPositionsManager, IPositionsManagerUtils, IVault, IVaultUtils, IncreasePosition, TokenInfo,
+    UpdatePosition, VaultError,
 };
 use stylus_sdk::{block, evm, msg, prelude::*};
 
@@ -47,9 +49,31 @@ impl PositionsIncreaseManager {
             return Ok(());
         }
 
+        let is_operator = IPositionsManager::new(self.positions_manager.get())
+            .is_position_operator(self, account, msg::sender())?;
+        validate(is_operator, VaultError::Forbidden)?;
+
         Ok(())
     }
 
+    /// besides the operators of the account, the operator approved for the position can manage it
+    fn validate_position_router(
+        &self,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<(), Vec<u8>> {
+        let key = get_position_key(account, collateral_token, index_token, is_long);
+        let approved =
+            IPositionsManager::new(self.positions_manager.get()).position_approval(self, key)?;
+        if approved != Address::ZERO && approved == msg::sender() {
+            return Ok(());
+        }
+
+        self.validate_router(account)
+    }
+
     fn update_guaranteed_usd_internal(
         &mut self,
         token: Address,
@@ -59,16 +83,29 @@ impl PositionsIncreaseManager {
             .update_guaranteed_usd(self, token, value)?)
     }
 
-    fn update_cumulative_funding_rate(&mut self, token: Address) -> Result<(), Vec<u8>> {
-        IFundingRateManager::new(self.funding_rate_manager.get())
-            .update_cumulative_funding_rate(self, token)?;
+    /// returns the updated cumulative funding rate of the token
+    fn update_cumulative_funding_rate(&mut self, token: Address) -> Result<U256, Vec<u8>> {
+        Ok(IFundingRateManager::new(self.funding_rate_manager.get())
+            .update_cumulative_funding_rate(self, token)?)
+    }
 
-        Ok(())
+    fn token_info(&self, token: Address) -> Result<TokenInfo, Vec<u8>> {
+        Ok(IVault::new(self.vault.get())
+            .get_token_info(self, token)?
+            .into())
     }
 
-    fn cumulative_funding_rate(&self, token: Address) -> Result<U256, Vec<u8>> {
-        Ok(IFundingRateManager::new(self.funding_rate_manager.get())
-            .cumulative_funding_rate(self, token)?)
+    fn index_price(
+        &self,
+        index_token: Address,
+        collateral_token: Address,
+        collateral_info: &TokenInfo,
+    ) -> Result<U256, Vec<u8>> {
+        if index_token == collateral_token {
+            return Ok(collateral_info.price);
+        }
+
+        Ok(IVault::new(self.vault.get()).get_price(self, index_token)?)
     }
 
     fn increase_pool_amount(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
@@ -138,10 +175,6 @@ impl PositionsIncreaseManager {
 
         Ok(())
     }
-
-    fn usd_to_token(&self, token: Address, usd_amount: U256) -> Result<U256, Vec<u8>> {
-        Ok(IVaultUtils::new(self.vault_utils.get()).usd_to_token(self, token, usd_amount)?)
-    }
 }
 
 #[external]
@@ -192,7 +225,7 @@ impl PositionsIncreaseManager {
     ) -> Result<(), Vec<u8>> {
         self.only_initialized()?;
 
-        self.validate_router(account)?;
+        self.validate_position_router(account, collateral_token, index_token, is_long)?;
 
         let positions_manager = IPositionsManager::new(self.positions_manager.get());
 
@@ -203,17 +236,14 @@ impl PositionsIncreaseManager {
 
         vault_utils.validate_tokens(self.ctx(), collateral_token, index_token, is_long)?;
 
-        self.update_cumulative_funding_rate(collateral_token)?;
+        let entry_funding_rate = self.update_cumulative_funding_rate(collateral_token)?;
 
         let mut position = self.position(account, collateral_token, index_token, is_long)?;
 
-        let price = vault.get_price(self.ctx(), index_token)?;
+        let collateral_info = self.token_info(collateral_token)?;
+        let price = self.index_price(index_token, collateral_token, &collateral_info)?;
         position.average_price = price;
 
-        if position.size == U256::ZERO {
-            self.position_update(account, collateral_token, index_token, is_long, position)?;
-        }
-
         if position.size > U256::ZERO && size_delta > U256::ZERO {
             position.average_price = positions_manager_utils.get_next_average_price(
                 self.ctx(),
@@ -225,7 +255,6 @@ impl PositionsIncreaseManager {
                 size_delta,
                 position.last_increased_time,
             )?;
-            self.position_update(account, collateral_token, index_token, is_long, position)?;
         }
 
         let fee = self.collect_margin_fees(
@@ -236,23 +265,49 @@ impl PositionsIncreaseManager {
         )?;
 
         let collateral_delta = vault.transfer_in(self.ctx(), collateral_token)?;
-        let collateral_delta_usd =
-            vault_utils.token_to_usd(self.ctx(), collateral_token, collateral_delta)?;
+        let collateral_delta_usd = collateral_info.token_to_usd(collateral_delta)?;
 
         position.collateral = safe_sub(safe_add(position.collateral, collateral_delta_usd)?, fee)
             .map_err(|_| VaultError::CollateralLessThenFees)?;
 
-        position.entry_funding_rate = self.cumulative_funding_rate(collateral_token)?;
+        // a trade that increases the open interest skew is charged from the collateral,
+        // a trade that reduces it gets a rebate from the impact pool
+        let price_impact = positions_manager.apply_price_impact(
+            self.ctx(),
+            index_token,
+            collateral_token,
+            is_long,
+            size_delta,
+            true,
+            U256::MAX,
+            collateral_info.price,
+            collateral_info.decimals,
+        )?;
+        position.collateral = if price_impact.is_negative() {
+            safe_add(position.collateral, price_impact.unsigned_abs())?
+        } else {
+            safe_sub(position.collateral, price_impact.unsigned_abs())
+                .map_err(|_| VaultError::CollateralLessThenFees)?
+        };
+
+        position.entry_funding_rate = entry_funding_rate;
 
         position.size = safe_add(position.size, size_delta)?;
         validate(position.size > U256::ZERO, VaultError::ZeroSize)?;
 
         position.last_increased_time = U256::from(block::timestamp());
 
-        self.position_update(account, collateral_token, index_token, is_long, position)?;
+        // reserve tokens to pay profits on the position
+        let reserve_delta = collateral_info.usd_to_token(size_delta)?;
+        position.reserve_amount = safe_add(position.reserve_amount, reserve_delta)?;
 
         validate_position(position.size, position.collateral)?;
 
+        let (min_size, min_collateral) = positions_manager.min_position(self.ctx(), index_token)?;
+        validate_min_position(position.size, position.collateral, min_size, min_collateral)?;
+
+        self.position_update(account, collateral_token, index_token, is_long, position)?;
+
         positions_manager_utils.validate_liquidation(
             self.ctx(),
             account,
@@ -262,28 +317,36 @@ impl PositionsIncreaseManager {
             true,
         )?;
 
-        // reserve tokens to pay profits on the position
-        let reserve_delta = self.usd_to_token(collateral_token, size_delta)?;
-        position.reserve_amount = safe_add(position.reserve_amount, reserve_delta)?;
-
-        self.position_update(account, collateral_token, index_token, is_long, position)?;
-
         vault.increase_reserved_amount(self.ctx(), collateral_token, reserve_delta)?;
 
         if is_long {
             // guaranteed_usd stores the sum of `position.size - position.collateral` for all positions
             // if a fee is charged on the collateral then guaranteed_usd should be increased by that fee amount
             // since `position.size - position.collateral` would have increased by `fee`
+            // the same applies to the charged price impact, rebates decrease it
             self.update_guaranteed_usd_internal(
                 collateral_token,
-                safe_sub_to_int(safe_add(size_delta, fee)?, collateral_delta_usd)?,
+                safe_sub_to_int(safe_add(size_delta, fee)?, collateral_delta_usd)?
+                    .checked_add(price_impact)
+                    .ok_or(VaultError::PositionOverflow)?,
             )?;
             // treat the deposited collateral as part of the pool
             self.increase_pool_amount(collateral_token, collateral_delta)?;
             // fees need to be deducted from the pool since fees are deducted from `position.collateral`
             // and collateral is treated as part of the pool
-            self.decrease_pool_amount(collateral_token, self.usd_to_token(collateral_token, fee)?)?;
+            self.decrease_pool_amount(collateral_token, collateral_info.usd_to_token(fee)?)?;
+
+            positions_manager.after_long_increase(self.ctx(), index_token, price, size_delta)?;
         } else {
+            // short collateral is not part of the pool, so the charged impact is moved to the pool
+            // and the rebate is paid from it
+            let price_impact_amount = collateral_info.usd_to_token(price_impact.unsigned_abs())?;
+            if price_impact.is_negative() {
+                self.decrease_pool_amount(collateral_token, price_impact_amount)?;
+            } else if price_impact_amount > U256::ZERO {
+                self.increase_pool_amount(collateral_token, price_impact_amount)?;
+            }
+
             positions_manager.after_short_increase(self.ctx(), index_token, price, size_delta)?;
         }
 
@@ -296,6 +359,10 @@ impl PositionsIncreaseManager {
             is_long,
             price,
             fee,
+            price_impact,
+            // the impact is settled with the collateral, so the average price stays at the
+            // oracle price and the execution price only reports the effective price of the trade
+            execution_price: get_execution_price(price, size_delta, price_impact, is_long)?,
         });
         evm::log(UpdatePosition {
             size: position.size,
@@ -309,4 +376,128 @@ impl PositionsIncreaseManager {
 
         Ok(())
     }
+
+    /// add collateral to an existing position without changing its size,
+    /// only the accrued funding fee is charged
+    pub fn deposit_collateral(
+        &mut self,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<(), Vec<u8>> {
+        self.only_initialized()?;
+
+        self.validate_position_router(account, collateral_token, index_token, is_long)?;
+
+        let positions_manager_utils =
+            IPositionsManagerUtils::new(self.positions_manager_utils.get());
+        let vault = IVault::new(self.vault.get());
+
+        let entry_funding_rate = self.update_cumulative_funding_rate(collateral_token)?;
+
+        let mut position = self.position(account, collateral_token, index_token, is_long)?;
+        validate(position.size > U256::ZERO, VaultError::ZeroSize)?;
+
+        let fee = self.collect_margin_fees(
+            collateral_token,
+            U256::ZERO,
+            position.size,
+            position.entry_funding_rate,
+        )?;
+
+        let collateral_delta = vault.transfer_in(self.ctx(), collateral_token)?;
+        validate(collateral_delta > U256::ZERO, VaultError::ZeroAmount)?;
+        let collateral_info = self.token_info(collateral_token)?;
+        let collateral_delta_usd = collateral_info.token_to_usd(collateral_delta)?;
+
+        position.collateral = safe_sub(safe_add(position.collateral, collateral_delta_usd)?, fee)
+            .map_err(|_| VaultError::CollateralLessThenFees)?;
+        position.entry_funding_rate = entry_funding_rate;
+
+        validate_position(position.size, position.collateral)?;
+
+        self.position_update(account, collateral_token, index_token, is_long, position)?;
+
+        positions_manager_utils.validate_liquidation(
+            self.ctx(),
+            account,
+            collateral_token,
+            index_token,
+            is_long,
+            true,
+        )?;
+
+        if is_long {
+            // `position.size - position.collateral` decreases by the deposited amount
+            // and increases by the charged funding fee
+            self.update_guaranteed_usd_internal(
+                collateral_token,
+                safe_sub_to_int(fee, collateral_delta_usd)?,
+            )?;
+            self.increase_pool_amount(collateral_token, collateral_delta)?;
+            self.decrease_pool_amount(collateral_token, collateral_info.usd_to_token(fee)?)?;
+        }
+
+        let price = self.index_price(index_token, collateral_token, &collateral_info)?;
+        evm::log(DepositCollateral {
+            account,
+            collateral_token,
+            index_token,
+            is_long,
+            collateral_delta: collateral_delta_usd,
+            fee,
+        });
+        evm::log(UpdatePosition {
+            size: position.size,
+            collateral: position.collateral,
+            average_price: position.average_price,
+            entry_funding_rate: position.entry_funding_rate,
+            reserve_amount: position.reserve_amount,
+            realised_pnl: position.realised_pnl,
+            mark_price: price,
+        });
+
+        Ok(())
+    }
+
+    /// deposit stable collateral to the cross-margin account of `account`,
+    /// the margin backs all short positions using the same collateral token
+    pub fn deposit_margin(
+        &mut self,
+        account: Address,
+        collateral_token: Address,
+    ) -> Result<(), Vec<u8>> {
+        self.only_initialized()?;
+
+        self.validate_router(account)?;
+
+        let vault = IVault::new(self.vault.get());
+        validate(
+            vault.is_whitelisted(self.ctx(), collateral_token)?,
+            VaultError::CollateralNotWhitelisted,
+        )?;
+        validate(
+            vault.is_stable(self.ctx(), collateral_token)?,
+            VaultError::CollateralNotStable,
+        )?;
+
+        let amount = vault.transfer_in(self.ctx(), collateral_token)?;
+        validate(amount > U256::ZERO, VaultError::ZeroAmount)?;
+
+        IPositionsManager::new(self.positions_manager.get()).increase_cross_margin_balance(
+            self.ctx(),
+            account,
+            collateral_token,
+            amount,
+        )?;
+
+        evm::log(DepositMargin {
+            account,
+            collateral_token,
+            amount,
+        });
+
+        Ok(())
+    }
 }
@@ -79,7 +79,29 @@ impl PositionsIncreaseRouter {
         Ok(())
     }
 
-    fn swap(&mut self, path: SwapPath, min_out: U256, receiver: Address) -> Result<U256, Vec<u8>> {
+    /// transfer the collateral to the vault, swapping it along the path if needed
+    fn transfer_collateral_to_vault(
+        &mut self,
+        path: &CollateralPath,
+        amount_in: U256,
+        min_out: U256,
+    ) -> Result<(), Vec<u8>> {
+        let vault = self.vault.get();
+
+        if amount_in > U256::ZERO {
+            safe_transfer_from(self.ctx(), path.token_in(), msg::sender(), vault, amount_in)?;
+        }
+
+        if path.is_path() && amount_in > U256::ZERO {
+            let path = path.unwrap_path();
+            let amount_out = self.swap(path, min_out, contract::address())?;
+            safe_transfer(self.ctx(), path.token_out(), vault, amount_out)?;
+        }
+
+        Ok(())
+    }
+
+    fn swap(&mut self, path: &SwapPath, min_out: U256, receiver: Address) -> Result<U256, Vec<u8>> {
         Ok(ISwapRouter::new(self.swap_router.get()).swap_for_position(
             self,
             path.to_vec(),
@@ -124,18 +146,9 @@ impl PositionsIncreaseRouter {
         is_long: bool,
         price: U256,
     ) -> Result<(), Vec<u8>> {
-        let vault = self.vault.get();
         let path = CollateralPath::from_arr(path)?;
 
-        if amount_in > U256::ZERO {
-            safe_transfer_from(self.ctx(), path.token_in(), msg::sender(), vault, amount_in)?;
-        }
-
-        if path.is_path() && amount_in > U256::ZERO {
-            let path = path.unwrap_path();
-            let amount_out = self.swap(path, min_out, contract::address())?;
-            safe_transfer(self.ctx(), path.token_out(), vault, amount_out)?;
-        }
+        self.transfer_collateral_to_vault(&path, amount_in, min_out)?;
 
         self.increase_position_internal(path.token_out(), index_token, size_delta, is_long, price)?;
 
@@ -174,4 +187,54 @@ impl PositionsIncreaseRouter {
 
         Ok(())
     }
+
+    pub fn deposit_collateral(
+        &mut self,
+        path: Vec<Address>,
+        index_token: Address,
+        amount_in: U256,
+        min_out: U256,
+        is_long: bool,
+    ) -> Result<(), Vec<u8>> {
+        self.only_initialized()?;
+
+        let path = CollateralPath::from_arr(path)?;
+
+        self.transfer_collateral_to_vault(&path, amount_in, min_out)?;
+
+        IPositionsIncreaseManager::new(self.positions_increase_manager.get()).deposit_collateral(
+            self.ctx(),
+            msg::sender(),
+            path.token_out(),
+            index_token,
+            is_long,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn deposit_margin(
+        &mut self,
+        collateral_token: Address,
+        amount_in: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_initialized()?;
+
+        let vault = self.vault.get();
+        safe_transfer_from(
+            self.ctx(),
+            collateral_token,
+            msg::sender(),
+            vault,
+            amount_in,
+        )?;
+
+        IPositionsIncreaseManager::new(self.positions_increase_manager.get()).deposit_margin(
+            self.ctx(),
+            msg::sender(),
+            collateral_token,
+        )?;
+
+        Ok(())
+    }
 }
@@ -5,11 +5,15 @@ extern crate alloc;
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 use alloy_primitives::{Address, I256, U256};
-use omx_common::{call_context::GetCallContext, safe_sub, safe_sub_to_int, LIQUIDATION_FEE_USD};
+use omx_common::{
+    call_context::GetCallContext, safe_add, safe_mul_ratio, safe_sub, safe_sub_to_int,
+    BASIS_POINTS_DIVISOR, LIQUIDATION_FEE_USD,
+};
 use omx_interfaces::vault::{
-    position::Position, validate, CollectMarginFees, IFeeManager, IFundingRateManager,
-    IPositionsDecreaseManager, IPositionsManager, IPositionsManagerUtils, IVault, IVaultUtils,
-    LiquidatePosition, VaultError,
+    get_pnl_to_collateral_bps, position::Position, validate, AutoDeleveragePosition,
+    CollectMarginFees, IFeeManager, IFundingRateManager, IPositionsDecreaseManager,
+    IPositionsManager, IPositionsManagerUtils, IVault, IVaultUtils, LiquidatePosition, SeizeMargin,
+    TokenInfo, VaultError,
 };
 use stylus_sdk::{evm, msg, prelude::*};
 
@@ -28,6 +32,15 @@ sol_storage! {
         address positions_decrease_manager;
 
         mapping (address => bool) is_liquidator;
+
+        mapping (address => bool) is_adl_keeper;
+        /// max ratio of the aggregated unrealised profit of traders to the pool value,
+        /// once exceeded the most profitable positions can be auto-deleveraged,
+        /// zero disables auto-deleveraging for the side
+        mapping (address => uint256) max_long_pnl_to_pool_bps;
+        mapping (address => uint256) max_short_pnl_to_pool_bps;
+        /// min pnl to collateral ratio per index token of the positions that can be auto-deleveraged
+        mapping (address => uint256) min_adl_pnl_to_collateral_bps;
     }
 }
 
@@ -91,7 +104,15 @@ impl PositionsLiquidationManager {
     }
 
     fn usd_to_token(&self, token: Address, usd_amount: U256) -> Result<U256, Vec<u8>> {
-        Ok(IVaultUtils::new(self.vault_utils.get()).usd_to_token(self, token, usd_amount)?)
+        if usd_amount == U256::ZERO {
+            return Ok(U256::ZERO);
+        }
+
+        let token_info: TokenInfo = IVault::new(self.vault.get())
+            .get_token_info(self, token)?
+            .into();
+
+        token_info.usd_to_token(usd_amount)
     }
 
     fn get_price(&self, token: Address) -> Result<U256, Vec<u8>> {
@@ -128,6 +149,37 @@ impl PositionsLiquidationManager {
 
         Ok(())
     }
+
+    /// usd value of the pool backing the positions of the side
+    fn pool_usd(&self, index_token: Address, is_long: bool) -> Result<U256, Vec<u8>> {
+        let vault = IVault::new(self.vault.get());
+        if is_long {
+            let token_info: TokenInfo = vault.get_token_info(self, index_token)?.into();
+            return token_info.token_to_usd(token_info.pool_amount);
+        }
+
+        let mut pool_usd = U256::ZERO;
+        let length = vault.all_whitelisted_tokens_length(self)?;
+        for i in 0..length.to::<u64>() {
+            let token = vault.all_whitelisted_tokens(self, i)?;
+            if !vault.is_stable(self, token)? {
+                continue;
+            }
+
+            let token_info: TokenInfo = vault.get_token_info(self, token)?.into();
+            pool_usd = safe_add(pool_usd, token_info.token_to_usd(token_info.pool_amount)?)?;
+        }
+
+        Ok(pool_usd)
+    }
+
+    fn max_pnl_to_pool_bps(&self, index_token: Address, is_long: bool) -> U256 {
+        if is_long {
+            self.max_long_pnl_to_pool_bps.get(index_token)
+        } else {
+            self.max_short_pnl_to_pool_bps.get(index_token)
+        }
+    }
 }
 
 #[external]
@@ -177,6 +229,176 @@ impl PositionsLiquidationManager {
         Ok(())
     }
 
+    pub fn set_adl_keeper(&mut self, keeper: Address, is_active: bool) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.is_adl_keeper.insert(keeper, is_active);
+
+        Ok(())
+    }
+
+    pub fn set_max_pnl_to_pool_bps(
+        &mut self,
+        index_token: Address,
+        is_long: bool,
+        max_pnl_to_pool_bps: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        if is_long {
+            self.max_long_pnl_to_pool_bps
+                .insert(index_token, max_pnl_to_pool_bps);
+        } else {
+            self.max_short_pnl_to_pool_bps
+                .insert(index_token, max_pnl_to_pool_bps);
+        }
+
+        Ok(())
+    }
+
+    pub fn set_min_adl_pnl_to_collateral_bps(
+        &mut self,
+        index_token: Address,
+        min_pnl_to_collateral_bps: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.min_adl_pnl_to_collateral_bps
+            .insert(index_token, min_pnl_to_collateral_bps);
+
+        Ok(())
+    }
+
+    pub fn min_adl_pnl_to_collateral_bps(&self, index_token: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.min_adl_pnl_to_collateral_bps.get(index_token))
+    }
+
+    /// ratio of the aggregated unrealised profit of all positions on the side
+    /// to the usd value of the pool backing them in basis points,
+    /// longs are backed by the index token pool and shorts by the pools of all stable tokens
+    pub fn get_pnl_to_pool_bps(
+        &self,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<U256, Vec<u8>> {
+        let positions_manager = IPositionsManager::new(self.positions_manager.get());
+        if is_long {
+            validate(
+                positions_manager.is_global_long_data_ready(self)?,
+                VaultError::GlobalLongDataNotReady,
+            )?;
+        }
+
+        let (size, average_price) =
+            positions_manager.global_position(self, index_token, is_long)?;
+        if size == U256::ZERO || average_price == U256::ZERO {
+            return Ok(U256::ZERO);
+        }
+
+        let price = self.get_price(index_token)?;
+        let has_profit = if is_long {
+            price > average_price
+        } else {
+            average_price > price
+        };
+        if !has_profit {
+            return Ok(U256::ZERO);
+        }
+
+        let delta = safe_mul_ratio(size, price.abs_diff(average_price), average_price)?;
+
+        let pool_usd = self.pool_usd(index_token, is_long)?;
+        if pool_usd == U256::ZERO {
+            return Ok(U256::MAX);
+        }
+
+        safe_mul_ratio(delta, BASIS_POINTS_DIVISOR, pool_usd)
+    }
+
+    /// reduce the most profitable positions of the market while the pnl to pool ratio is above the threshold,
+    /// the keeper ranks the positions off-chain, the batch must be sorted by pnl to collateral ratio
+    /// in descending order and every position must be at or above the min ratio of the index token
+    pub fn auto_deleverage(
+        &mut self,
+        accounts: Vec<Address>,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+        size_deltas: Vec<U256>,
+    ) -> Result<(), Vec<u8>> {
+        self.only_initialized()?;
+        validate(self.is_adl_keeper.get(msg::sender()), VaultError::Forbidden)?;
+        validate(
+            accounts.len() == size_deltas.len(),
+            VaultError::ArrayLengthMismatch,
+        )?;
+
+        let max_pnl_to_pool_bps = self.max_pnl_to_pool_bps(index_token, is_long);
+        validate(max_pnl_to_pool_bps > U256::ZERO, VaultError::AdlNotRequired)?;
+
+        self.update_cumulative_funding_rate(collateral_token)?;
+
+        let positions_decrease_manager =
+            IPositionsDecreaseManager::new(self.positions_decrease_manager.get());
+
+        let price = self.get_price(index_token)?;
+        let min_score = self.min_adl_pnl_to_collateral_bps.get(index_token);
+
+        let mut last_score = U256::MAX;
+        for (i, (account, size_delta)) in accounts.into_iter().zip(size_deltas).enumerate() {
+            let pnl_to_pool_bps = self.get_pnl_to_pool_bps(index_token, is_long)?;
+            if pnl_to_pool_bps <= max_pnl_to_pool_bps {
+                validate(i > 0, VaultError::AdlNotRequired)?;
+                break;
+            }
+
+            let position = self.position(account, collateral_token, index_token, is_long)?;
+            validate(position.size > U256::ZERO, VaultError::ZeroSize)?;
+
+            let score = get_pnl_to_collateral_bps(&position, price, is_long)?;
+            validate(score > U256::ZERO, VaultError::AdlPositionNotProfitable)?;
+            validate(score >= min_score, VaultError::AdlPositionBelowThreshold)?;
+            validate(score <= last_score, VaultError::AdlInvalidOrder)?;
+            last_score = score;
+
+            let size_delta = size_delta.min(position.size);
+            let amount_out = positions_decrease_manager.decrease_position(
+                self.ctx(),
+                account,
+                collateral_token,
+                index_token,
+                U256::ZERO,
+                size_delta,
+                is_long,
+                account,
+            )?;
+
+            // profit paid out net of the fees and the price impact taken from the collateral
+            let collateral_info: TokenInfo = IVault::new(self.vault.get())
+                .get_token_info(&*self, collateral_token)?
+                .into();
+            let collateral = self
+                .position(account, collateral_token, index_token, is_long)?
+                .collateral;
+            let realised_pnl = safe_sub_to_int(
+                collateral_info.token_to_usd(amount_out)?,
+                safe_sub(position.collateral, collateral)?,
+            )?;
+
+            evm::log(AutoDeleveragePosition {
+                account,
+                collateral_token,
+                index_token,
+                is_long,
+                size_delta,
+                realised_pnl,
+                mark_price: price,
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn liquidate_position(
         &mut self,
         account: Address,
@@ -225,6 +447,28 @@ impl PositionsLiquidationManager {
             return Ok(());
         }
 
+        // the positions of an unhealthy margin account are liquidated from the largest loss,
+        // so a profitable position is never seized while the account is being liquidated
+        if !is_long && positions_manager.cross_margin_enabled(self.ctx(), account)? {
+            let (has_profit, delta) = IVaultUtils::new(self.vault_utils.get()).get_delta(
+                self.ctx(),
+                index_token,
+                position.size,
+                position.average_price,
+                is_long,
+                position.last_increased_time,
+            )?;
+            let (_, worst_loss) = positions_manager_utils.get_worst_cross_margin_position(
+                self.ctx(),
+                account,
+                collateral_token,
+            )?;
+            validate(
+                !has_profit && delta > U256::ZERO && delta == worst_loss,
+                VaultError::CrossMarginLiquidationOrder,
+            )?;
+        }
+
         let fee_tokens = self.usd_to_token(collateral_token, margin_fees)?;
 
         let fee_manager = IFeeManager::new(self.fee_manager.get());
@@ -265,7 +509,45 @@ impl PositionsLiquidationManager {
             )?;
         }
 
-        if !is_long {
+        // the margin account no longer covers the maintenance margin of the account,
+        // so it covers the part of the losses and fees of the liquidated position
+        // that exceeds the position collateral, other positions keep the rest
+        if !is_long && positions_manager.cross_margin_enabled(self.ctx(), account)? {
+            let (has_profit, delta) = IVaultUtils::new(self.vault_utils.get()).get_delta(
+                self.ctx(),
+                index_token,
+                position.size,
+                position.average_price,
+                is_long,
+                position.last_increased_time,
+            )?;
+            let losses = if has_profit { U256::ZERO } else { delta };
+            let shortfall = safe_add(safe_add(losses, margin_fees)?, LIQUIDATION_FEE_USD)?
+                .saturating_sub(position.collateral);
+
+            let margin =
+                positions_manager.cross_margin_balance(self.ctx(), account, collateral_token)?;
+            let amount = self.usd_to_token(collateral_token, shortfall)?.min(margin);
+            if amount > U256::ZERO {
+                positions_manager.decrease_cross_margin_balance(
+                    self.ctx(),
+                    account,
+                    collateral_token,
+                    amount,
+                )?;
+                self.increase_pool_amount(collateral_token, amount)?;
+
+                evm::log(SeizeMargin {
+                    account,
+                    collateral_token,
+                    amount,
+                });
+            }
+        }
+
+        if is_long {
+            positions_manager.decrease_global_long_size(self.ctx(), index_token, position.size)?;
+        } else {
             positions_manager.decrease_global_short_size(self.ctx(), index_token, position.size)?;
         }
 
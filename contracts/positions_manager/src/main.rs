@@ -4,15 +4,40 @@ extern crate alloc;
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
-use alloy_primitives::{Address, I256, U256};
-use omx_common::{safe_add, safe_add_int, safe_mul_ratio, safe_sub};
+use alloy_primitives::{Address, FixedBytes, I256, U128, U256, U64};
+use omx_common::{safe_add, safe_add_int, safe_mul, safe_mul_ratio, safe_sub, safe_sub_to_int};
 use omx_interfaces::vault::{
-    get_position_key, position::RawPositionData, validate, DecreaseGuaranteedUsd,
-    IncreaseGuaranteedUsd, VaultError,
+    get_position_key, position::RawPositionData, validate, ApprovePosition, DecreaseGuaranteedUsd,
+    IPositionsManagerUtils, IncreaseGuaranteedUsd, PositionTransferRequested, PositionTransferred,
+    SetCrossMargin, SetMaxGlobalSizes, SetMinPosition, SetPositionOperator, SetPriceImpactDepth,
+    TokenInfo, UpdatePriceImpactPool, VaultError,
+};
+use stylus_sdk::{
+    evm, msg,
+    prelude::*,
+    storage::{StorageFixedBytes, StorageMap, StorageU256, StorageVec},
 };
-use stylus_sdk::{evm, msg, prelude::*};
 
 sol_storage! {
+    /// position data packed into 4 storage slots:
+    /// [size | collateral], [average_price | entry_funding_rate],
+    /// [reserve_amount | last_increased_time | packed], [realised_pnl]
+    ///
+    /// usd values have 30 decimals, so a uint128 caps size, collateral and
+    /// average price at ~3.4e8 usd, writes above the cap revert with `PositionOverflow`
+    pub struct PackedPosition {
+        uint128 size;
+        uint128 collateral;
+        uint128 average_price;
+        uint128 entry_funding_rate;
+        uint128 reserve_amount;
+        uint64 last_increased_time;
+        /// set once the position was written to the packed storage,
+        /// positions without the flag are still read from the legacy mappings
+        bool packed;
+        int256 realised_pnl;
+    }
+
     #[entrypoint]
     pub struct PositionsManager {
         bool initialized;
@@ -43,6 +68,73 @@ sol_storage! {
         /// in the case of sudden price decreases, the guaranteed value should be corrected
         /// after liquidations are carried out
         mapping (address => uint256) guaranteed_usd;
+
+        /// global_long_sizes and global_long_average_prices mirror the short side tracking
+        /// and are used to estimate the aggregated PnL of longs for auto-deleveraging
+        mapping (address => uint256) global_long_sizes;
+        mapping (address => uint256) global_long_average_prices;
+
+        /// global long sizes are not tracked for positions opened before they were introduced,
+        /// set by `init` for new deployments and by `set_global_long_data` for existing ones
+        bool is_global_long_data_ready;
+
+        /// accounts that opted in to cross-margin, their short positions are backed by
+        /// both the position collateral and the margin balance of the collateral token
+        mapping (address => bool) cross_margin_enabled;
+        /// collateral tokens deposited to the margin account of (account, collateral_token),
+        /// valued at the current price whenever the account is evaluated
+        mapping (address => mapping (address => uint256)) cross_margin_balances;
+        /// sum of all margin balances of the account, only checked against zero
+        mapping (address => uint256) cross_margin_total_balances;
+
+        /// owner, collateral_token, index_token and is_long of open positions by position key
+        mapping (bytes32 => address) position_owners;
+        mapping (bytes32 => address) position_collateral_tokens;
+        mapping (bytes32 => address) position_index_tokens;
+        mapping (bytes32 => bool) position_is_long;
+        /// recipient that has to accept the transfer of the position
+        mapping (bytes32 => address) pending_position_transfers;
+        /// operators approved by the owner to manage all of the owner's positions
+        mapping (address => mapping (address => bool)) position_operators;
+
+        /// operator approved by the owner to manage a single position, cleared when the position
+        /// is closed or moved
+        mapping (bytes32 => address) position_approvals;
+
+        /// contracts allowed to lock the positions approved to them and to move the locked positions
+        mapping (address => bool) is_position_transfer_agent;
+
+        /// transfer agent holding the position, such as the position NFT while the position is wrapped,
+        /// locked positions can only be moved by the agent and their approvals can not be changed,
+        /// the lock follows the position when it is moved and stays on the key when it is closed
+        mapping (bytes32 => address) position_locks;
+
+        mapping (bytes32 => PackedPosition) packed_positions;
+
+        /// usd caps of the long open interest per index token, zero means no cap
+        mapping (address => uint256) max_global_long_sizes;
+
+        /// usd skew between long and short open interest at which the price impact reaches 100%,
+        /// zero disables the price impact of the market
+        mapping (address => uint256) price_impact_depths;
+        /// charged price impact that has not been rebated yet per index and collateral token,
+        /// kept in collateral tokens as it is part of the vault pool of that token,
+        /// rebates paid in a collateral token are capped by its pool
+        mapping (address => mapping (address => uint256)) price_impact_pools;
+
+        /// open position keys of each account
+        mapping (address => bytes32[]) account_position_keys;
+        /// index of the key in `account_position_keys` shifted by one, zero if the key is not there
+        mapping (bytes32 => uint256) account_position_indexes;
+
+        /// keys of all open positions, scanned by liquidation keepers
+        bytes32[] open_position_keys;
+        /// index of the key in `open_position_keys` shifted by one, zero if the key is not there
+        mapping (bytes32 => uint256) open_position_indexes;
+
+        /// usd minimums of size and collateral of positions per index token, zero means no minimum
+        mapping (address => uint256) min_position_sizes;
+        mapping (address => uint256) min_position_collaterals;
     }
 }
 
@@ -80,6 +172,300 @@ impl PositionsManager {
 
         Ok(())
     }
+
+    fn increase_global_long_size(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
+        let global_long_sizes = safe_add(self.global_long_sizes.get(token), amount)?;
+
+        let max_size = self.max_global_long_sizes.get(token);
+        if max_size != U256::ZERO {
+            validate(global_long_sizes <= max_size, VaultError::MaxLongsExceeded)?;
+        }
+
+        self.global_long_sizes.insert(token, global_long_sizes);
+
+        Ok(())
+    }
+
+    /// signed usd price impact of the trade, positive if it increases the skew and is charged,
+    /// negative if it reduces the skew and is rebated
+    ///
+    /// the impact is `(skew_after^2 - skew_before^2) / (2 * depth)`
+    /// where skew is the difference between long and short open interest
+    fn get_price_impact_internal(
+        &self,
+        index_token: Address,
+        is_long: bool,
+        size_delta: U256,
+        is_increase: bool,
+    ) -> Result<I256, Vec<u8>> {
+        let depth = self.price_impact_depths.get(index_token);
+        if depth == U256::ZERO || size_delta == U256::ZERO {
+            return Ok(I256::ZERO);
+        }
+
+        let long_size = self.global_long_sizes.get(index_token);
+        let short_size = self.global_short_sizes.get(index_token);
+        let (next_long_size, next_short_size) = match (is_long, is_increase) {
+            (true, true) => (safe_add(long_size, size_delta)?, short_size),
+            (true, false) => (long_size.saturating_sub(size_delta), short_size),
+            (false, true) => (long_size, safe_add(short_size, size_delta)?),
+            (false, false) => (long_size, short_size.saturating_sub(size_delta)),
+        };
+
+        let skew = safe_sub_to_int(long_size, short_size)?;
+        let next_skew = safe_sub_to_int(next_long_size, next_short_size)?;
+
+        let skew_delta = next_skew
+            .checked_sub(skew)
+            .ok_or(VaultError::PositionOverflow)?;
+        let skew_sum = next_skew
+            .checked_add(skew)
+            .ok_or(VaultError::PositionOverflow)?;
+        let impact = safe_mul_ratio(
+            skew_delta.unsigned_abs(),
+            skew_sum.unsigned_abs(),
+            safe_mul(depth, 2)?,
+        )?;
+        let impact = I256::try_from(impact).map_err(|_| VaultError::PositionOverflow)?;
+
+        if next_skew.unsigned_abs() > skew.unsigned_abs() {
+            Ok(impact)
+        } else {
+            Ok(-impact)
+        }
+    }
+
+    fn read_position(&self, key: FixedBytes<32>) -> RawPositionData {
+        let packed = self.packed_positions.get(key);
+        if !packed.packed.get() {
+            return self.read_legacy_position(key);
+        }
+
+        (
+            U256::from(packed.size.get()),
+            U256::from(packed.collateral.get()),
+            U256::from(packed.average_price.get()),
+            U256::from(packed.entry_funding_rate.get()),
+            U256::from(packed.reserve_amount.get()),
+            packed.realised_pnl.get(),
+            U256::from(packed.last_increased_time.get()),
+        )
+    }
+
+    fn read_legacy_position(&self, key: FixedBytes<32>) -> RawPositionData {
+        (
+            self.position_size.get(key),
+            self.position_collateral.get(key),
+            self.position_average_price.get(key),
+            self.position_entry_funding_rate.get(key),
+            self.position_reserve_amount.get(key),
+            self.position_realised_pnl.get(key),
+            self.position_last_increased_time.get(key),
+        )
+    }
+
+    fn write_position(
+        &mut self,
+        key: FixedBytes<32>,
+        position: RawPositionData,
+    ) -> Result<(), Vec<u8>> {
+        let size = to_u128(position.0)?;
+        let collateral = to_u128(position.1)?;
+        let average_price = to_u128(position.2)?;
+        let entry_funding_rate = to_u128(position.3)?;
+        let reserve_amount = to_u128(position.4)?;
+        validate(
+            position.6 <= U256::from(u64::MAX),
+            VaultError::PositionOverflow,
+        )?;
+        let last_increased_time = U64::from(position.6.to::<u64>());
+
+        let was_packed = self.packed_positions.get(key).packed.get();
+
+        let mut packed = self.packed_positions.setter(key);
+        packed.size.set(size);
+        packed.collateral.set(collateral);
+        packed.average_price.set(average_price);
+        packed.entry_funding_rate.set(entry_funding_rate);
+        packed.reserve_amount.set(reserve_amount);
+        packed.last_increased_time.set(last_increased_time);
+        packed.packed.set(true);
+        packed.realised_pnl.set(position.5);
+
+        // positions opened after the upgrade have nothing to clean up in the legacy mappings
+        if !was_packed && self.position_size.get(key) != U256::ZERO {
+            self.delete_legacy_position(key);
+        }
+
+        Ok(())
+    }
+
+    fn delete_legacy_position(&mut self, key: FixedBytes<32>) {
+        self.position_size.delete(key);
+        self.position_collateral.delete(key);
+        self.position_average_price.delete(key);
+        self.position_entry_funding_rate.delete(key);
+        self.position_reserve_amount.delete(key);
+        self.position_realised_pnl.delete(key);
+        self.position_last_increased_time.delete(key);
+    }
+
+    /// sets the position info of an open position that is not registered yet
+    fn register_position(
+        &mut self,
+        key: FixedBytes<32>,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+    ) {
+        if self.position_owners.get(key) != Address::ZERO || self.read_position(key).0 == U256::ZERO
+        {
+            return;
+        }
+
+        self.set_position_info(key, account, collateral_token, index_token, is_long);
+    }
+
+    fn set_position_info(
+        &mut self,
+        key: FixedBytes<32>,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+    ) {
+        self.position_owners.insert(key, account);
+        self.position_collateral_tokens
+            .insert(key, collateral_token);
+        self.position_index_tokens.insert(key, index_token);
+        self.position_is_long.insert(key, is_long);
+
+        insert_key(
+            &mut self.account_position_keys.setter(account),
+            &mut self.account_position_indexes,
+            key,
+        );
+        insert_key(
+            &mut self.open_position_keys,
+            &mut self.open_position_indexes,
+            key,
+        );
+    }
+
+    fn move_position(
+        &mut self,
+        key: FixedBytes<32>,
+        new_owner: Address,
+    ) -> Result<FixedBytes<32>, Vec<u8>> {
+        let owner = self.position_owners.get(key);
+        let collateral_token = self.position_collateral_tokens.get(key);
+        let index_token = self.position_index_tokens.get(key);
+        let is_long = self.position_is_long.get(key);
+        validate(
+            is_long || !self.cross_margin_enabled.get(new_owner),
+            VaultError::CrossMarginPosition,
+        )?;
+
+        let new_key = get_position_key(new_owner, collateral_token, index_token, is_long);
+        validate(
+            self.read_position(new_key).0 == U256::ZERO,
+            VaultError::PositionAlreadyExists,
+        )?;
+
+        let position = self.read_position(key);
+        self.write_position(new_key, position)?;
+        self.write_position(key, RawPositionData::default())?;
+
+        self.delete_position_info(key);
+        self.set_position_info(new_key, new_owner, collateral_token, index_token, is_long);
+
+        let lock = self.position_locks.get(key);
+        if lock != Address::ZERO {
+            self.position_locks.delete(key);
+            self.position_locks.insert(new_key, lock);
+        }
+
+        evm::log(PositionTransferred {
+            key: key.0,
+            new_key: new_key.0,
+            owner,
+            new_owner,
+        });
+
+        Ok(new_key)
+    }
+
+    fn delete_position_info(&mut self, key: FixedBytes<32>) {
+        remove_key(
+            &mut self
+                .account_position_keys
+                .setter(self.position_owners.get(key)),
+            &mut self.account_position_indexes,
+            key,
+        );
+        remove_key(
+            &mut self.open_position_keys,
+            &mut self.open_position_indexes,
+            key,
+        );
+
+        self.position_owners.delete(key);
+        self.position_collateral_tokens.delete(key);
+        self.position_index_tokens.delete(key);
+        self.position_is_long.delete(key);
+
+        // most positions are never approved or transferred, skip the no-op stores
+        if self.pending_position_transfers.get(key) != Address::ZERO {
+            self.pending_position_transfers.delete(key);
+        }
+        if self.position_approvals.get(key) != Address::ZERO {
+            self.position_approvals.delete(key);
+        }
+    }
+}
+
+/// appends the key to the set, indexes are shifted by one so that zero means the key is not there
+fn insert_key(
+    keys: &mut StorageVec<StorageFixedBytes<32>>,
+    indexes: &mut StorageMap<FixedBytes<32>, StorageU256>,
+    key: FixedBytes<32>,
+) {
+    if indexes.get(key) != U256::ZERO {
+        return;
+    }
+
+    keys.push(key);
+    indexes.insert(key, U256::from(keys.len()));
+}
+
+/// swaps the key with the last key of the set and pops it
+fn remove_key(
+    keys: &mut StorageVec<StorageFixedBytes<32>>,
+    indexes: &mut StorageMap<FixedBytes<32>, StorageU256>,
+    key: FixedBytes<32>,
+) {
+    let index = indexes.get(key);
+    if index == U256::ZERO {
+        return;
+    }
+
+    let last_key = keys.pop().unwrap_or_default();
+    if last_key != key {
+        if let Some(mut slot) = keys.setter(index.to::<usize>() - 1) {
+            slot.set(last_key);
+        }
+        indexes.insert(last_key, index);
+    }
+
+    indexes.delete(key);
+}
+
+/// narrows a position field to its packed width, see `PackedPosition` for the caps
+fn to_u128(value: U256) -> Result<U128, Vec<u8>> {
+    validate(value <= U256::from(u128::MAX), VaultError::PositionOverflow)?;
+
+    Ok(U128::from(value.to::<u128>()))
 }
 
 #[external]
@@ -108,6 +494,7 @@ impl PositionsManager {
             .set(positions_liquidation_manager);
         self.positions_manager_utils.set(positions_manager_utils);
 
+        self.is_global_long_data_ready.set(true);
         self.initialized.set(true);
 
         Ok(())
@@ -164,6 +551,50 @@ impl PositionsManager {
         Ok(())
     }
 
+    pub fn after_long_increase(
+        &mut self,
+        index_token: Address,
+        price: U256,
+        size_delta: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_manager()?;
+
+        if self.global_long_sizes.get(index_token) == U256::ZERO {
+            self.global_long_average_prices
+                .setter(index_token)
+                .set(price);
+        } else {
+            let new_average_price =
+                self.get_next_global_long_average_price(index_token, price, size_delta)?;
+            self.global_long_average_prices
+                .setter(index_token)
+                .set(new_average_price);
+        }
+
+        self.increase_global_long_size(index_token, size_delta)?;
+
+        Ok(())
+    }
+
+    pub fn decrease_global_long_size(
+        &mut self,
+        token: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_manager()?;
+
+        let size = self.global_long_sizes.get(token);
+        let amount = if amount > size {
+            U256::ZERO
+        } else {
+            size - amount
+        };
+
+        self.global_long_sizes.insert(token, amount);
+
+        Ok(())
+    }
+
     pub fn decrease_global_short_size(
         &mut self,
         token: Address,
@@ -200,15 +631,7 @@ impl PositionsManager {
     ) -> Result<RawPositionData, Vec<u8>> {
         let key = get_position_key(account, collateral_token, index_token, is_long);
 
-        Ok((
-            self.position_size.get(key),
-            self.position_collateral.get(key),
-            self.position_average_price.get(key),
-            self.position_entry_funding_rate.get(key),
-            self.position_reserve_amount.get(key),
-            self.position_realised_pnl.get(key),
-            self.position_last_increased_time.get(key),
-        ))
+        Ok(self.read_position(key))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -230,15 +653,25 @@ impl PositionsManager {
 
         let key = get_position_key(account, collateral_token, index_token, is_long);
 
-        self.position_size.insert(key, size);
-        self.position_collateral.insert(key, collateral);
-        self.position_average_price.insert(key, average_price);
-        self.position_entry_funding_rate
-            .insert(key, entry_funding_rate);
-        self.position_reserve_amount.insert(key, reserve_amount);
-        self.position_realised_pnl.insert(key, realised_pnl);
-        self.position_last_increased_time
-            .insert(key, last_increased_time);
+        self.write_position(
+            key,
+            (
+                size,
+                collateral,
+                average_price,
+                entry_funding_rate,
+                reserve_amount,
+                realised_pnl,
+                last_increased_time,
+            ),
+        )?;
+
+        let has_info = self.position_owners.get(key) != Address::ZERO;
+        if size != U256::ZERO && !has_info {
+            self.set_position_info(key, account, collateral_token, index_token, is_long);
+        } else if size == U256::ZERO && has_info {
+            self.delete_position_info(key);
+        }
 
         Ok(())
     }
@@ -267,7 +700,746 @@ impl PositionsManager {
         safe_mul_ratio(next_price, next_size, divisor)
     }
 
+    /// caps are in usd, zero disables the cap
+    pub fn set_max_global_sizes(
+        &mut self,
+        index_token: Address,
+        max_long_size: U256,
+        max_short_size: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.max_global_long_sizes
+            .insert(index_token, max_long_size);
+        self.max_global_short_sizes
+            .insert(index_token, max_short_size);
+
+        evm::log(SetMaxGlobalSizes {
+            index_token,
+            max_long_size,
+            max_short_size,
+        });
+
+        Ok(())
+    }
+
+    pub fn max_global_long_size(&self, token: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.max_global_long_sizes.get(token))
+    }
+
+    pub fn max_global_short_size(&self, token: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.max_global_short_sizes.get(token))
+    }
+
+    /// usd size that can still be opened on the side, `U256::MAX` if the side is not capped
+    pub fn available_open_interest(
+        &self,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<U256, Vec<u8>> {
+        let (size, max_size) = if is_long {
+            (
+                self.global_long_sizes.get(index_token),
+                self.max_global_long_sizes.get(index_token),
+            )
+        } else {
+            (
+                self.global_short_sizes.get(index_token),
+                self.max_global_short_sizes.get(index_token),
+            )
+        };
+
+        if max_size == U256::ZERO {
+            return Ok(U256::MAX);
+        }
+
+        Ok(max_size.saturating_sub(size))
+    }
+
+    pub fn set_min_position(
+        &mut self,
+        index_token: Address,
+        min_size: U256,
+        min_collateral: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.min_position_sizes.insert(index_token, min_size);
+        self.min_position_collaterals
+            .insert(index_token, min_collateral);
+
+        evm::log(SetMinPosition {
+            index_token,
+            min_size,
+            min_collateral,
+        });
+
+        Ok(())
+    }
+
+    /// returns min_size and min_collateral of positions of the index token
+    pub fn min_position(&self, index_token: Address) -> Result<(U256, U256), Vec<u8>> {
+        Ok((
+            self.min_position_sizes.get(index_token),
+            self.min_position_collaterals.get(index_token),
+        ))
+    }
+
+    pub fn set_price_impact_depth(
+        &mut self,
+        index_token: Address,
+        depth: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.price_impact_depths.insert(index_token, depth);
+
+        evm::log(SetPriceImpactDepth { index_token, depth });
+
+        Ok(())
+    }
+
+    pub fn price_impact_depth(&self, index_token: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.price_impact_depths.get(index_token))
+    }
+
+    /// collateral tokens available to rebate the price impact of the market
+    pub fn price_impact_pool(
+        &self,
+        index_token: Address,
+        collateral_token: Address,
+    ) -> Result<U256, Vec<u8>> {
+        Ok(self
+            .price_impact_pools
+            .getter(index_token)
+            .get(collateral_token))
+    }
+
+    /// price impact of the trade before it is applied to the global sizes,
+    /// rebates are not capped by the impact pool
+    pub fn get_price_impact(
+        &self,
+        index_token: Address,
+        is_long: bool,
+        size_delta: U256,
+        is_increase: bool,
+    ) -> Result<I256, Vec<u8>> {
+        self.get_price_impact_internal(index_token, is_long, size_delta, is_increase)
+    }
+
+    /// settles the usd price impact of the trade with the impact pool of the collateral token,
+    /// must be called before the global sizes are updated.
+    /// charges are capped by `max_charge` and rebates by the impact pool,
+    /// the collateral price and decimals convert the impact to pool tokens
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_price_impact(
+        &mut self,
+        index_token: Address,
+        collateral_token: Address,
+        is_long: bool,
+        size_delta: U256,
+        is_increase: bool,
+        max_charge: U256,
+        collateral_price: U256,
+        collateral_decimals: u8,
+    ) -> Result<I256, Vec<u8>> {
+        self.only_manager()?;
+
+        let impact =
+            self.get_price_impact_internal(index_token, is_long, size_delta, is_increase)?;
+        if impact == I256::ZERO {
+            return Ok(impact);
+        }
+
+        let collateral_info = TokenInfo {
+            price: collateral_price,
+            decimals: collateral_decimals,
+            ..Default::default()
+        };
+        let pool = self
+            .price_impact_pools
+            .getter(index_token)
+            .get(collateral_token);
+        let (next_pool, impact) = if impact.is_negative() {
+            let rebate = impact
+                .unsigned_abs()
+                .min(collateral_info.token_to_usd(pool)?);
+            (
+                safe_sub(pool, collateral_info.usd_to_token(rebate)?)?,
+                -I256::from_raw(rebate),
+            )
+        } else {
+            let charge = impact.unsigned_abs().min(max_charge);
+            (
+                safe_add(pool, collateral_info.usd_to_token(charge)?)?,
+                I256::from_raw(charge),
+            )
+        };
+
+        self.price_impact_pools
+            .setter(index_token)
+            .insert(collateral_token, next_pool);
+
+        evm::log(UpdatePriceImpactPool {
+            index_token,
+            collateral_token,
+            amount: next_pool,
+        });
+
+        Ok(impact)
+    }
+
     pub fn global_short_size(&self, token: Address) -> Result<U256, Vec<u8>> {
         Ok(self.global_short_sizes.get(token))
     }
+
+    /// for longs with profit: next_average_price = (next_price * next_size) / (next_size + delta)
+    /// for longs with loss: next_average_price = (next_price * next_size) / (next_size - delta)
+    pub fn get_next_global_long_average_price(
+        &self,
+        index_token: Address,
+        next_price: U256,
+        size_delta: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let size = self.global_long_sizes.get(index_token);
+        let average_price = self.global_long_average_prices.get(index_token);
+        let price_delta = average_price.abs_diff(next_price);
+        let delta = safe_mul_ratio(size, price_delta, average_price)?;
+        let has_profit = next_price > average_price;
+
+        let next_size = safe_add(size, size_delta)?;
+        let divisor = if has_profit {
+            safe_add(next_size, delta)?
+        } else {
+            safe_sub(next_size, delta)?
+        };
+
+        safe_mul_ratio(next_price, next_size, divisor)
+    }
+
+    pub fn global_short_average_price(&self, token: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.global_short_average_prices.get(token))
+    }
+
+    pub fn global_long_size(&self, token: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.global_long_sizes.get(token))
+    }
+
+    pub fn global_long_average_price(&self, token: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.global_long_average_prices.get(token))
+    }
+
+    /// global size and average price of longs or shorts in a single call
+    pub fn global_position(
+        &self,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<(U256, U256), Vec<u8>> {
+        if is_long {
+            return Ok((
+                self.global_long_sizes.get(index_token),
+                self.global_long_average_prices.get(index_token),
+            ));
+        }
+
+        Ok((
+            self.global_short_sizes.get(index_token),
+            self.global_short_average_prices.get(index_token),
+        ))
+    }
+
+    /// one-time backfill of the global long sizes and average prices of positions
+    /// opened before they were tracked
+    pub fn set_global_long_data(
+        &mut self,
+        tokens: Vec<Address>,
+        sizes: Vec<U256>,
+        average_prices: Vec<U256>,
+    ) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+        validate(
+            !self.is_global_long_data_ready.get(),
+            VaultError::GlobalLongDataReady,
+        )?;
+        validate(
+            tokens.len() == sizes.len() && tokens.len() == average_prices.len(),
+            VaultError::ArrayLengthMismatch,
+        )?;
+
+        for ((token, size), average_price) in tokens.into_iter().zip(sizes).zip(average_prices) {
+            self.global_long_sizes.insert(token, size);
+            self.global_long_average_prices.insert(token, average_price);
+        }
+
+        self.is_global_long_data_ready.set(true);
+
+        Ok(())
+    }
+
+    pub fn is_global_long_data_ready(&self) -> Result<bool, Vec<u8>> {
+        Ok(self.is_global_long_data_ready.get())
+    }
+
+    pub fn get_position_key(
+        &self,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<FixedBytes<32>, Vec<u8>> {
+        Ok(get_position_key(
+            account,
+            collateral_token,
+            index_token,
+            is_long,
+        ))
+    }
+
+    /// moves positions from the legacy mappings to the packed storage and
+    /// backfills their owner and key indexes
+    pub fn migrate_positions(
+        &mut self,
+        accounts: Vec<Address>,
+        collateral_tokens: Vec<Address>,
+        index_tokens: Vec<Address>,
+        is_long: Vec<bool>,
+    ) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+        validate(
+            accounts.len() == collateral_tokens.len()
+                && accounts.len() == index_tokens.len()
+                && accounts.len() == is_long.len(),
+            VaultError::ArrayLengthMismatch,
+        )?;
+
+        for (((account, collateral_token), index_token), is_long) in accounts
+            .into_iter()
+            .zip(collateral_tokens)
+            .zip(index_tokens)
+            .zip(is_long)
+        {
+            let key = get_position_key(account, collateral_token, index_token, is_long);
+            if !self.packed_positions.get(key).packed.get() {
+                let position = self.read_legacy_position(key);
+                if position.0 == U256::ZERO {
+                    continue;
+                }
+
+                self.write_position(key, position)?;
+            }
+
+            self.register_position(key, account, collateral_token, index_token, is_long);
+        }
+
+        Ok(())
+    }
+
+    /// backfills the owner and the key indexes of positions opened before they were tracked
+    pub fn register_positions(
+        &mut self,
+        accounts: Vec<Address>,
+        collateral_tokens: Vec<Address>,
+        index_tokens: Vec<Address>,
+        is_long: Vec<bool>,
+    ) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+        validate(
+            accounts.len() == collateral_tokens.len()
+                && accounts.len() == index_tokens.len()
+                && accounts.len() == is_long.len(),
+            VaultError::ArrayLengthMismatch,
+        )?;
+
+        for (((account, collateral_token), index_token), is_long) in accounts
+            .into_iter()
+            .zip(collateral_tokens)
+            .zip(index_tokens)
+            .zip(is_long)
+        {
+            let key = get_position_key(account, collateral_token, index_token, is_long);
+            self.register_position(key, account, collateral_token, index_token, is_long);
+        }
+
+        Ok(())
+    }
+
+    pub fn is_position_migrated(&self, key: FixedBytes<32>) -> Result<bool, Vec<u8>> {
+        Ok(self.packed_positions.get(key).packed.get())
+    }
+
+    pub fn position_by_key(&self, key: FixedBytes<32>) -> Result<RawPositionData, Vec<u8>> {
+        Ok(self.read_position(key))
+    }
+
+    /// returns owner, collateral_token, index_token and is_long of the position
+    pub fn position_info(
+        &self,
+        key: FixedBytes<32>,
+    ) -> Result<(Address, Address, Address, bool), Vec<u8>> {
+        Ok((
+            self.position_owners.get(key),
+            self.position_collateral_tokens.get(key),
+            self.position_index_tokens.get(key),
+            self.position_is_long.get(key),
+        ))
+    }
+
+    pub fn account_positions_length(&self, account: Address) -> Result<U256, Vec<u8>> {
+        Ok(U256::from(self.account_position_keys.get(account).len()))
+    }
+
+    pub fn account_position_key(
+        &self,
+        account: Address,
+        index: u64,
+    ) -> Result<FixedBytes<32>, Vec<u8>> {
+        Ok(self
+            .account_position_keys
+            .get(account)
+            .get(index)
+            .unwrap_or_default())
+    }
+
+    /// collateral_token, index_token and is_long of all open positions of the account
+    #[allow(clippy::type_complexity)]
+    pub fn account_positions(
+        &self,
+        account: Address,
+    ) -> Result<(Vec<Address>, Vec<Address>, Vec<bool>), Vec<u8>> {
+        let keys = self.account_position_keys.get(account);
+
+        let mut collateral_tokens = Vec::with_capacity(keys.len());
+        let mut index_tokens = Vec::with_capacity(keys.len());
+        let mut is_long = Vec::with_capacity(keys.len());
+        for i in 0..keys.len() {
+            let key = keys.get(i).unwrap_or_default();
+            collateral_tokens.push(self.position_collateral_tokens.get(key));
+            index_tokens.push(self.position_index_tokens.get(key));
+            is_long.push(self.position_is_long.get(key));
+        }
+
+        Ok((collateral_tokens, index_tokens, is_long))
+    }
+
+    pub fn open_positions_length(&self) -> Result<U256, Vec<u8>> {
+        Ok(U256::from(self.open_position_keys.len()))
+    }
+
+    /// page of open positions: key, owner and the liquidation state of the position
+    /// as reported by `PositionsManagerUtils::get_position_health`,
+    /// the state is only a hint, keepers still have to validate the liquidation
+    #[allow(clippy::type_complexity)]
+    pub fn get_open_positions(
+        &self,
+        offset: U256,
+        limit: U256,
+    ) -> Result<(Vec<FixedBytes<32>>, Vec<Address>, Vec<u8>), Vec<u8>> {
+        let positions_manager_utils =
+            IPositionsManagerUtils::new(self.positions_manager_utils.get());
+
+        let length = U256::from(self.open_position_keys.len());
+        let start = offset.min(length);
+        let end = safe_add(start, limit.min(length - start))?;
+
+        let mut keys = vec![];
+        let mut owners = vec![];
+        let mut states = vec![];
+        for i in start.to::<usize>()..end.to::<usize>() {
+            let key = self.open_position_keys.get(i).unwrap_or_default();
+            let owner = self.position_owners.get(key);
+            let (state, _) = positions_manager_utils.get_position_health(
+                self,
+                owner,
+                self.position_collateral_tokens.get(key),
+                self.position_index_tokens.get(key),
+                self.position_is_long.get(key),
+            )?;
+
+            keys.push(key);
+            owners.push(owner);
+            states.push(state);
+        }
+
+        Ok((keys, owners, states))
+    }
+
+    pub fn pending_position_transfer(&self, key: FixedBytes<32>) -> Result<Address, Vec<u8>> {
+        Ok(self.pending_position_transfers.get(key))
+    }
+
+    /// start the transfer of the position to `new_owner`, the transfer is completed
+    /// once `new_owner` accepts it, passing zero address cancels the pending transfer
+    pub fn transfer_position(
+        &mut self,
+        key: FixedBytes<32>,
+        new_owner: Address,
+    ) -> Result<(), Vec<u8>> {
+        let owner = self.position_owners.get(key);
+        validate(owner != Address::ZERO, VaultError::PositionNotFound)?;
+        validate(owner == msg::sender(), VaultError::Forbidden)?;
+        validate(
+            self.position_locks.get(key) == Address::ZERO,
+            VaultError::PositionLocked,
+        )?;
+        validate(new_owner != owner, VaultError::InvalidPositionOwner)?;
+        validate(
+            self.position_is_long.get(key) || !self.cross_margin_enabled.get(owner),
+            VaultError::CrossMarginPosition,
+        )?;
+
+        self.pending_position_transfers.insert(key, new_owner);
+
+        evm::log(PositionTransferRequested {
+            key: key.0,
+            owner,
+            new_owner,
+        });
+
+        Ok(())
+    }
+
+    /// accept the pending transfer of the position, the position is moved
+    /// under the key of the new owner, which must not have the same position opened
+    pub fn accept_position(&mut self, key: FixedBytes<32>) -> Result<(), Vec<u8>> {
+        let new_owner = msg::sender();
+        validate(
+            new_owner != Address::ZERO && self.pending_position_transfers.get(key) == new_owner,
+            VaultError::Forbidden,
+        )?;
+        validate(
+            self.position_locks.get(key) == Address::ZERO,
+            VaultError::PositionLocked,
+        )?;
+
+        self.move_position(key, new_owner)?;
+
+        Ok(())
+    }
+
+    /// move the position to `new_owner` immediately, callable only by the transfer agent
+    /// (such as the position NFT) holding the lock of the position
+    pub fn transfer_position_from(
+        &mut self,
+        owner: Address,
+        key: FixedBytes<32>,
+        new_owner: Address,
+    ) -> Result<FixedBytes<32>, Vec<u8>> {
+        validate(
+            self.is_position_transfer_agent.get(msg::sender()),
+            VaultError::Forbidden,
+        )?;
+        validate(
+            self.position_owners.get(key) == owner,
+            VaultError::PositionNotFound,
+        )?;
+        validate(
+            self.position_locks.get(key) == msg::sender(),
+            VaultError::Forbidden,
+        )?;
+        validate(
+            new_owner != Address::ZERO && new_owner != owner,
+            VaultError::InvalidPositionOwner,
+        )?;
+        validate(
+            self.position_is_long.get(key) || !self.cross_margin_enabled.get(owner),
+            VaultError::CrossMarginPosition,
+        )?;
+
+        self.move_position(key, new_owner)
+    }
+
+    pub fn set_position_transfer_agent(
+        &mut self,
+        agent: Address,
+        is_active: bool,
+    ) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.is_position_transfer_agent.insert(agent, is_active);
+
+        Ok(())
+    }
+
+    pub fn is_position_transfer_agent(&self, agent: Address) -> Result<bool, Vec<u8>> {
+        Ok(self.is_position_transfer_agent.get(agent))
+    }
+
+    /// lock the position to the calling transfer agent, the owner must have approved
+    /// the agent for the position with `approve_position` beforehand
+    pub fn lock_position(&mut self, key: FixedBytes<32>) -> Result<(), Vec<u8>> {
+        let agent = msg::sender();
+        validate(
+            self.is_position_transfer_agent.get(agent),
+            VaultError::Forbidden,
+        )?;
+        validate(
+            self.position_owners.get(key) != Address::ZERO,
+            VaultError::PositionNotFound,
+        )?;
+        validate(
+            self.position_locks.get(key) == Address::ZERO,
+            VaultError::PositionLocked,
+        )?;
+        validate(
+            self.position_approvals.get(key) == agent,
+            VaultError::Forbidden,
+        )?;
+
+        self.position_locks.insert(key, agent);
+        self.pending_position_transfers.delete(key);
+
+        Ok(())
+    }
+
+    /// release the lock of the calling transfer agent and revoke its approval for the position
+    pub fn unlock_position(&mut self, key: FixedBytes<32>) -> Result<(), Vec<u8>> {
+        validate(
+            self.position_locks.get(key) == msg::sender(),
+            VaultError::Forbidden,
+        )?;
+
+        self.position_locks.delete(key);
+        self.position_approvals.delete(key);
+
+        Ok(())
+    }
+
+    pub fn position_lock(&self, key: FixedBytes<32>) -> Result<Address, Vec<u8>> {
+        Ok(self.position_locks.get(key))
+    }
+
+    /// approve `operator` to increase, decrease and manage the collateral
+    /// of all positions of the sender, proceeds are always sent to the owner
+    pub fn set_position_operator(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Vec<u8>> {
+        let owner = msg::sender();
+
+        self.position_operators
+            .setter(owner)
+            .insert(operator, approved);
+
+        evm::log(SetPositionOperator {
+            owner,
+            operator,
+            approved,
+        });
+
+        Ok(())
+    }
+
+    pub fn is_position_operator(&self, owner: Address, operator: Address) -> Result<bool, Vec<u8>> {
+        Ok(self.position_operators.getter(owner).get(operator))
+    }
+
+    /// approve `operator` to manage the single position of the sender,
+    /// passing zero address removes the approval
+    pub fn approve_position(
+        &mut self,
+        key: FixedBytes<32>,
+        operator: Address,
+    ) -> Result<(), Vec<u8>> {
+        let owner = self.position_owners.get(key);
+        validate(owner != Address::ZERO, VaultError::PositionNotFound)?;
+        validate(owner == msg::sender(), VaultError::Forbidden)?;
+        validate(
+            self.position_locks.get(key) == Address::ZERO,
+            VaultError::PositionLocked,
+        )?;
+
+        self.position_approvals.insert(key, operator);
+
+        evm::log(ApprovePosition {
+            key: key.0,
+            owner,
+            operator,
+        });
+
+        Ok(())
+    }
+
+    pub fn position_approval(&self, key: FixedBytes<32>) -> Result<Address, Vec<u8>> {
+        Ok(self.position_approvals.get(key))
+    }
+
+    /// opt in or out of cross-margin, the account must have no margin deposited to opt out
+    pub fn set_cross_margin_enabled(&mut self, enabled: bool) -> Result<(), Vec<u8>> {
+        let account = msg::sender();
+
+        if !enabled {
+            validate(
+                self.cross_margin_total_balances.get(account) == U256::ZERO,
+                VaultError::MarginBalanceNotZero,
+            )?;
+        }
+
+        self.cross_margin_enabled.insert(account, enabled);
+
+        evm::log(SetCrossMargin { account, enabled });
+
+        Ok(())
+    }
+
+    pub fn cross_margin_enabled(&self, account: Address) -> Result<bool, Vec<u8>> {
+        Ok(self.cross_margin_enabled.get(account))
+    }
+
+    pub fn cross_margin_balance(
+        &self,
+        account: Address,
+        collateral_token: Address,
+    ) -> Result<U256, Vec<u8>> {
+        Ok(self
+            .cross_margin_balances
+            .getter(account)
+            .get(collateral_token))
+    }
+
+    pub fn increase_cross_margin_balance(
+        &mut self,
+        account: Address,
+        collateral_token: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_manager()?;
+        validate(
+            self.cross_margin_enabled.get(account),
+            VaultError::CrossMarginDisabled,
+        )?;
+
+        let balance = self.cross_margin_balance(account, collateral_token)?;
+        self.cross_margin_balances
+            .setter(account)
+            .insert(collateral_token, safe_add(balance, amount)?);
+        self.cross_margin_total_balances.insert(
+            account,
+            safe_add(self.cross_margin_total_balances.get(account), amount)?,
+        );
+
+        Ok(())
+    }
+
+    pub fn decrease_cross_margin_balance(
+        &mut self,
+        account: Address,
+        collateral_token: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_manager()?;
+
+        let balance = self.cross_margin_balance(account, collateral_token)?;
+        validate(balance >= amount, VaultError::MarginBalanceExceeded)?;
+
+        self.cross_margin_balances
+            .setter(account)
+            .insert(collateral_token, balance - amount);
+        self.cross_margin_total_balances.insert(
+            account,
+            safe_sub(self.cross_margin_total_balances.get(account), amount)?,
+        );
+
+        Ok(())
+    }
 }
@@ -7,10 +7,11 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 use alloy_primitives::{Address, I256, U256};
 use omx_common::{
     safe_add, safe_mul, safe_mul_ratio, safe_sub, BASIS_POINTS_DIVISOR, LIQUIDATION_FEE_USD,
-    MAX_LEVERAGE,
+    LIQUIDATION_STATE_NORMAL, MAX_LEVERAGE,
 };
 use omx_interfaces::vault::{
-    position::Position, validate, IFeeManager, IPositionsManager, IVault, IVaultUtils, UpdatePnl,
+    position::{get_decrease_size_delta, Position, PositionHealth},
+    validate, IFeeManager, IPositionsManager, IVault, IVaultUtils, TokenInfo, UpdatePnl,
     VaultError,
 };
 use stylus_sdk::{evm, msg, prelude::*};
@@ -96,19 +97,16 @@ impl PositionsManagerUtils {
         Ok(IVault::new(self.vault.get()).get_price(self, token)?)
     }
 
-    fn token_decimals(&self, token: Address) -> Result<u8, Vec<u8>> {
-        Ok(IVault::new(self.vault.get()).get_token_decimals(self, token)?)
-    }
-
     fn usd_to_token(&self, token: Address, usd_amount: U256) -> Result<U256, Vec<u8>> {
         if usd_amount == U256::ZERO {
             return Ok(U256::ZERO);
         }
 
-        let price = self.get_price(token)?;
-        let decimals = self.token_decimals(token)?;
+        let token_info: TokenInfo = IVault::new(self.vault.get())
+            .get_token_info(self, token)?
+            .into();
 
-        safe_mul_ratio(usd_amount, U256::from(10).pow(U256::from(decimals)), price)
+        token_info.usd_to_token(usd_amount)
     }
 
     fn increase_pool_amount(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
@@ -116,6 +114,326 @@ impl PositionsManagerUtils {
 
         Ok(())
     }
+
+    fn is_cross_margin(&self, account: Address, is_long: bool) -> Result<bool, Vec<u8>> {
+        if is_long {
+            return Ok(false);
+        }
+
+        Ok(IPositionsManager::new(self.positions_manager.get())
+            .cross_margin_enabled(self, account)?)
+    }
+
+    fn position_margin_fees(
+        &self,
+        collateral_token: Address,
+        position: &Position,
+    ) -> Result<U256, Vec<u8>> {
+        let fee_manager = IFeeManager::new(self.fee_manager.get());
+        let funding_fee = fee_manager.get_funding_fee(
+            self,
+            collateral_token,
+            position.size,
+            position.entry_funding_rate,
+        )?;
+
+        safe_add(
+            funding_fee,
+            fee_manager.get_position_fee(self, position.size)?,
+        )
+    }
+
+    fn get_position_health_internal(
+        &self,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<(PositionHealth, U256), Vec<u8>> {
+        if self.is_cross_margin(account, is_long)? {
+            let (equity, losses, maintenance_margin, total_size) =
+                self.cross_margin_account(account, collateral_token)?;
+
+            return evaluate_health(equity, losses, maintenance_margin, total_size);
+        }
+
+        let position = self.position(account, collateral_token, index_token, is_long)?;
+        if position.size == U256::ZERO {
+            return Ok((PositionHealth::Healthy, U256::ZERO));
+        }
+
+        let (has_profit, delta) = self.get_delta(
+            index_token,
+            position.size,
+            position.average_price,
+            is_long,
+            position.last_increased_time,
+        )?;
+        let losses = if has_profit { U256::ZERO } else { delta };
+        let maintenance_margin = safe_add(
+            self.position_margin_fees(collateral_token, &position)?,
+            LIQUIDATION_FEE_USD,
+        )?;
+
+        evaluate_health(
+            position.collateral,
+            losses,
+            maintenance_margin,
+            position.size,
+        )
+    }
+
+    /// price impact as it would be settled by `PositionsManager::apply_price_impact`
+    #[allow(clippy::too_many_arguments)]
+    fn quote_price_impact(
+        &self,
+        index_token: Address,
+        collateral_token: Address,
+        collateral_info: &TokenInfo,
+        is_long: bool,
+        size_delta: U256,
+        is_increase: bool,
+        max_charge: U256,
+    ) -> Result<I256, Vec<u8>> {
+        let positions_manager = IPositionsManager::new(self.positions_manager.get());
+        let impact = positions_manager.get_price_impact(
+            self,
+            index_token,
+            is_long,
+            size_delta,
+            is_increase,
+        )?;
+
+        if impact.is_negative() {
+            let pool = positions_manager.price_impact_pool(self, index_token, collateral_token)?;
+            return Ok(-I256::from_raw(
+                impact
+                    .unsigned_abs()
+                    .min(collateral_info.token_to_usd(pool)?),
+            ));
+        }
+
+        Ok(I256::from_raw(impact.unsigned_abs().min(max_charge)))
+    }
+
+    /// index price at which the remaining collateral reaches the larger of
+    /// the margin fees with the liquidation fee and the collateral required by the max leverage
+    fn get_liquidation_price_internal(
+        &self,
+        size: U256,
+        collateral: U256,
+        average_price: U256,
+        is_long: bool,
+        margin_fees: U256,
+    ) -> Result<U256, Vec<u8>> {
+        if size == U256::ZERO || average_price == U256::ZERO {
+            return Ok(U256::ZERO);
+        }
+
+        let threshold = safe_add(margin_fees, LIQUIDATION_FEE_USD)?.max(safe_mul_ratio(
+            size,
+            BASIS_POINTS_DIVISOR,
+            MAX_LEVERAGE,
+        )?);
+
+        // for longs the price can drop by `collateral - threshold`,
+        // for shorts it can rise by the same amount
+        let (numerator, subtrahend) = if is_long {
+            (safe_add(size, threshold)?, collateral)
+        } else {
+            (safe_add(size, collateral)?, threshold)
+        };
+        if subtrahend >= numerator {
+            return Ok(U256::ZERO);
+        }
+
+        safe_mul_ratio(average_price, numerator - subtrahend, size)
+    }
+
+    /// equity, losses, maintenance margin and total size of all short positions
+    /// backed by the margin account of (account, collateral_token),
+    /// only the open positions of the account are visited
+    fn cross_margin_account(
+        &self,
+        account: Address,
+        collateral_token: Address,
+    ) -> Result<(U256, U256, U256, U256), Vec<u8>> {
+        let positions_manager = IPositionsManager::new(self.positions_manager.get());
+
+        let collateral_info: TokenInfo = IVault::new(self.vault.get())
+            .get_token_info(self, collateral_token)?
+            .into();
+        let mut equity = collateral_info.token_to_usd(positions_manager.cross_margin_balance(
+            self,
+            account,
+            collateral_token,
+        )?)?;
+        let mut losses = U256::ZERO;
+        let mut maintenance_margin = U256::ZERO;
+        let mut total_size = U256::ZERO;
+
+        let (collateral_tokens, index_tokens, is_long) =
+            positions_manager.account_positions(self, account)?;
+        for ((position_collateral_token, index_token), is_long) in
+            collateral_tokens.into_iter().zip(index_tokens).zip(is_long)
+        {
+            if is_long || position_collateral_token != collateral_token {
+                continue;
+            }
+
+            let position = self.position(account, collateral_token, index_token, false)?;
+            if position.size == U256::ZERO {
+                continue;
+            }
+
+            let (has_profit, delta) = self.get_delta(
+                index_token,
+                position.size,
+                position.average_price,
+                false,
+                position.last_increased_time,
+            )?;
+
+            equity = safe_add(equity, position.collateral)?;
+            if has_profit {
+                equity = safe_add(equity, delta)?;
+            } else {
+                losses = safe_add(losses, delta)?;
+            }
+
+            maintenance_margin = safe_add(
+                maintenance_margin,
+                safe_add(
+                    self.position_margin_fees(collateral_token, &position)?,
+                    LIQUIDATION_FEE_USD,
+                )?,
+            )?;
+            total_size = safe_add(total_size, position.size)?;
+        }
+
+        Ok((equity, losses, maintenance_margin, total_size))
+    }
+
+    /// index token of the short position with the largest loss backed by the margin account
+    /// of (account, collateral_token), zero address if none of the positions is losing
+    fn worst_cross_margin_position(
+        &self,
+        account: Address,
+        collateral_token: Address,
+    ) -> Result<(Address, U256), Vec<u8>> {
+        let positions_manager = IPositionsManager::new(self.positions_manager.get());
+
+        let mut worst_index_token = Address::ZERO;
+        let mut worst_loss = U256::ZERO;
+
+        let (collateral_tokens, index_tokens, is_long) =
+            positions_manager.account_positions(self, account)?;
+        for ((position_collateral_token, index_token), is_long) in
+            collateral_tokens.into_iter().zip(index_tokens).zip(is_long)
+        {
+            if is_long || position_collateral_token != collateral_token {
+                continue;
+            }
+
+            let position = self.position(account, collateral_token, index_token, false)?;
+            if position.size == U256::ZERO {
+                continue;
+            }
+
+            let (has_profit, delta) = self.get_delta(
+                index_token,
+                position.size,
+                position.average_price,
+                false,
+                position.last_increased_time,
+            )?;
+
+            if !has_profit && delta > worst_loss {
+                worst_index_token = index_token;
+                worst_loss = delta;
+            }
+        }
+
+        Ok((worst_index_token, worst_loss))
+    }
+
+    /// evaluate the equity of the margin account of (account, collateral_token)
+    /// against the maintenance margin of all short positions backed by it
+    fn validate_cross_margin_internal(
+        &self,
+        account: Address,
+        collateral_token: Address,
+        raise: bool,
+    ) -> Result<U256, Vec<u8>> {
+        let (equity, losses, maintenance_margin, total_size) =
+            self.cross_margin_account(account, collateral_token)?;
+
+        if equity < losses {
+            if raise {
+                return Err(VaultError::LossesExceedCollateral.into());
+            }
+            return Ok(U256::from(1));
+        }
+
+        let remaining_equity = equity - losses;
+
+        if remaining_equity < maintenance_margin {
+            if raise {
+                return Err(VaultError::LiquidationFeesExceedCollateral.into());
+            }
+            return Ok(U256::from(1));
+        }
+
+        if safe_mul(remaining_equity, MAX_LEVERAGE)? < safe_mul(total_size, BASIS_POINTS_DIVISOR)? {
+            if raise {
+                return Err(VaultError::MaxLeverageExceeded.into());
+            }
+            return Ok(U256::from(2));
+        }
+
+        Ok(LIQUIDATION_STATE_NORMAL)
+    }
+}
+
+/// health of the collateral backing positions of `size`,
+/// the buffer is the usd loss the collateral can absorb before the position gets liquidated
+fn evaluate_health(
+    equity: U256,
+    losses: U256,
+    maintenance_margin: U256,
+    size: U256,
+) -> Result<(PositionHealth, U256), Vec<u8>> {
+    if equity < losses {
+        return Ok((PositionHealth::Liquidatable, U256::ZERO));
+    }
+
+    let remaining = equity - losses;
+    if remaining < maintenance_margin {
+        return Ok((PositionHealth::Liquidatable, U256::ZERO));
+    }
+
+    if safe_mul(remaining, MAX_LEVERAGE)? < safe_mul(size, BASIS_POINTS_DIVISOR)? {
+        return Ok((PositionHealth::MaxLeverageExceeded, U256::ZERO));
+    }
+
+    let leverage_margin = safe_mul_ratio(size, BASIS_POINTS_DIVISOR, MAX_LEVERAGE)?;
+
+    Ok((
+        PositionHealth::Healthy,
+        remaining.saturating_sub(maintenance_margin.max(leverage_margin)),
+    ))
+}
+
+/// position leverage in basis points
+fn get_leverage(size: U256, collateral: U256) -> Result<U256, Vec<u8>> {
+    if size == U256::ZERO {
+        return Ok(U256::ZERO);
+    }
+    if collateral == U256::ZERO {
+        return Ok(U256::MAX);
+    }
+
+    safe_mul_ratio(size, BASIS_POINTS_DIVISOR, collateral)
 }
 
 #[external]
@@ -142,6 +460,9 @@ impl PositionsManagerUtils {
         Ok(())
     }
 
+    /// settles the fees and the pnl of the decrease and returns
+    /// usd_out, usd_out_after_fee, the next collateral and the next realised pnl,
+    /// the position itself is written once by the caller
     pub fn reduce_collateral(
         &mut self,
         account: Address,
@@ -150,7 +471,7 @@ impl PositionsManagerUtils {
         collateral_delta: U256,
         size_delta: U256,
         is_long: bool,
-    ) -> Result<(U256, U256), Vec<u8>> {
+    ) -> Result<(U256, U256, U256, I256), Vec<u8>> {
         self.only_manager()?;
 
         let mut position = self.position(account, collateral_token, index_token, is_long)?;
@@ -171,8 +492,6 @@ impl PositionsManagerUtils {
         )?;
         let adjusted_delta = safe_mul_ratio(size_delta, delta, position.size)?;
 
-        let initial_position = position;
-
         // transfer profits out
         let mut usd_out = U256::ZERO;
         if has_profit && adjusted_delta > U256::ZERO {
@@ -191,6 +510,20 @@ impl PositionsManagerUtils {
         }
 
         if !has_profit && adjusted_delta > U256::ZERO {
+            // losses exceeding the position collateral are covered by the margin account
+            if position.collateral < adjusted_delta && self.is_cross_margin(account, is_long)? {
+                let shortfall =
+                    self.usd_to_token(collateral_token, adjusted_delta - position.collateral)?;
+                IPositionsManager::new(self.positions_manager.get())
+                    .decrease_cross_margin_balance(
+                        &mut *self,
+                        account,
+                        collateral_token,
+                        shortfall,
+                    )?;
+                position.collateral = adjusted_delta;
+            }
+
             position.collateral = safe_sub(position.collateral, adjusted_delta)?;
             position.realised_pnl = position
                 .realised_pnl
@@ -234,24 +567,6 @@ impl PositionsManagerUtils {
             usd_out
         };
 
-        let positions_manager = IPositionsManager::new(self.positions_manager.get());
-        if position != initial_position {
-            positions_manager.position_update(
-                self,
-                account,
-                collateral_token,
-                index_token,
-                is_long,
-                position.size,
-                position.collateral,
-                position.average_price,
-                position.entry_funding_rate,
-                position.reserve_amount,
-                position.realised_pnl,
-                position.last_increased_time,
-            )?;
-        }
-
         evm::log(UpdatePnl {
             account,
             collateral_token,
@@ -261,7 +576,12 @@ impl PositionsManagerUtils {
             delta: adjusted_delta,
         });
 
-        Ok((usd_out, usd_out_after_fee))
+        Ok((
+            usd_out,
+            usd_out_after_fee,
+            position.collateral,
+            position.realised_pnl,
+        ))
     }
 
     pub fn validate_liquidation(
@@ -274,6 +594,14 @@ impl PositionsManagerUtils {
     ) -> Result<(U256, U256), Vec<u8>> {
         let position = self.position(account, collateral_token, index_token, is_long)?;
 
+        if self.is_cross_margin(account, is_long)? {
+            let liquidation_state =
+                self.validate_cross_margin_internal(account, collateral_token, raise)?;
+            let margin_fees = self.position_margin_fees(collateral_token, &position)?;
+
+            return Ok((liquidation_state, margin_fees));
+        }
+
         let (has_profit, delta) = self.get_delta(
             index_token,
             position.size,
@@ -330,7 +658,295 @@ impl PositionsManagerUtils {
             return Ok((U256::from(2), margin_fees));
         }
 
-        Ok((U256::from(1), margin_fees))
+        Ok((LIQUIDATION_STATE_NORMAL, margin_fees))
+    }
+
+    /// health state of the position and the usd loss it can absorb before the liquidation,
+    /// positions of cross-margin accounts report the health of the whole margin account
+    pub fn get_position_health(
+        &self,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<(u8, U256), Vec<u8>> {
+        let (health, buffer) =
+            self.get_position_health_internal(account, collateral_token, index_token, is_long)?;
+
+        Ok((health as u8, buffer))
+    }
+
+    /// index price at which the position becomes liquidatable, zero if there is no position
+    pub fn get_liquidation_price(
+        &self,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<U256, Vec<u8>> {
+        let position = self.position(account, collateral_token, index_token, is_long)?;
+        if position.size == U256::ZERO {
+            return Ok(U256::ZERO);
+        }
+
+        if !self.is_cross_margin(account, is_long)? {
+            let margin_fees = self.position_margin_fees(collateral_token, &position)?;
+
+            return self.get_liquidation_price_internal(
+                position.size,
+                position.collateral,
+                position.average_price,
+                is_long,
+                margin_fees,
+            );
+        }
+
+        // the whole buffer of the margin account can be lost on this position
+        let price = self.get_price(index_token)?;
+        let (health, buffer) =
+            self.get_position_health_internal(account, collateral_token, index_token, is_long)?;
+        if health != PositionHealth::Healthy {
+            return Ok(price);
+        }
+
+        safe_add(
+            price,
+            safe_mul_ratio(buffer, position.average_price, position.size)?,
+        )
+    }
+
+    pub fn validate_cross_margin(
+        &self,
+        account: Address,
+        collateral_token: Address,
+        raise: bool,
+    ) -> Result<U256, Vec<u8>> {
+        self.validate_cross_margin_internal(account, collateral_token, raise)
+    }
+
+    /// index token and usd loss of the short position with the largest loss
+    /// of the margin account, it is the only one that can be liquidated next
+    pub fn get_worst_cross_margin_position(
+        &self,
+        account: Address,
+        collateral_token: Address,
+    ) -> Result<(Address, U256), Vec<u8>> {
+        self.worst_cross_margin_position(account, collateral_token)
+    }
+
+    /// returns the fee, price impact, next size, next collateral, next average price,
+    /// leverage in basis points and liquidation price of the position after the increase.
+    /// `amount_in` is the amount of collateral tokens that will be transferred to the vault
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    pub fn quote_increase_position(
+        &self,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        amount_in: U256,
+        size_delta: U256,
+        is_long: bool,
+    ) -> Result<(U256, I256, U256, U256, U256, U256, U256), Vec<u8>> {
+        let vault = IVault::new(self.vault.get());
+        let fee_manager = IFeeManager::new(self.fee_manager.get());
+
+        let mut position = self.position(account, collateral_token, index_token, is_long)?;
+
+        let collateral_info: TokenInfo = vault.get_token_info(self, collateral_token)?.into();
+        let price = if index_token == collateral_token {
+            collateral_info.price
+        } else {
+            vault.get_price(self, index_token)?
+        };
+
+        // mirrors `PositionsIncreaseManager::increase_position`
+        position.average_price = price;
+        if position.size > U256::ZERO && size_delta > U256::ZERO {
+            position.average_price = self.get_next_average_price(
+                index_token,
+                position.size,
+                position.average_price,
+                is_long,
+                price,
+                size_delta,
+                position.last_increased_time,
+            )?;
+        }
+
+        let fee = safe_add(
+            fee_manager.get_position_fee(self, size_delta)?,
+            fee_manager.get_next_funding_fee(
+                self,
+                collateral_token,
+                position.size,
+                position.entry_funding_rate,
+            )?,
+        )?;
+
+        let collateral_delta_usd = collateral_info.token_to_usd(amount_in)?;
+        position.collateral = safe_sub(safe_add(position.collateral, collateral_delta_usd)?, fee)
+            .map_err(|_| VaultError::CollateralLessThenFees)?;
+
+        let price_impact = self.quote_price_impact(
+            index_token,
+            collateral_token,
+            &collateral_info,
+            is_long,
+            size_delta,
+            true,
+            U256::MAX,
+        )?;
+        position.collateral = if price_impact.is_negative() {
+            safe_add(position.collateral, price_impact.unsigned_abs())?
+        } else {
+            safe_sub(position.collateral, price_impact.unsigned_abs())
+                .map_err(|_| VaultError::CollateralLessThenFees)?
+        };
+
+        position.size = safe_add(position.size, size_delta)?;
+
+        // the funding fee is settled, so only the closing fee remains
+        let margin_fees = fee_manager.get_position_fee(self, position.size)?;
+        let liquidation_price = self.get_liquidation_price_internal(
+            position.size,
+            position.collateral,
+            position.average_price,
+            is_long,
+            margin_fees,
+        )?;
+
+        Ok((
+            fee,
+            price_impact,
+            position.size,
+            position.collateral,
+            position.average_price,
+            get_leverage(position.size, position.collateral)?,
+            liquidation_price,
+        ))
+    }
+
+    /// returns the fee, price impact, usd out, usd out after fee, next size, next collateral,
+    /// leverage in basis points and liquidation price of the position after the decrease
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    pub fn quote_decrease_position(
+        &self,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        collateral_delta: U256,
+        size_delta: U256,
+        is_long: bool,
+    ) -> Result<(U256, I256, U256, U256, U256, U256, U256, U256), Vec<u8>> {
+        let fee_manager = IFeeManager::new(self.fee_manager.get());
+
+        let mut position = self.position(account, collateral_token, index_token, is_long)?;
+        validate(position.size > U256::ZERO, VaultError::ZeroSize)?;
+        validate(position.size >= size_delta, VaultError::SizeLessThenDelta)?;
+        validate(
+            position.collateral >= collateral_delta,
+            VaultError::CollateralLessThenDelta,
+        )?;
+
+        let (min_size, min_collateral) =
+            IPositionsManager::new(self.positions_manager.get()).min_position(self, index_token)?;
+        let size_delta = get_decrease_size_delta(
+            &position,
+            collateral_delta,
+            size_delta,
+            min_size,
+            min_collateral,
+        );
+
+        // mirrors `reduce_collateral`, the decrease does not update the funding rate
+        let fee = safe_add(
+            fee_manager.get_position_fee(self, size_delta)?,
+            fee_manager.get_funding_fee(
+                self,
+                collateral_token,
+                position.size,
+                position.entry_funding_rate,
+            )?,
+        )?;
+
+        let (has_profit, delta) = self.get_delta(
+            index_token,
+            position.size,
+            position.average_price,
+            is_long,
+            position.last_increased_time,
+        )?;
+        let adjusted_delta = safe_mul_ratio(size_delta, delta, position.size)?;
+
+        let mut usd_out = U256::ZERO;
+        if has_profit {
+            usd_out = adjusted_delta;
+        } else if adjusted_delta > U256::ZERO {
+            if position.collateral < adjusted_delta && self.is_cross_margin(account, is_long)? {
+                position.collateral = adjusted_delta;
+            }
+            position.collateral = safe_sub(position.collateral, adjusted_delta)?;
+        }
+
+        if collateral_delta > U256::ZERO {
+            usd_out = safe_add(usd_out, collateral_delta)?;
+            position.collateral = safe_sub(position.collateral, collateral_delta)?;
+        }
+
+        if position.size == size_delta {
+            usd_out = safe_add(usd_out, position.collateral)?;
+            position.collateral = U256::ZERO;
+        }
+
+        let mut usd_out_after_fee = if usd_out > fee {
+            safe_sub(usd_out, fee)?
+        } else {
+            position.collateral = safe_sub(position.collateral, fee)?;
+            usd_out
+        };
+
+        // mirrors `PositionsDecreaseManager::decrease_position`
+        let collateral_info: TokenInfo = IVault::new(self.vault.get())
+            .get_token_info(self, collateral_token)?
+            .into();
+        let price_impact = self.quote_price_impact(
+            index_token,
+            collateral_token,
+            &collateral_info,
+            is_long,
+            size_delta,
+            false,
+            usd_out_after_fee,
+        )?;
+        if price_impact.is_negative() {
+            usd_out = safe_add(usd_out, price_impact.unsigned_abs())?;
+            usd_out_after_fee = safe_add(usd_out_after_fee, price_impact.unsigned_abs())?;
+        } else {
+            usd_out = safe_sub(usd_out, price_impact.unsigned_abs())?;
+            usd_out_after_fee = safe_sub(usd_out_after_fee, price_impact.unsigned_abs())?;
+        }
+
+        position.size = safe_sub(position.size, size_delta)?;
+
+        let margin_fees = fee_manager.get_position_fee(self, position.size)?;
+        let liquidation_price = self.get_liquidation_price_internal(
+            position.size,
+            position.collateral,
+            position.average_price,
+            is_long,
+            margin_fees,
+        )?;
+
+        Ok((
+            fee,
+            price_impact,
+            usd_out,
+            usd_out_after_fee,
+            position.size,
+            position.collateral,
+            get_leverage(position.size, position.collateral)?,
+            liquidation_price,
+        ))
     }
 
     /// for longs: next_average_price = (next_price * next_size)/ (next_size + delta)
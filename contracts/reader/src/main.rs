@@ -0,0 +1,205 @@
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+use alloy_primitives::{Address, U256};
+use omx_interfaces::{
+    erc20::IErc20,
+    vault::{
+        position::Position, validate, IFeeManager, IFundingRateManager, IPositionsManager,
+        ISwapManager, IVault, IVaultUtils, VaultError,
+    },
+    vault_price_feed::IVaultPriceFeed,
+};
+use stylus_sdk::prelude::*;
+
+/// number of values returned by `get_positions` for each position
+pub const POSITION_PROPS_LENGTH: usize = 9;
+/// number of values returned by `get_vault_token_info` for each token
+pub const VAULT_TOKEN_PROPS_LENGTH: usize = 9;
+
+sol_storage! {
+    #[entrypoint]
+    pub struct Reader {
+        bool initialized;
+
+        address vault;
+        address positions_manager;
+        address vault_utils;
+        address swap_manager;
+        address funding_rate_manager;
+        address fee_manager;
+        address price_feed;
+    }
+}
+
+impl Reader {
+    fn position(
+        &self,
+        account: Address,
+        collateral_token: Address,
+        index_token: Address,
+        is_long: bool,
+    ) -> Result<Position, Vec<u8>> {
+        Ok(IPositionsManager::new(self.positions_manager.get())
+            .position(self, account, collateral_token, index_token, is_long)?
+            .into())
+    }
+}
+
+#[external]
+impl Reader {
+    #[allow(clippy::too_many_arguments)]
+    pub fn init(
+        &mut self,
+        vault: Address,
+        positions_manager: Address,
+        vault_utils: Address,
+        swap_manager: Address,
+        funding_rate_manager: Address,
+        fee_manager: Address,
+        price_feed: Address,
+    ) -> Result<(), Vec<u8>> {
+        validate(!self.initialized.get(), VaultError::AlreadyInitialized)?;
+
+        self.vault.set(vault);
+        self.positions_manager.set(positions_manager);
+        self.vault_utils.set(vault_utils);
+        self.swap_manager.set(swap_manager);
+        self.funding_rate_manager.set(funding_rate_manager);
+        self.fee_manager.set(fee_manager);
+        self.price_feed.set(price_feed);
+
+        self.initialized.set(true);
+
+        Ok(())
+    }
+
+    /// for each position:
+    /// - size
+    /// - collateral
+    /// - average_price
+    /// - entry_funding_rate
+    /// - has_realised_profit
+    /// - realised_pnl, absolute value
+    /// - last_increased_time
+    /// - has_profit
+    /// - delta
+    pub fn get_positions(
+        &self,
+        account: Address,
+        collateral_tokens: Vec<Address>,
+        index_tokens: Vec<Address>,
+        is_long: Vec<bool>,
+    ) -> Result<Vec<U256>, Vec<u8>> {
+        validate(
+            collateral_tokens.len() == index_tokens.len() && index_tokens.len() == is_long.len(),
+            VaultError::ArrayLengthMismatch,
+        )?;
+
+        let vault_utils = IVaultUtils::new(self.vault_utils.get());
+
+        let mut result = Vec::with_capacity(collateral_tokens.len() * POSITION_PROPS_LENGTH);
+        for i in 0..collateral_tokens.len() {
+            let position =
+                self.position(account, collateral_tokens[i], index_tokens[i], is_long[i])?;
+
+            let (has_profit, delta) = if position.average_price > U256::ZERO {
+                vault_utils.get_delta(
+                    self,
+                    index_tokens[i],
+                    position.size,
+                    position.average_price,
+                    is_long[i],
+                    position.last_increased_time,
+                )?
+            } else {
+                (false, U256::ZERO)
+            };
+
+            result.extend([
+                position.size,
+                position.collateral,
+                position.average_price,
+                position.entry_funding_rate,
+                U256::from(position.realised_pnl.is_positive() as u8),
+                position.realised_pnl.unsigned_abs(),
+                position.last_increased_time,
+                U256::from(has_profit as u8),
+                delta,
+            ]);
+        }
+
+        Ok(result)
+    }
+
+    /// for each token:
+    /// - pool_amount
+    /// - reserved_amount
+    /// - usdo_amount
+    /// - token_weight
+    /// - min_price
+    /// - max_price
+    /// - cumulative_funding_rate
+    /// - next_funding_rate
+    /// - guaranteed_usd
+    pub fn get_vault_token_info(&self, tokens: Vec<Address>) -> Result<Vec<U256>, Vec<u8>> {
+        let vault = IVault::new(self.vault.get());
+        let positions_manager = IPositionsManager::new(self.positions_manager.get());
+        let swap_manager = ISwapManager::new(self.swap_manager.get());
+        let funding_rate_manager = IFundingRateManager::new(self.funding_rate_manager.get());
+        let price_feed = IVaultPriceFeed::new(self.price_feed.get());
+
+        let mut result = Vec::with_capacity(tokens.len() * VAULT_TOKEN_PROPS_LENGTH);
+        for token in tokens {
+            result.extend([
+                vault.pool_amount(self, token)?,
+                vault.reserved_amount(self, token)?,
+                swap_manager.usdo_amount(self, token)?,
+                vault.token_weight(self, token)?,
+                price_feed.get_price(self, token, false)?,
+                price_feed.get_price(self, token, true)?,
+                funding_rate_manager.cumulative_funding_rate(self, token)?,
+                funding_rate_manager.get_next_funding_rate(self, token)?,
+                positions_manager.guaranteed_usd(self, token)?,
+            ]);
+        }
+
+        Ok(result)
+    }
+
+    /// for each token: balance of the account and total supply,
+    /// the zero address stands for the native token which has no supply
+    pub fn get_token_balances_with_supplies(
+        &self,
+        account: Address,
+        tokens: Vec<Address>,
+    ) -> Result<Vec<U256>, Vec<u8>> {
+        let mut result = Vec::with_capacity(tokens.len() * 2);
+        for token in tokens {
+            if token.is_zero() {
+                result.extend([account.balance(), U256::ZERO]);
+                continue;
+            }
+
+            let erc20 = IErc20::new(token);
+            result.extend([erc20.balance_of(self, account)?, erc20.total_supply(self)?]);
+        }
+
+        Ok(result)
+    }
+
+    /// collected fee reserves of each token
+    pub fn get_fees(&self, tokens: Vec<Address>) -> Result<Vec<U256>, Vec<u8>> {
+        let fee_manager = IFeeManager::new(self.fee_manager.get());
+
+        let mut result = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            result.push(fee_manager.get_fee_reserve(self, token)?);
+        }
+
+        Ok(result)
+    }
+}
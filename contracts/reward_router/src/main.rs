@@ -9,7 +9,7 @@ use omx_common::{call_context::GetCallContext, safe_mul_ratio};
 use omx_interfaces::{
     base_token::IBaseToken,
     olp_manager::IOlpManager,
-    reward_router::{RewardRouterError, StakeOmx, UnstakeOlp, UnstakeOmx},
+    reward_router::{RewardRouterError, StakeOlp, StakeOmx, UnstakeOlp, UnstakeOmx},
     reward_tracker::IRewardTrackerStaking,
 };
 use stylus_sdk::{evm, msg, prelude::*};
@@ -36,6 +36,11 @@ sol_storage! {
         address fee_olp_tracker_staking;
 
         address olp_manager;
+
+        /// deposit token of the staked olp tracker
+        address fee_olp_tracker;
+        /// the only account allowed to stake olp it already holds
+        address olp_vault;
     }
 }
 
@@ -48,6 +53,14 @@ impl RewardRouter {
         Ok(())
     }
 
+    fn only_olp_vault(&self) -> Result<(), RewardRouterError> {
+        if self.olp_vault.get() != msg::sender() {
+            return Err(RewardRouterError::Forbidden);
+        }
+
+        Ok(())
+    }
+
     fn compound_internal(&mut self, account: Address) -> Result<(), Vec<u8>> {
         self.compound_omx_internal(account)?;
         self.compound_olp_internal(account)?;
@@ -214,6 +227,43 @@ impl RewardRouter {
 
         evm::log(UnstakeOmx { account, amount });
 
+        Ok(())
+    }
+    fn stake_olp_internal(&mut self, account: Address, olp_amount: U256) -> Result<(), Vec<u8>> {
+        let olp = self.olp.get();
+        let fee_olp_tracker_token = self.fee_olp_tracker.get();
+
+        let fee_olp_tracker = IRewardTrackerStaking::new(self.fee_olp_tracker_staking.get());
+        fee_olp_tracker.stake_for_account(self.ctx(), account, account, olp, olp_amount)?;
+
+        let staked_olp_tracker = IRewardTrackerStaking::new(self.staked_olp_tracker_staking.get());
+        staked_olp_tracker.stake_for_account(
+            self.ctx(),
+            account,
+            account,
+            fee_olp_tracker_token,
+            olp_amount,
+        )?;
+
+        Ok(())
+    }
+
+    fn unstake_olp_internal(&mut self, account: Address, olp_amount: U256) -> Result<(), Vec<u8>> {
+        let fee_olp_tracker = IRewardTrackerStaking::new(self.fee_olp_tracker_staking.get());
+        let staked_olp_tracker = IRewardTrackerStaking::new(self.staked_olp_tracker_staking.get());
+        let fee_olp_tracker_token = self.fee_olp_tracker.get();
+
+        staked_olp_tracker.unstake_for_account(
+            self.ctx(),
+            account,
+            fee_olp_tracker_token,
+            olp_amount,
+            account,
+        )?;
+
+        let olp = self.olp.get();
+        fee_olp_tracker.unstake_for_account(self.ctx(), account, olp, olp_amount, account)?;
+
         Ok(())
     }
 }
@@ -230,6 +280,7 @@ impl RewardRouter {
         olp: Address,
         staked_omx_tracker: Address,
         bonus_omx_tracker: Address,
+        fee_olp_tracker: Address,
         staked_omx_tracker_staking: Address,
         bonus_omx_tracker_staking: Address,
         fee_omx_tracker_staking: Address,
@@ -249,6 +300,7 @@ impl RewardRouter {
         self.olp.set(olp);
         self.staked_omx_tracker.set(staked_omx_tracker);
         self.bonus_omx_tracker.set(bonus_omx_tracker);
+        self.fee_olp_tracker.set(fee_olp_tracker);
         self.staked_omx_tracker_staking
             .set(staked_omx_tracker_staking);
         self.bonus_omx_tracker_staking
@@ -272,6 +324,14 @@ impl RewardRouter {
         Ok(())
     }
 
+    pub fn set_olp_vault(&mut self, olp_vault: Address) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.olp_vault.set(olp_vault);
+
+        Ok(())
+    }
+
     pub fn batch_stake_omx_for_account(
         &mut self,
         accounts: Vec<Address>,
@@ -320,6 +380,43 @@ impl RewardRouter {
         Ok(())
     }
 
+    /// stakes olp already held by the olp vault, the trackers pull it as olp handlers.
+    /// Restricted to the vault, which enforces the redemption cooldown on deposits
+    pub fn stake_olp(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_olp_vault()?;
+
+        if amount == U256::ZERO {
+            return Err(RewardRouterError::InvalidOlpAmount.into());
+        }
+
+        self.stake_olp_internal(msg::sender(), amount)?;
+
+        evm::log(StakeOlp {
+            account: msg::sender(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// unstakes olp of the olp vault without redeeming it
+    pub fn unstake_olp(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_olp_vault()?;
+
+        if amount == U256::ZERO {
+            return Err(RewardRouterError::InvalidOlpAmount.into());
+        }
+
+        self.unstake_olp_internal(msg::sender(), amount)?;
+
+        evm::log(UnstakeOlp {
+            account: msg::sender(),
+            amount,
+        });
+
+        Ok(())
+    }
+
     pub fn mint_and_stake_olp(
         &mut self,
         token: Address,
@@ -332,7 +429,6 @@ impl RewardRouter {
         }
 
         let account = msg::sender();
-        let olp = self.olp.get();
 
         let olp_manager = IOlpManager::new(self.olp_manager.get());
         let olp_amount = olp_manager.add_liquidity_for_account(
@@ -345,17 +441,7 @@ impl RewardRouter {
             min_olp,
         )?;
 
-        let fee_olp_tracker = IRewardTrackerStaking::new(self.fee_olp_tracker_staking.get());
-        fee_olp_tracker.stake_for_account(self.ctx(), account, account, olp, olp_amount)?;
-
-        let staked_olp_tracker = IRewardTrackerStaking::new(self.staked_olp_tracker_staking.get());
-        staked_olp_tracker.stake_for_account(
-            self.ctx(),
-            account,
-            account,
-            fee_olp_tracker.address,
-            olp_amount,
-        )?;
+        self.stake_olp_internal(account, olp_amount)?;
 
         evm::log(StakeOmx { account, amount });
 
@@ -373,33 +459,76 @@ impl RewardRouter {
             return Err(RewardRouterError::InvalidOlpAmount.into());
         }
 
-        let fee_olp_tracker = IRewardTrackerStaking::new(self.fee_olp_tracker_staking.get());
-        let staked_olp_tracker = IRewardTrackerStaking::new(self.staked_olp_tracker_staking.get());
+        self.unstake_olp_internal(msg::sender(), olp_amount)?;
 
-        staked_olp_tracker.unstake_for_account(
+        let olp_manager = IOlpManager::new(self.olp_manager.get());
+        let amount_out = olp_manager.remove_liquidity_for_account(
             self.ctx(),
             msg::sender(),
-            fee_olp_tracker.address,
+            token_out,
             olp_amount,
-            msg::sender(),
+            min_out,
+            receiver,
         )?;
 
-        let olp = self.olp.get();
-        fee_olp_tracker.unstake_for_account(
+        evm::log(UnstakeOlp {
+            account: msg::sender(),
+            amount: olp_amount,
+        });
+
+        Ok(amount_out)
+    }
+
+    pub fn mint_and_stake_olp_basket(
+        &mut self,
+        tokens: Vec<Address>,
+        amounts: Vec<U256>,
+        min_usdo: U256,
+        min_olp: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let account = msg::sender();
+
+        let olp_manager = IOlpManager::new(self.olp_manager.get());
+        let olp_amount = olp_manager.add_liquidity_basket_for_account(
             self.ctx(),
-            msg::sender(),
-            olp,
-            olp_amount,
-            msg::sender(),
+            account,
+            account,
+            tokens,
+            amounts,
+            min_usdo,
+            min_olp,
         )?;
 
+        self.stake_olp_internal(account, olp_amount)?;
+
+        evm::log(StakeOlp {
+            account,
+            amount: olp_amount,
+        });
+
+        Ok(olp_amount)
+    }
+
+    pub fn unstake_and_redeem_olp_basket(
+        &mut self,
+        olp_amount: U256,
+        tokens: Vec<Address>,
+        min_amounts: Vec<U256>,
+        receiver: Address,
+    ) -> Result<Vec<U256>, Vec<u8>> {
+        if olp_amount == U256::ZERO {
+            return Err(RewardRouterError::InvalidOlpAmount.into());
+        }
+
+        self.unstake_olp_internal(msg::sender(), olp_amount)?;
+
         let olp_manager = IOlpManager::new(self.olp_manager.get());
-        let amount_out = olp_manager.remove_liquidity_for_account(
+        let amounts_out = olp_manager.remove_liquidity_basket_for_account(
             self.ctx(),
             msg::sender(),
-            token_out,
             olp_amount,
-            min_out,
+            tokens,
+            min_amounts,
             receiver,
         )?;
 
@@ -408,7 +537,7 @@ impl RewardRouter {
             amount: olp_amount,
         });
 
-        Ok(amount_out)
+        Ok(amounts_out)
     }
 
     pub fn claim(&mut self) -> Result<(), Vec<u8>> {
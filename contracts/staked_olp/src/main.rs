@@ -0,0 +1,212 @@
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+use alloy_primitives::{Address, U256};
+use omx_common::{call_context::GetCallContext, safe_add, safe_sub};
+use omx_interfaces::{
+    olp_manager::IOlpManager,
+    reward_tracker::{IRewardTracker, IRewardTrackerStaking},
+    staked_olp::{Approval, StakedOlpError, Transfer},
+};
+use stylus_sdk::{block, evm, msg, prelude::*};
+
+pub const NAME: &str = "Staked OLP";
+pub const SYMBOL: &str = "sOLP";
+pub const DECIMALS: u8 = 18;
+
+sol_storage! {
+    #[entrypoint]
+    pub struct StakedOlp {
+        bool initialized;
+
+        address olp;
+        address olp_manager;
+        address fee_olp_tracker;
+        address fee_olp_tracker_staking;
+        address staked_olp_tracker;
+        address staked_olp_tracker_staking;
+
+        mapping (address => mapping (address => uint256)) allowances;
+    }
+}
+
+impl StakedOlp {
+    fn only_initialized(&self) -> Result<(), StakedOlpError> {
+        if !self.initialized.get() {
+            return Err(StakedOlpError::NotInitialized);
+        }
+
+        Ok(())
+    }
+
+    fn approve_internal(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        if spender.is_zero() {
+            return Err(StakedOlpError::ApproveToZeroAddress.into());
+        }
+
+        self.allowances.setter(owner).insert(spender, amount);
+
+        evm::log(Approval {
+            owner,
+            spender,
+            value: amount,
+        });
+
+        Ok(())
+    }
+
+    /// moves staked olp of the sender to the recipient, rewards accrued before
+    /// the transfer stay claimable by the sender
+    fn transfer_internal(
+        &mut self,
+        sender: Address,
+        recipient: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        if sender.is_zero() {
+            return Err(StakedOlpError::TransferFromZeroAddress.into());
+        }
+        if recipient.is_zero() {
+            return Err(StakedOlpError::TransferToZeroAddress.into());
+        }
+
+        let olp_manager = IOlpManager::new(self.olp_manager.get());
+        let last_added_at = olp_manager.last_added_at(self.ctx(), sender)?;
+        let cooldown_duration = olp_manager.cooldown_duration(self.ctx())?;
+        if safe_add(last_added_at, cooldown_duration)? > U256::from(block::timestamp()) {
+            return Err(StakedOlpError::CooldownNotPassed.into());
+        }
+
+        let olp = self.olp.get();
+        let fee_olp_tracker = self.fee_olp_tracker.get();
+        let fee_olp_tracker_staking =
+            IRewardTrackerStaking::new(self.fee_olp_tracker_staking.get());
+        let staked_olp_tracker_staking =
+            IRewardTrackerStaking::new(self.staked_olp_tracker_staking.get());
+
+        staked_olp_tracker_staking.unstake_for_account(
+            self.ctx(),
+            sender,
+            fee_olp_tracker,
+            amount,
+            sender,
+        )?;
+        fee_olp_tracker_staking.unstake_for_account(self.ctx(), sender, olp, amount, sender)?;
+
+        fee_olp_tracker_staking.stake_for_account(self.ctx(), sender, recipient, olp, amount)?;
+        staked_olp_tracker_staking.stake_for_account(
+            self.ctx(),
+            recipient,
+            recipient,
+            fee_olp_tracker,
+            amount,
+        )?;
+
+        evm::log(Transfer {
+            from: sender,
+            to: recipient,
+            value: amount,
+        });
+
+        Ok(())
+    }
+}
+
+#[external]
+impl StakedOlp {
+    pub fn init(
+        &mut self,
+        olp: Address,
+        olp_manager: Address,
+        fee_olp_tracker: Address,
+        fee_olp_tracker_staking: Address,
+        staked_olp_tracker: Address,
+        staked_olp_tracker_staking: Address,
+    ) -> Result<(), Vec<u8>> {
+        if self.initialized.get() {
+            return Err(StakedOlpError::AlreadyInitialized.into());
+        }
+
+        self.olp.set(olp);
+        self.olp_manager.set(olp_manager);
+        self.fee_olp_tracker.set(fee_olp_tracker);
+        self.fee_olp_tracker_staking.set(fee_olp_tracker_staking);
+        self.staked_olp_tracker.set(staked_olp_tracker);
+        self.staked_olp_tracker_staking
+            .set(staked_olp_tracker_staking);
+
+        self.initialized.set(true);
+
+        Ok(())
+    }
+
+    pub fn name(&self) -> Result<String, Vec<u8>> {
+        Ok(NAME.into())
+    }
+
+    pub fn symbol(&self) -> Result<String, Vec<u8>> {
+        Ok(SYMBOL.into())
+    }
+
+    pub fn decimals(&self) -> Result<u8, Vec<u8>> {
+        Ok(DECIMALS)
+    }
+
+    pub fn total_supply(&self) -> Result<U256, Vec<u8>> {
+        let staked_olp_tracker = IRewardTracker::new(self.staked_olp_tracker.get());
+
+        Ok(staked_olp_tracker.total_supply(self)?)
+    }
+
+    pub fn balance_of(&self, account: Address) -> Result<U256, Vec<u8>> {
+        let staked_olp_tracker_staking =
+            IRewardTrackerStaking::new(self.staked_olp_tracker_staking.get());
+
+        let fee_olp_tracker = self.fee_olp_tracker.get();
+
+        Ok(staked_olp_tracker_staking.deposit_balance(self, account, fee_olp_tracker)?)
+    }
+
+    pub fn allowance(&self, owner: Address, spender: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.allowances.getter(owner).get(spender))
+    }
+
+    pub fn approve(&mut self, spender: Address, amount: U256) -> Result<bool, Vec<u8>> {
+        self.approve_internal(msg::sender(), spender, amount)?;
+
+        Ok(true)
+    }
+
+    pub fn transfer(&mut self, recipient: Address, amount: U256) -> Result<bool, Vec<u8>> {
+        self.only_initialized()?;
+
+        self.transfer_internal(msg::sender(), recipient, amount)?;
+
+        Ok(true)
+    }
+
+    pub fn transfer_from(
+        &mut self,
+        sender: Address,
+        recipient: Address,
+        amount: U256,
+    ) -> Result<bool, Vec<u8>> {
+        self.only_initialized()?;
+
+        let new_allowance = safe_sub(self.allowances.getter(sender).get(msg::sender()), amount)
+            .map_err(|_| StakedOlpError::InsufficientAllowance)?;
+        self.approve_internal(sender, msg::sender(), new_allowance)?;
+
+        self.transfer_internal(sender, recipient, amount)?;
+
+        Ok(true)
+    }
+}
@@ -6,14 +6,14 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 use alloy_primitives::{Address, U256};
 use omx_common::{
-    call_context::GetCallContext, safe_add, safe_mul_ratio, safe_sub, MINT_BURN_FEE_BASIS_POINTS,
-    PRICE_PRECISION, USDO_DECIMALS,
+    call_context::GetCallContext, safe_add, safe_mul_ratio, safe_mul_ratio_up, safe_sub,
+    BASIS_POINTS_DIVISOR, MINT_BURN_FEE_BASIS_POINTS, PRICE_PRECISION, USDO_DECIMALS,
 };
 use omx_interfaces::{
     base_token::IBaseToken,
     vault::{
         validate, BuyUSDO, DecreaseUsdoAmount, IFeeManager, IFundingRateManager, IVault, SellUSDO,
-        Swap, VaultError,
+        SetBufferAmount, SetMaxUsdoAmount, Swap, VaultError,
     },
 };
 use stylus_sdk::{console, evm, msg, prelude::*};
@@ -44,6 +44,24 @@ sol_storage! {
     }
 }
 
+/// amount left after the fee, same as `FeeManager::collect_swap_fees`
+fn apply_fee(amount: U256, fee_basis_points: U256) -> Result<U256, Vec<u8>> {
+    safe_mul_ratio(
+        amount,
+        safe_sub(BASIS_POINTS_DIVISOR, fee_basis_points)?,
+        BASIS_POINTS_DIVISOR,
+    )
+}
+
+/// smallest amount that is at least `amount_after_fees` after the fee
+fn reverse_fee(amount_after_fees: U256, fee_basis_points: U256) -> Result<U256, Vec<u8>> {
+    safe_mul_ratio_up(
+        amount_after_fees,
+        BASIS_POINTS_DIVISOR,
+        safe_sub(BASIS_POINTS_DIVISOR, fee_basis_points)?,
+    )
+}
+
 impl SwapManager {
     fn only_gov(&self) -> Result<(), Vec<u8>> {
         validate(msg::sender() == self.gov.get(), VaultError::Forbidden)?;
@@ -144,30 +162,130 @@ impl SwapManager {
         Ok(())
     }
 
+    fn decimals(&self, token: Address) -> Result<u8, Vec<u8>> {
+        if token == self.usdo.get() {
+            Ok(USDO_DECIMALS)
+        } else {
+            self.token_decimals(token)
+        }
+    }
+
     fn adjust_for_decimals(
         &self,
         amount: U256,
         token_div: Address,
         token_mul: Address,
     ) -> Result<U256, Vec<u8>> {
-        let decimals_div = if token_div == self.usdo.get() {
-            USDO_DECIMALS
-        } else {
-            self.token_decimals(token_div)?
-        };
-        let decimals_mul = if token_mul == self.usdo.get() {
-            USDO_DECIMALS
-        } else {
-            self.token_decimals(token_mul)?
-        };
-
         safe_mul_ratio(
             amount,
-            U256::from(10).pow(U256::from(decimals_mul)),
-            U256::from(10).pow(U256::from(decimals_div)),
+            U256::from(10).pow(U256::from(self.decimals(token_mul)?)),
+            U256::from(10).pow(U256::from(self.decimals(token_div)?)),
+        )
+    }
+
+    fn adjust_for_decimals_up(
+        &self,
+        amount: U256,
+        token_div: Address,
+        token_mul: Address,
+    ) -> Result<U256, Vec<u8>> {
+        safe_mul_ratio_up(
+            amount,
+            U256::from(10).pow(U256::from(self.decimals(token_mul)?)),
+            U256::from(10).pow(U256::from(self.decimals(token_div)?)),
         )
     }
 
+    fn token_to_usdo(&self, token: Address, amount: U256, price: U256) -> Result<U256, Vec<u8>> {
+        let usdo_amount = safe_mul_ratio(amount, price, PRICE_PRECISION)?;
+
+        self.adjust_for_decimals(usdo_amount, token, self.usdo.get())
+    }
+
+    fn usdo_to_token(
+        &self,
+        token: Address,
+        usdo_amount: U256,
+        price: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let amount = safe_mul_ratio(usdo_amount, PRICE_PRECISION, price)?;
+
+        self.adjust_for_decimals(amount, self.usdo.get(), token)
+    }
+
+    fn get_swap_fee_basis_points(
+        &self,
+        token_in: Address,
+        token_out: Address,
+    ) -> Result<U256, Vec<u8>> {
+        Ok(IFeeManager::new(self.fee_manager.get())
+            .get_swap_fee_basis_points(self, token_in, token_out)?)
+    }
+
+    /// returns usdo_amount, mint_amount and fee_basis_points of `buy_usdo`
+    fn quote_buy_usdo(
+        &self,
+        token: Address,
+        token_amount: U256,
+    ) -> Result<(U256, U256, U256), Vec<u8>> {
+        let price = self.get_price(token)?;
+        let fee_basis_points = MINT_BURN_FEE_BASIS_POINTS;
+
+        let usdo_amount = self.token_to_usdo(token, token_amount, price)?;
+        let amount_after_fees = apply_fee(token_amount, fee_basis_points)?;
+        let mint_amount = self.token_to_usdo(token, amount_after_fees, price)?;
+
+        Ok((usdo_amount, mint_amount, fee_basis_points))
+    }
+
+    /// returns redemption_amount, amount_out and fee_basis_points of `sell_usdo`
+    fn quote_sell_usdo(
+        &self,
+        token: Address,
+        usdo_amount: U256,
+    ) -> Result<(U256, U256, U256), Vec<u8>> {
+        let price = self.get_price(token)?;
+        let fee_basis_points = MINT_BURN_FEE_BASIS_POINTS;
+
+        let redemption_amount = self.usdo_to_token(token, usdo_amount, price)?;
+        let amount_out = apply_fee(redemption_amount, fee_basis_points)?;
+
+        Ok((redemption_amount, amount_out, fee_basis_points))
+    }
+
+    /// returns amount_out, amount_out_after_fees and fee_basis_points of `swap`
+    fn quote_swap(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+    ) -> Result<(U256, U256, U256), Vec<u8>> {
+        let price_in = self.get_price(token_in)?;
+        let price_out = self.get_price(token_out)?;
+
+        let amount_out = safe_mul_ratio(amount_in, price_in, price_out)?;
+        let amount_out = self.adjust_for_decimals(amount_out, token_in, token_out)?;
+
+        let fee_basis_points = self.get_swap_fee_basis_points(token_in, token_out)?;
+        let amount_out_after_fees = apply_fee(amount_out, fee_basis_points)?;
+
+        Ok((amount_out, amount_out_after_fees, fee_basis_points))
+    }
+
+    fn validate_quote(&self, token_in: Address, token_out: Address) -> Result<(), Vec<u8>> {
+        validate(token_in != token_out, VaultError::SameToken)?;
+
+        let usdo = self.usdo.get();
+        if token_in != usdo {
+            self.validate_white_listed(token_in)?;
+        }
+        if token_out != usdo {
+            self.validate_white_listed(token_out)?;
+        }
+
+        Ok(())
+    }
+
     fn collect_swap_fees(
         &mut self,
         token: Address,
@@ -238,6 +356,116 @@ impl SwapManager {
         Ok(self.usdo_amounts.get(token))
     }
 
+    pub fn set_buffer_amount(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.buffer_amounts.insert(token, amount);
+
+        evm::log(SetBufferAmount { token, amount });
+
+        Ok(())
+    }
+
+    pub fn buffer_amount(&self, token: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.buffer_amounts.get(token))
+    }
+
+    /// zero amount removes the cap
+    pub fn set_max_usdo_amount(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.max_usdo_amounts.insert(token, amount);
+
+        evm::log(SetMaxUsdoAmount { token, amount });
+
+        Ok(())
+    }
+
+    pub fn max_usdo_amount(&self, token: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.max_usdo_amounts.get(token))
+    }
+
+    /// amount of USDO debt that can still be added for the token by `buy_usdo` or `swap`
+    pub fn get_max_usdo_mint(&self, token: Address) -> Result<U256, Vec<u8>> {
+        let max_usdo_amount = self.max_usdo_amounts.get(token);
+        if max_usdo_amount == U256::ZERO {
+            return Ok(U256::MAX);
+        }
+
+        Ok(max_usdo_amount.saturating_sub(self.usdo_amounts.get(token)))
+    }
+
+    /// amount of the token that can still be swapped out of the pool before reaching the buffer
+    pub fn get_max_swap_out(&self, token: Address) -> Result<U256, Vec<u8>> {
+        let pool_amount = self.pool_amount(token)?;
+
+        Ok(pool_amount.saturating_sub(self.buffer_amounts.get(token)))
+    }
+
+    /// quotes `buy_usdo`, `sell_usdo` or `swap` depending on the tokens,
+    /// returns amount_out, amount_out_after_fees and fee_basis_points.
+    /// pool capacity limits are not checked
+    pub fn get_amount_out(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+    ) -> Result<(U256, U256, U256), Vec<u8>> {
+        self.validate_quote(token_in, token_out)?;
+
+        let usdo = self.usdo.get();
+        if token_out == usdo {
+            self.quote_buy_usdo(token_in, amount_in)
+        } else if token_in == usdo {
+            self.quote_sell_usdo(token_out, amount_in)
+        } else {
+            self.quote_swap(token_in, token_out, amount_in)
+        }
+    }
+
+    /// smallest amount_in that gives at least `amount_out_after_fees`,
+    /// returns amount_in, amount_out and fee_basis_points
+    pub fn get_amount_in(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_out_after_fees: U256,
+    ) -> Result<(U256, U256, U256), Vec<u8>> {
+        self.validate_quote(token_in, token_out)?;
+
+        let usdo = self.usdo.get();
+        // every conversion of the forward quote is reversed in the opposite order and rounded up
+        let amount_in = if token_out == usdo {
+            let price = self.get_price(token_in)?;
+            let amount_after_fees =
+                self.adjust_for_decimals_up(amount_out_after_fees, usdo, token_in)?;
+            let amount_after_fees = safe_mul_ratio_up(amount_after_fees, PRICE_PRECISION, price)?;
+
+            reverse_fee(amount_after_fees, MINT_BURN_FEE_BASIS_POINTS)?
+        } else if token_in == usdo {
+            let price = self.get_price(token_out)?;
+            let redemption_amount = reverse_fee(amount_out_after_fees, MINT_BURN_FEE_BASIS_POINTS)?;
+            let redemption_amount =
+                self.adjust_for_decimals_up(redemption_amount, token_out, usdo)?;
+
+            safe_mul_ratio_up(redemption_amount, price, PRICE_PRECISION)?
+        } else {
+            let price_in = self.get_price(token_in)?;
+            let price_out = self.get_price(token_out)?;
+            let fee_basis_points = self.get_swap_fee_basis_points(token_in, token_out)?;
+
+            let amount_out = reverse_fee(amount_out_after_fees, fee_basis_points)?;
+            let amount_out = self.adjust_for_decimals_up(amount_out, token_out, token_in)?;
+
+            safe_mul_ratio_up(amount_out, price_out, price_in)?
+        };
+
+        let (amount_out, _, fee_basis_points) =
+            self.get_amount_out(token_in, token_out, amount_in)?;
+
+        Ok((amount_in, amount_out, fee_basis_points))
+    }
+
     pub fn buy_usdo(&mut self, token: Address, receiver: Address) -> Result<U256, Vec<u8>> {
         self.only_manager()?;
 
@@ -248,18 +476,11 @@ impl SwapManager {
 
         self.update_cumulative_funding_rate(token)?;
 
-        let price = self.get_price(token)?;
-
-        let usdo_amount = safe_mul_ratio(token_amount, price, PRICE_PRECISION)?;
-        let usdo_amount = self.adjust_for_decimals(usdo_amount, token, self.usdo.get())?;
+        let (usdo_amount, mint_amount, fee_basis_points) =
+            self.quote_buy_usdo(token, token_amount)?;
         validate(usdo_amount > U256::ZERO, VaultError::ZeroUsdoAmount)?;
 
-        let fee_basis_points = MINT_BURN_FEE_BASIS_POINTS;
-
         let amount_after_fees = self.collect_swap_fees(token, token_amount, fee_basis_points)?;
-        let mint_amount = safe_mul_ratio(amount_after_fees, price, PRICE_PRECISION)?;
-        let usdo = self.usdo.get();
-        let mint_amount = self.adjust_for_decimals(mint_amount, token, usdo)?;
 
         validate(mint_amount > U256::ZERO, VaultError::ZeroUsdoAmount)?;
 
@@ -288,10 +509,7 @@ impl SwapManager {
 
         self.update_cumulative_funding_rate(token)?;
 
-        let price = self.get_price(token)?;
-        let redemption_amount = safe_mul_ratio(usdo_amount, PRICE_PRECISION, price)?;
-        let redemption_amount =
-            self.adjust_for_decimals(redemption_amount, self.usdo.get(), token)?;
+        let (redemption_amount, _, fee_basis_points) = self.quote_sell_usdo(token, usdo_amount)?;
 
         validate(
             redemption_amount > U256::ZERO,
@@ -312,8 +530,6 @@ impl SwapManager {
         let vault = IVault::new(self.vault.get());
         vault.update_token_balance(self.ctx(), usdo.address)?;
 
-        let fee_basis_points = MINT_BURN_FEE_BASIS_POINTS;
-
         let amount_out = self.collect_swap_fees(token, redemption_amount, fee_basis_points)?;
         validate(amount_out > U256::ZERO, VaultError::ZeroAmount)?;
 
@@ -330,6 +546,98 @@ impl SwapManager {
         Ok(amount_out)
     }
 
+    /// redeems the usdo sent to the vault pro-rata to the available liquidity of every
+    /// whitelisted token, no swap fees are charged since the pool composition is unchanged.
+    /// Returns the tokens in the order of `Vault::all_whitelisted_tokens` with their amounts
+    pub fn sell_usdo_basket(
+        &mut self,
+        receiver: Address,
+    ) -> Result<(Vec<Address>, Vec<U256>), Vec<u8>> {
+        self.only_manager()?;
+
+        let usdo_amount = self.transfer_in(self.usdo.get())?;
+        validate(usdo_amount > U256::ZERO, VaultError::ZeroUsdoAmount)?;
+
+        let vault = IVault::new(self.vault.get());
+
+        let length: u64 = vault.all_whitelisted_tokens_length(self.ctx())?.to();
+        let mut tokens = Vec::with_capacity(length as usize);
+        let mut values = Vec::with_capacity(length as usize);
+        let mut total_value = U256::ZERO;
+        for i in 0..length {
+            let token = vault.all_whitelisted_tokens(self.ctx(), i)?;
+            let value = if vault.is_whitelisted(self.ctx(), token)? {
+                let available = safe_sub(
+                    self.pool_amount(token)?,
+                    vault.reserved_amount(self.ctx(), token)?,
+                )?;
+                self.token_to_usdo(token, available, self.get_price(token)?)?
+            } else {
+                U256::ZERO
+            };
+
+            tokens.push(token);
+            values.push(value);
+            total_value = safe_add(total_value, value)?;
+        }
+        validate(total_value > U256::ZERO, VaultError::ZeroRedemptionAmount)?;
+
+        // the rounding dust of the shares goes to the last token with liquidity
+        let last = values.iter().rposition(|value| *value > U256::ZERO);
+        let mut remaining = usdo_amount;
+
+        let mut amounts_out = vec![U256::ZERO; tokens.len()];
+        for (i, &token) in tokens.iter().enumerate() {
+            if values[i] == U256::ZERO {
+                continue;
+            }
+
+            let token_usdo = if Some(i) == last {
+                remaining
+            } else {
+                safe_mul_ratio(usdo_amount, values[i], total_value)?
+            };
+            remaining = safe_sub(remaining, token_usdo)?;
+            if token_usdo == U256::ZERO {
+                continue;
+            }
+
+            self.update_cumulative_funding_rate(token)?;
+
+            let amount_out = self.usdo_to_token(token, token_usdo, self.get_price(token)?)?;
+
+            self.decrease_usdo_amount(token, token_usdo)?;
+            self.decrease_pool_amount(token, amount_out)?;
+
+            validate(
+                self.pool_amount(token)? >= self.buffer_amounts.get(token),
+                VaultError::PoolLessThenBuffer,
+            )?;
+
+            if amount_out > U256::ZERO {
+                self.transfer_out(token, amount_out, receiver)?;
+            }
+
+            evm::log(SellUSDO {
+                account: receiver,
+                token,
+                usdo_amount: token_usdo,
+                token_amount: amount_out,
+                fee_basis_points: U256::ZERO,
+            });
+
+            amounts_out[i] = amount_out;
+        }
+
+        let usdo = IBaseToken::new(self.usdo.get());
+        usdo.burn(self.ctx(), vault.address, usdo_amount)?;
+
+        // usdo is burnt instead of transferred out, see `sell_usdo`
+        vault.update_token_balance(self.ctx(), usdo.address)?;
+
+        Ok((tokens, amounts_out))
+    }
+
     pub fn swap(
         &mut self,
         token_in: Address,
@@ -347,19 +655,11 @@ impl SwapManager {
         let amount_in = self.transfer_in(token_in)?;
         validate(amount_in > U256::ZERO, VaultError::ZeroAmount)?;
 
-        let price_in = self.get_price(token_in)?;
-        let price_out = self.get_price(token_out)?;
-
-        let amount_out = safe_mul_ratio(amount_in, price_in, price_out)?;
-        let amount_out = self.adjust_for_decimals(amount_out, token_in, token_out)?;
+        let (amount_out, _, fee_basis_points) = self.quote_swap(token_in, token_out, amount_in)?;
 
         // adjust usdoAmounts by the same usdo_amount as debt is shifted between the assets
-        let usdo_amount = safe_mul_ratio(amount_in, price_in, PRICE_PRECISION)?;
-        let usdo_amount = self.adjust_for_decimals(usdo_amount, token_in, self.usdo.get())?;
+        let usdo_amount = self.token_to_usdo(token_in, amount_in, self.get_price(token_in)?)?;
 
-        let fee_manager = IFeeManager::new(self.fee_manager.get());
-        let fee_basis_points =
-            fee_manager.get_swap_fee_basis_points(self.ctx(), token_in, token_out)?;
         let amount_out_after_fees =
             self.collect_swap_fees(token_out, amount_out, fee_basis_points)?;
 
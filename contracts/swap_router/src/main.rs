@@ -8,12 +8,15 @@ use alloy_primitives::{Address, U256};
 use omx_common::call_context::GetCallContext;
 use omx_interfaces::{
     erc20::{safe_transfer, safe_transfer_from},
-    router::{RouterError, Swap, SwapPath},
+    router::{RouterError, Swap},
     vault::{ISwapManager, IVault},
     weth::IWeth,
 };
 use stylus_sdk::{console, contract, evm, msg, prelude::*};
 
+/// default max number of tokens in a swap path, including token_in and token_out
+pub const DEFAULT_MAX_PATH_LENGTH: u64 = 4;
+
 sol_storage! {
     #[entrypoint]
     pub struct SwapRouter {
@@ -24,6 +27,10 @@ sol_storage! {
         address vault;
         address swap_manager;
         address positions_router;
+
+        address gov;
+        /// max number of tokens in a swap path, including token_in and token_out
+        uint256 max_path_length;
     }
 }
 
@@ -51,26 +58,58 @@ impl SwapRouter {
         Ok(())
     }
 
+    fn only_gov(&self) -> Result<(), RouterError> {
+        if self.gov.get() != msg::sender() {
+            return Err(RouterError::Forbidden);
+        }
+
+        Ok(())
+    }
+
+    fn validate_path(&self, path: &[Address]) -> Result<(), RouterError> {
+        if path.len() < 2 || U256::from(path.len()) > self.max_path_length.get() {
+            return Err(RouterError::InvalidPath);
+        }
+
+        Ok(())
+    }
+
+    /// swaps the tokens already transferred to the vault along the path,
+    /// intermediate amounts go through this contract
     fn swap_internal(
         &mut self,
-        path: SwapPath,
+        path: &[Address],
         min_out: U256,
         receiver: Address,
     ) -> Result<U256, Vec<u8>> {
-        if path.is_direct() {
-            return self.vault_swap_internal(path.token_in(), path.token_out(), min_out, receiver);
+        self.validate_path(path)?;
+
+        let vault = self.vault.get();
+        let token_out = path[path.len() - 1];
+
+        let mut token_in = path[0];
+        for &hop_token_out in &path[1..path.len() - 1] {
+            let amount_out =
+                self.vault_swap_internal(token_in, hop_token_out, U256::ZERO, contract::address())?;
+            safe_transfer(self.ctx(), hop_token_out, vault, amount_out)?;
+
+            token_in = hop_token_out;
         }
 
-        let (token_in, intermediate, token_out) = path.unwrap_indirect();
-        let mid_out =
-            self.vault_swap_internal(token_in, intermediate, U256::ZERO, contract::address())?;
+        self.vault_swap_internal(token_in, token_out, min_out, receiver)
+    }
+
+    fn get_amount_in_internal(&self, path: &[Address], amount_out: U256) -> Result<U256, Vec<u8>> {
+        self.validate_path(path)?;
 
-        let vault = self.vault.get();
-        safe_transfer(self.ctx(), intermediate, vault, mid_out)?;
+        let swap_manager = ISwapManager::new(self.swap_manager.get());
 
-        let amount_out = self.vault_swap_internal(intermediate, token_out, min_out, receiver)?;
+        let mut amount = amount_out;
+        for hop in path.windows(2).rev() {
+            (amount, _, _) = swap_manager.get_amount_in(self, hop[0], hop[1], amount)?;
+        }
 
-        Ok(amount_out)
+        Ok(amount)
     }
 
     fn vault_swap_internal(
@@ -104,6 +143,7 @@ impl SwapRouter {
 impl SwapRouter {
     pub fn init(
         &mut self,
+        gov: Address,
         weth: Address,
         usdo: Address,
         vault: Address,
@@ -120,11 +160,40 @@ impl SwapRouter {
         self.swap_manager.set(swap_manager);
         self.positions_router.set(positions_router);
 
+        self.gov.set(gov);
+        self.max_path_length
+            .set(U256::from(DEFAULT_MAX_PATH_LENGTH));
+
         self.initialized.set(true);
 
         Ok(())
     }
 
+    pub fn set_gov(&mut self, gov: Address) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        self.gov.set(gov);
+
+        Ok(())
+    }
+
+    /// max number of tokens in a swap path, including token_in and token_out
+    pub fn set_max_path_length(&mut self, max_path_length: U256) -> Result<(), Vec<u8>> {
+        self.only_gov()?;
+
+        if max_path_length < U256::from(2) {
+            return Err(RouterError::InvalidPath.into());
+        }
+
+        self.max_path_length.set(max_path_length);
+
+        Ok(())
+    }
+
+    pub fn max_path_length(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.max_path_length.get())
+    }
+
     pub fn direct_pool_deposit(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
         let vault = self.vault.get();
 
@@ -143,9 +212,7 @@ impl SwapRouter {
     ) -> Result<U256, Vec<u8>> {
         self.only_positions_router()?;
 
-        let path = SwapPath::from_arr(path)?;
-
-        self.swap_internal(path, min_out, receiver)
+        self.swap_internal(&path, min_out, receiver)
     }
 
     pub fn swap(
@@ -155,17 +222,20 @@ impl SwapRouter {
         min_out: U256,
         receiver: Address,
     ) -> Result<(), Vec<u8>> {
+        self.validate_path(&path)?;
+
         let vault = self.vault.get();
-        let path = SwapPath::from_arr(path)?;
+        let token_in = path[0];
+        let token_out = path[path.len() - 1];
 
-        safe_transfer_from(self.ctx(), path.token_in(), msg::sender(), vault, amount_in)?;
+        safe_transfer_from(self.ctx(), token_in, msg::sender(), vault, amount_in)?;
 
-        let amount_out = self.swap_internal(path, min_out, receiver)?;
+        let amount_out = self.swap_internal(&path, min_out, receiver)?;
 
         evm::log(Swap {
             account: msg::sender(),
-            token_in: path.token_in(),
-            token_out: path.token_out(),
+            token_in,
+            token_out,
             amount_in,
             amount_out,
         });
@@ -173,6 +243,42 @@ impl SwapRouter {
         Ok(())
     }
 
+    /// swaps the least amount of `path[0]` needed to receive `amount_out` of the
+    /// last token in the path, returns the amount_in
+    ///
+    /// the amount_in is quoted without simulating the earlier hops, if the swap
+    /// then returns less than `amount_out` it reverts instead of underpaying
+    pub fn swap_exact_out(
+        &mut self,
+        path: Vec<Address>,
+        amount_out: U256,
+        max_in: U256,
+        receiver: Address,
+    ) -> Result<U256, Vec<u8>> {
+        let amount_in = self.get_amount_in_internal(&path, amount_out)?;
+        if amount_in > max_in {
+            return Err(RouterError::ExcessiveAmountIn.into());
+        }
+
+        let vault = self.vault.get();
+        let token_in = path[0];
+        let token_out = path[path.len() - 1];
+
+        safe_transfer_from(self.ctx(), token_in, msg::sender(), vault, amount_in)?;
+
+        let amount_out = self.swap_internal(&path, amount_out, receiver)?;
+
+        evm::log(Swap {
+            account: msg::sender(),
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+        });
+
+        Ok(amount_in)
+    }
+
     #[payable]
     pub fn swap_eth_to_tokens(
         &mut self,
@@ -180,16 +286,16 @@ impl SwapRouter {
         min_out: U256,
         receiver: Address,
     ) -> Result<(), Vec<u8>> {
-        let path = SwapPath::from_arr(path)?;
+        self.validate_path(&path)?;
 
         self.transfer_eth_to_vault()?;
 
-        let amount_out = self.swap_internal(path, min_out, receiver)?;
+        let amount_out = self.swap_internal(&path, min_out, receiver)?;
 
         evm::log(Swap {
             account: msg::sender(),
-            token_in: path.token_in(),
-            token_out: path.token_out(),
+            token_in: path[0],
+            token_out: path[path.len() - 1],
             amount_in: msg::value(),
             amount_out,
         });
@@ -207,14 +313,8 @@ impl SwapRouter {
         let vault = self.vault.get();
         safe_transfer_from(self.ctx(), token_in, msg::sender(), vault, amount_in)?;
 
-        let amount_out = self.swap_internal(
-            SwapPath::Direct {
-                token_in,
-                token_out: self.weth.get(),
-            },
-            min_out,
-            contract::address(),
-        )?;
+        let amount_out =
+            self.swap_internal(&[token_in, self.weth.get()], min_out, contract::address())?;
 
         self.transfer_out_eth(amount_out, receiver)?;
 
@@ -228,4 +328,29 @@ impl SwapRouter {
 
         Ok(())
     }
+
+    /// amount_out after fees of swapping `amount_in` along the path
+    ///
+    /// every hop is quoted against the current pool state, the pool and usdo changes
+    /// of the earlier hops are not simulated, so a path visiting a token twice or
+    /// close to a dynamic fee threshold can return a different amount when swapped,
+    /// callers should keep a `min_out` margin
+    pub fn get_amount_out(&self, path: Vec<Address>, amount_in: U256) -> Result<U256, Vec<u8>> {
+        self.validate_path(&path)?;
+
+        let swap_manager = ISwapManager::new(self.swap_manager.get());
+
+        let mut amount = amount_in;
+        for hop in path.windows(2) {
+            (_, amount, _) = swap_manager.get_amount_out(self, hop[0], hop[1], amount)?;
+        }
+
+        Ok(amount)
+    }
+
+    /// amount_in required to receive at least `amount_out` after fees at the end of the path,
+    /// quoted hop by hop against the current pool state like `get_amount_out`
+    pub fn get_amount_in(&self, path: Vec<Address>, amount_out: U256) -> Result<U256, Vec<u8>> {
+        self.get_amount_in_internal(&path, amount_out)
+    }
 }
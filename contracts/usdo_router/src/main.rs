@@ -0,0 +1,145 @@
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+use alloy_primitives::{Address, U256};
+use omx_common::call_context::GetCallContext;
+use omx_interfaces::{
+    erc20::safe_transfer_from,
+    usdo_router::{MintUsdo, RedeemUsdo, UsdoRouterError},
+    vault::{ISwapManager, IVault},
+};
+use stylus_sdk::{evm, msg, prelude::*};
+
+sol_storage! {
+    #[entrypoint]
+    pub struct UsdoRouter {
+        bool initialized;
+
+        address usdo;
+        address vault;
+        address swap_manager;
+    }
+}
+
+impl UsdoRouter {
+    fn only_initialized(&self) -> Result<(), UsdoRouterError> {
+        if !self.initialized.get() {
+            return Err(UsdoRouterError::NotInitialized);
+        }
+
+        Ok(())
+    }
+
+    fn validate_stable(&self, token: Address) -> Result<(), Vec<u8>> {
+        if !IVault::new(self.vault.get()).is_stable(self, token)? {
+            return Err(UsdoRouterError::NotStableToken.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[external]
+impl UsdoRouter {
+    pub fn init(
+        &mut self,
+        usdo: Address,
+        vault: Address,
+        swap_manager: Address,
+    ) -> Result<(), Vec<u8>> {
+        if self.initialized.get() {
+            return Err(UsdoRouterError::AlreadyInitialized.into());
+        }
+
+        self.usdo.set(usdo);
+        self.vault.set(vault);
+        self.swap_manager.set(swap_manager);
+
+        self.initialized.set(true);
+
+        Ok(())
+    }
+
+    /// mints USDO against `amount_in` of a stable token, returns the minted amount
+    pub fn mint_usdo(
+        &mut self,
+        token: Address,
+        amount_in: U256,
+        min_out: U256,
+        receiver: Address,
+    ) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+        self.validate_stable(token)?;
+
+        let vault = self.vault.get();
+        safe_transfer_from(self.ctx(), token, msg::sender(), vault, amount_in)?;
+
+        let swap_manager = ISwapManager::new(self.swap_manager.get());
+        let usdo_amount = swap_manager.buy_usdo(self.ctx(), token, receiver)?;
+        if usdo_amount < min_out {
+            return Err(UsdoRouterError::InsufficientAmountOut.into());
+        }
+
+        evm::log(MintUsdo {
+            account: msg::sender(),
+            token,
+            amount_in,
+            usdo_amount,
+        });
+
+        Ok(usdo_amount)
+    }
+
+    /// redeems `usdo_amount` of USDO for a whitelisted token, returns the amount out
+    pub fn redeem_usdo(
+        &mut self,
+        token: Address,
+        usdo_amount: U256,
+        min_out: U256,
+        receiver: Address,
+    ) -> Result<U256, Vec<u8>> {
+        self.only_initialized()?;
+
+        let vault = self.vault.get();
+        let usdo = self.usdo.get();
+        safe_transfer_from(self.ctx(), usdo, msg::sender(), vault, usdo_amount)?;
+
+        let swap_manager = ISwapManager::new(self.swap_manager.get());
+        let amount_out = swap_manager.sell_usdo(self.ctx(), token, receiver)?;
+        if amount_out < min_out {
+            return Err(UsdoRouterError::InsufficientAmountOut.into());
+        }
+
+        evm::log(RedeemUsdo {
+            account: msg::sender(),
+            token,
+            usdo_amount,
+            amount_out,
+        });
+
+        Ok(amount_out)
+    }
+
+    /// USDO minted after fees for `amount_in` of a stable token
+    pub fn get_mint_amount(&self, token: Address, amount_in: U256) -> Result<U256, Vec<u8>> {
+        self.validate_stable(token)?;
+
+        let swap_manager = ISwapManager::new(self.swap_manager.get());
+        let (_, usdo_amount, _) =
+            swap_manager.get_amount_out(self, token, self.usdo.get(), amount_in)?;
+
+        Ok(usdo_amount)
+    }
+
+    /// token amount after fees received for redeeming `usdo_amount` of USDO
+    pub fn get_redeem_amount(&self, token: Address, usdo_amount: U256) -> Result<U256, Vec<u8>> {
+        let swap_manager = ISwapManager::new(self.swap_manager.get());
+        let (_, amount_out, _) =
+            swap_manager.get_amount_out(self, self.usdo.get(), token, usdo_amount)?;
+
+        Ok(amount_out)
+    }
+}
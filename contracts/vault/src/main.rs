@@ -10,7 +10,7 @@ use omx_interfaces::{
     erc20::{safe_transfer, IErc20},
     vault::{
         validate, DecreasePoolAmount, DecreaseReservedAmount, DirectPoolDeposit,
-        IncreasePoolAmount, IncreaseReservedAmount, VaultError,
+        IncreasePoolAmount, IncreaseReservedAmount, RawTokenInfo, VaultError,
     },
     vault_price_feed::IVaultPriceFeed,
 };
@@ -251,6 +251,26 @@ impl Vault {
         Ok(price)
     }
 
+    pub fn get_max_price(&self, token: Address) -> Result<U256, Vec<u8>> {
+        let price = IVaultPriceFeed::new(self.price_feed.get()).get_price(self, token, true)?;
+
+        Ok(price)
+    }
+
+    pub fn get_min_price(&self, token: Address) -> Result<U256, Vec<u8>> {
+        self.get_price(token)
+    }
+
+    /// price, decimals, pool amount and reserved amount of the token in a single call
+    pub fn get_token_info(&self, token: Address) -> Result<RawTokenInfo, Vec<u8>> {
+        Ok((
+            self.get_price(token)?,
+            self.token_decimals.get(token).to::<u8>(),
+            self.pool_amounts.get(token),
+            self.reserved_amounts.get(token),
+        ))
+    }
+
     pub fn set_token_config(
         &mut self,
         token: Address,
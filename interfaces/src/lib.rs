@@ -9,10 +9,18 @@ pub mod erc20;
 #[allow(clippy::too_many_arguments)]
 pub mod olp_manager;
 #[allow(clippy::too_many_arguments)]
+pub mod olp_oracle;
+#[allow(clippy::too_many_arguments)]
+pub mod olp_vault;
+#[allow(clippy::too_many_arguments)]
 pub mod orderbook;
 #[allow(clippy::too_many_arguments)]
+pub mod position_nft;
+#[allow(clippy::too_many_arguments)]
 pub mod pyth;
 #[allow(clippy::too_many_arguments)]
+pub mod reader;
+#[allow(clippy::too_many_arguments)]
 pub mod reward_distributor;
 #[allow(clippy::too_many_arguments)]
 pub mod reward_router;
@@ -24,6 +32,8 @@ pub mod router;
 pub mod shorts_tracker;
 #[allow(clippy::too_many_arguments)]
 pub mod staked_olp;
+#[allow(clippy::too_many_arguments)]
+pub mod usdo_router;
 pub mod vault;
 #[allow(clippy::too_many_arguments)]
 pub mod vault_price_feed;
@@ -25,6 +25,26 @@ sol! {
         uint256 amount_out
     );
 
+    event AddLiquidityBasket(
+        address account,
+        address[] tokens,
+        uint256[] amounts,
+        uint256 aum_in_usdo,
+        uint256 olp_supply,
+        uint256 usdo_amount,
+        uint256 mint_amount
+    );
+
+    event RemoveLiquidityBasket(
+        address account,
+        uint256 olp_amount,
+        uint256 aum_in_usdo,
+        uint256 olp_supply,
+        uint256 usdo_amount,
+        address[] tokens,
+        uint256[] amounts_out
+    );
+
 
     error Forbidden();
     error AlreadyInitialized();
@@ -38,6 +58,10 @@ sol! {
     error InsufficientOlpOutput(uint256 amount_out);
     error InsufficientUsdoOutput(uint256 amount_out);
     error InvalidWeight(uint256 max_weight);
+    error InvalidCooldownDuration(uint256 max_duration);
+    error InvalidAumAdjustment(uint256 max_adjustment);
+    error ArrayLengthMismatch();
+    error TokenListMismatch();
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -54,6 +78,10 @@ pub enum OlpManagerError {
     InsufficientOlpOutput { amount_out: U256 },
     InsufficientUsdoOutput { amount_out: U256 },
     InvalidWeight { max_weight: U256 },
+    InvalidCooldownDuration { max_duration: U256 },
+    InvalidAumAdjustment { max_adjustment: U256 },
+    ArrayLengthMismatch,
+    TokenListMismatch,
 }
 
 impl From<OlpManagerError> for Vec<u8> {
@@ -76,6 +104,14 @@ impl From<OlpManagerError> for Vec<u8> {
                 InsufficientUsdoOutput { amount_out }.encode()
             }
             E::InvalidWeight { max_weight } => InvalidWeight { max_weight }.encode(),
+            E::InvalidCooldownDuration { max_duration } => {
+                InvalidCooldownDuration { max_duration }.encode()
+            }
+            E::InvalidAumAdjustment { max_adjustment } => {
+                InvalidAumAdjustment { max_adjustment }.encode()
+            }
+            E::ArrayLengthMismatch => ArrayLengthMismatch {}.encode(),
+            E::TokenListMismatch => TokenListMismatch {}.encode(),
         }
     }
 }
@@ -90,6 +126,16 @@ sol_interface! {
 
         function setHandler(address handler, bool is_active) external;
 
+        function setCooldownDuration(uint256 cooldown_duration) external;
+
+        function cooldownDuration() external view returns (uint256);
+
+        function lastAddedAt(address account) external view returns (uint256);
+
+        function setCooldownExempt(address account, bool is_exempt) external;
+
+        function isCooldownExempt(address account) external view returns (bool);
+
         function addLiquidity(address token, uint256 amount, uint256 min_usdo, uint256 min_olp) external returns (uint256);
 
         function addLiquidityForAccount(address funding_account, address account, address token, uint256 amount, uint256 min_usdo, uint256 min_olp) external returns (uint256);
@@ -97,22 +143,40 @@ sol_interface! {
         function removeLiquidity(address token_out, uint256 olp_amount, uint256 min_amount, address recipient) external returns (uint256);
 
         function removeLiquidityForAccount(address account, address token_out, uint256 olp_amount, uint256 min_amount, address recipient) external returns (uint256);
+
+        function addLiquidityBasket(address[] memory tokens, uint256[] memory amounts, uint256 min_usdo, uint256 min_olp) external returns (uint256);
+
+        function addLiquidityBasketForAccount(address funding_account, address account, address[] memory tokens, uint256[] memory amounts, uint256 min_usdo, uint256 min_olp) external returns (uint256);
+
+        function removeLiquidityBasket(uint256 olp_amount, address[] memory tokens, uint256[] memory min_amounts, address recipient) external returns (uint256[] memory);
+
+        function removeLiquidityBasketForAccount(address account, uint256 olp_amount, address[] memory tokens, uint256[] memory min_amounts, address recipient) external returns (uint256[] memory);
     }
 }
 
 sol_interface! {
     interface IOlpManagerUtils {
-        function init(address vault, address positions_manager, address shorts_tracker, address olp) external;
+        function init(address gov, address vault, address positions_manager, address shorts_tracker, address olp, address usdo, address olp_manager) external;
 
         function isHandler(address account) external view returns (bool);
 
         function setShortsTrackerAveragePriceWeight(uint256 weight) external;
 
-        function getPrice() external view returns (uint256);
+        function setAumAdjustment(uint256 aum_addition, uint256 aum_deduction) external;
+
+        function aumAddition() external view returns (uint256);
+
+        function aumDeduction() external view returns (uint256);
+
+        function getPrice(bool maximise) external view returns (uint256);
+
+        function getAums() external view returns (uint256, uint256);
+
+        function getAumInUsdo(bool maximise) external view returns (uint256);
 
-        function getAumInUsdo() external view returns (uint256);
+        function getUsdoLiability() external view returns (uint256);
 
-        function getAum() external view returns (uint256);
+        function getAum(bool maximise) external view returns (uint256);
 
         function getGlobalShortDelta(address token, uint256 price, uint256 size) external view returns (uint256, bool);
 
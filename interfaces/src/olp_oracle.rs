@@ -0,0 +1,60 @@
+extern crate alloc;
+
+use alloy_primitives::U256;
+use alloy_sol_types::{sol, SolError};
+use stylus_sdk::stylus_proc::sol_interface;
+
+sol! {
+    event Checkpoint(uint256 index, uint256 timestamp, uint256 max_price, uint256 min_price);
+
+    error AlreadyInitialized();
+    error NotInitialized();
+    error CheckpointTooEarly(uint256 next_checkpoint_at);
+    error SnapshotNotFound();
+    error InvalidPeriod();
+    error StaleSnapshot(uint256 latest_timestamp);
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OlpOracleError {
+    AlreadyInitialized,
+    NotInitialized,
+    CheckpointTooEarly { next_checkpoint_at: U256 },
+    SnapshotNotFound,
+    InvalidPeriod,
+    StaleSnapshot { latest_timestamp: U256 },
+}
+
+impl From<OlpOracleError> for Vec<u8> {
+    fn from(err: OlpOracleError) -> Vec<u8> {
+        use OlpOracleError as E;
+        match err {
+            E::AlreadyInitialized => AlreadyInitialized {}.encode(),
+            E::NotInitialized => NotInitialized {}.encode(),
+            E::CheckpointTooEarly { next_checkpoint_at } => {
+                CheckpointTooEarly { next_checkpoint_at }.encode()
+            }
+            E::SnapshotNotFound => SnapshotNotFound {}.encode(),
+            E::InvalidPeriod => InvalidPeriod {}.encode(),
+            E::StaleSnapshot { latest_timestamp } => StaleSnapshot { latest_timestamp }.encode(),
+        }
+    }
+}
+
+sol_interface! {
+    interface IOlpOracle {
+        function init(address olp_manager_utils) external;
+
+        function getPrice(bool maximise) external view returns (uint256);
+
+        function checkpoint() external returns (uint256);
+
+        function snapshotsCount() external view returns (uint256);
+
+        function getSnapshot(uint256 index) external view returns (uint256, uint256, uint256);
+
+        function priceAt(uint256 timestamp, bool maximise) external view returns (uint256);
+
+        function getTwap(uint256 period, bool maximise) external view returns (uint256);
+    }
+}
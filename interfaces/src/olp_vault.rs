@@ -0,0 +1,113 @@
+extern crate alloc;
+
+use alloy_sol_types::{sol, SolError};
+use stylus_sdk::stylus_proc::sol_interface;
+
+sol! {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
+    event Deposit(address indexed sender, address indexed owner, uint256 assets, uint256 shares);
+    event Withdraw(address indexed sender, address indexed receiver, address indexed owner, uint256 assets, uint256 shares);
+    event Harvest(address caller, uint256 weth_amount, uint256 olp_amount);
+
+    error Forbidden();
+    error AlreadyInitialized();
+    error NotInitialized();
+    error ZeroAmount();
+    error TransferFromZeroAddress();
+    error TransferToZeroAddress();
+    error InsufficientBalance();
+    error InsufficientAllowance();
+    error CooldownNotPassed();
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OlpVaultError {
+    Forbidden,
+    AlreadyInitialized,
+    NotInitialized,
+    ZeroAmount,
+    TransferFromZeroAddress,
+    TransferToZeroAddress,
+    InsufficientBalance,
+    InsufficientAllowance,
+    CooldownNotPassed,
+}
+
+impl From<OlpVaultError> for Vec<u8> {
+    fn from(err: OlpVaultError) -> Vec<u8> {
+        use OlpVaultError as E;
+        match err {
+            E::Forbidden => Forbidden {}.encode(),
+            E::AlreadyInitialized => AlreadyInitialized {}.encode(),
+            E::NotInitialized => NotInitialized {}.encode(),
+            E::ZeroAmount => ZeroAmount {}.encode(),
+            E::TransferFromZeroAddress => TransferFromZeroAddress {}.encode(),
+            E::TransferToZeroAddress => TransferToZeroAddress {}.encode(),
+            E::InsufficientBalance => InsufficientBalance {}.encode(),
+            E::InsufficientAllowance => InsufficientAllowance {}.encode(),
+            E::CooldownNotPassed => CooldownNotPassed {}.encode(),
+        }
+    }
+}
+
+sol_interface! {
+    interface IOlpVault {
+        function init(address gov, address olp, address weth, address vault, address olp_manager, address olp_manager_utils, address reward_router, address staked_olp_tracker_staking) external;
+
+        function setGov(address gov) external;
+
+        function name() external view returns (string memory);
+
+        function symbol() external view returns (string memory);
+
+        function decimals() external view returns (uint8);
+
+        function totalSupply() external view returns (uint256);
+
+        function balanceOf(address account) external view returns (uint256);
+
+        function allowance(address owner, address spender) external view returns (uint256);
+
+        function approve(address spender, uint256 amount) external returns (bool);
+
+        function transfer(address recipient, uint256 amount) external returns (bool);
+
+        function transferFrom(address sender, address recipient, uint256 amount) external returns (bool);
+
+        function asset() external view returns (address);
+
+        function totalAssets() external view returns (uint256);
+
+        function convertToShares(uint256 assets) external view returns (uint256);
+
+        function convertToAssets(uint256 shares) external view returns (uint256);
+
+        function maxDeposit(address receiver) external view returns (uint256);
+
+        function maxMint(address receiver) external view returns (uint256);
+
+        function maxWithdraw(address owner) external view returns (uint256);
+
+        function maxRedeem(address owner) external view returns (uint256);
+
+        function previewDeposit(uint256 assets) external view returns (uint256);
+
+        function previewMint(uint256 shares) external view returns (uint256);
+
+        function previewWithdraw(uint256 assets) external view returns (uint256);
+
+        function previewRedeem(uint256 shares) external view returns (uint256);
+
+        function deposit(uint256 assets, address receiver) external returns (uint256);
+
+        function mint(uint256 shares, address receiver) external returns (uint256);
+
+        function withdraw(uint256 assets, address receiver, address owner) external returns (uint256);
+
+        function redeem(uint256 shares, address receiver, address owner) external returns (uint256);
+
+        function harvest() external returns (uint256);
+    }
+}
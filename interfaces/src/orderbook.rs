@@ -161,6 +161,8 @@ sol! {
     error IncorrectFeeTransferred(uint256 expected);
     error OrderNotFound(uint256 order_index);
     error InvalidExecutionPrice(uint256 execution_price, uint256 trigger_price, bool trigger_above_threshold);
+    error MaxOpenInterestExceeded(uint256 available);
+    error PositionsManagerNotSet();
 }
 
 /// - account
@@ -210,7 +212,9 @@ sol_interface! {
 
 sol_interface! {
     interface IOrderbookIncrease {
-        function init(address gov, address increase_router) external;
+        function init(address gov, address increase_router, address positions_manager) external;
+
+        function setPositionsManager(address positions_manager) external;
 
         function getCurrentIndex(address account) external view returns (uint256);
 
@@ -249,6 +253,10 @@ pub enum OrderbookError {
         trigger_price: U256,
         trigger_above_threshold: bool,
     },
+    MaxOpenInterestExceeded {
+        available: U256,
+    },
+    PositionsManagerNotSet,
 }
 
 impl From<OrderbookError> for Vec<u8> {
@@ -281,6 +289,10 @@ impl From<OrderbookError> for Vec<u8> {
             E::InsufficientExecutionFee { min_execution_fee } => {
                 InsufficientExecutionFee { min_execution_fee }.encode()
             }
+            E::MaxOpenInterestExceeded { available } => {
+                MaxOpenInterestExceeded { available }.encode()
+            }
+            E::PositionsManagerNotSet => PositionsManagerNotSet {}.encode(),
         }
     }
 }
@@ -0,0 +1,114 @@
+extern crate alloc;
+
+use alloy_sol_types::{sol, SolError};
+use stylus_sdk::stylus_proc::sol_interface;
+
+sol! {
+    event Transfer(address indexed from, address indexed to, uint256 indexed token_id);
+    event Approval(address indexed owner, address indexed approved, uint256 indexed token_id);
+    event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+
+    event WrapPosition(address account, uint256 token_id, address collateral_token, address index_token, bool is_long);
+    event UnwrapPosition(address account, uint256 token_id);
+
+    error AlreadyInitialized();
+    error NotInitialized();
+    error Forbidden();
+    error TokenNotFound();
+    error TransferToZeroAddress();
+    error NotTokenOwner();
+    error PositionNotFound();
+    error PositionAlreadyWrapped();
+    error OperatorNotApproved();
+    error InvalidReceiver();
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PositionNftError {
+    AlreadyInitialized,
+    NotInitialized,
+    Forbidden,
+    TokenNotFound,
+    TransferToZeroAddress,
+    NotTokenOwner,
+    PositionNotFound,
+    PositionAlreadyWrapped,
+    OperatorNotApproved,
+    InvalidReceiver,
+}
+
+impl From<PositionNftError> for Vec<u8> {
+    fn from(err: PositionNftError) -> Vec<u8> {
+        use PositionNftError as E;
+        match err {
+            E::AlreadyInitialized => AlreadyInitialized {}.encode(),
+            E::NotInitialized => NotInitialized {}.encode(),
+            E::Forbidden => Forbidden {}.encode(),
+            E::TokenNotFound => TokenNotFound {}.encode(),
+            E::TransferToZeroAddress => TransferToZeroAddress {}.encode(),
+            E::NotTokenOwner => NotTokenOwner {}.encode(),
+            E::PositionNotFound => PositionNotFound {}.encode(),
+            E::PositionAlreadyWrapped => PositionAlreadyWrapped {}.encode(),
+            E::OperatorNotApproved => OperatorNotApproved {}.encode(),
+            E::InvalidReceiver => InvalidReceiver {}.encode(),
+        }
+    }
+}
+
+sol_interface! {
+    interface IPositionNft {
+        function init(address gov, address positions_manager, address vault_utils) external;
+
+        function setGov(address gov) external;
+
+        function setBaseUri(string calldata base_uri) external;
+
+        function name() external view returns (string memory);
+
+        function symbol() external view returns (string memory);
+
+        function tokenURI(uint256 token_id) external view returns (string memory);
+
+        function totalSupply() external view returns (uint256);
+
+        function balanceOf(address owner) external view returns (uint256);
+
+        function ownerOf(uint256 token_id) external view returns (address);
+
+        function getApproved(uint256 token_id) external view returns (address);
+
+        function isApprovedForAll(address owner, address operator) external view returns (bool);
+
+        function approve(address approved, uint256 token_id) external;
+
+        function setApprovalForAll(address operator, bool approved) external;
+
+        function transferFrom(address from, address to, uint256 token_id) external;
+
+        function safeTransferFrom(address from, address to, uint256 token_id, bytes calldata data) external;
+
+        function supportsInterface(bytes4 interface_id) external view returns (bool);
+
+        function wrap(address collateral_token, address index_token, bool is_long) external returns (uint256);
+
+        function unwrap(uint256 token_id) external;
+
+        function tokenOfPosition(bytes32 key) external view returns (uint256);
+
+        function tokenPosition(uint256 token_id) external view returns (bytes32, address, address, bool);
+
+        function positionSize(uint256 token_id) external view returns (uint256);
+
+        function positionCollateral(uint256 token_id) external view returns (uint256);
+
+        function positionEntryPrice(uint256 token_id) external view returns (uint256);
+
+        function positionPnl(uint256 token_id) external view returns (bool, uint256);
+    }
+}
+
+sol_interface! {
+    interface IErc721Receiver {
+        function onERC721Received(address operator, address from, uint256 token_id, bytes calldata data) external returns (bytes4);
+    }
+}
@@ -0,0 +1,17 @@
+extern crate alloc;
+
+use stylus_sdk::stylus_proc::sol_interface;
+
+sol_interface! {
+    interface IReader {
+        function init(address vault, address positions_manager, address vault_utils, address swap_manager, address funding_rate_manager, address fee_manager, address price_feed) external;
+
+        function getPositions(address account, address[] memory collateral_tokens, address[] memory index_tokens, bool[] memory is_long) external view returns (uint256[] memory);
+
+        function getVaultTokenInfo(address[] memory tokens) external view returns (uint256[] memory);
+
+        function getTokenBalancesWithSupplies(address account, address[] memory tokens) external view returns (uint256[] memory);
+
+        function getFees(address[] memory tokens) external view returns (uint256[] memory);
+    }
+}
@@ -10,7 +10,13 @@ sol! {
 
 sol_interface! {
     interface ISwapRouter {
-        function init(address weth, address usdo, address vault, address swap_manager, address positions_router) external;
+        function init(address gov, address weth, address usdo, address vault, address swap_manager, address positions_router) external;
+
+        function setGov(address gov) external;
+
+        function setMaxPathLength(uint256 max_path_length) external;
+
+        function maxPathLength() external view returns (uint256);
 
         function directPoolDeposit(address token, uint256 amount) external;
 
@@ -18,9 +24,15 @@ sol_interface! {
 
         function swap(address[] memory path, uint256 amount_in, uint256 min_out, address receiver) external;
 
+        function swapExactOut(address[] memory path, uint256 amount_out, uint256 max_in, address receiver) external returns (uint256);
+
         function swapEthToTokens(address[] memory path, uint256 min_out, address receiver) external payable;
 
         function swapToEth(address token_in, uint256 amount_in, uint256 min_out, address receiver) external;
+
+        function getAmountOut(address[] memory path, uint256 amount_in) external view returns (uint256);
+
+        function getAmountIn(address[] memory path, uint256 amount_out) external view returns (uint256);
     }
 }
 
@@ -31,6 +43,10 @@ sol_interface! {
         function increasePosition(address[] memory path, address index_token, uint256 amount_in, uint256 min_out, uint256 size_delta, bool is_long, uint256 price) external;
 
         function increasePositionEth(address[] memory path, address index_token, uint256 min_out, uint256 size_delta, bool is_long, uint256 price) external payable;
+
+        function depositCollateral(address[] memory path, address index_token, uint256 amount_in, uint256 min_out, bool is_long) external;
+
+        function depositMargin(address collateral_token, uint256 amount_in) external;
     }
 }
 
@@ -41,6 +57,12 @@ sol_interface! {
         function decreasePosition(address collateral_token, address index_token, uint256 collateral_delta, uint256 size_delta, bool is_long, address receiver, uint256 price) external returns (uint256);
 
         function decreasePositionEth(address collateral_token, address index_token, uint256 collateral_delta, uint256 size_delta, bool is_long, address receiver, uint256 price) external returns (uint256);
+
+        function withdrawCollateral(address collateral_token, address index_token, uint256 collateral_delta, bool is_long, address receiver) external returns (uint256);
+
+        function withdrawCollateralEth(address collateral_token, address index_token, uint256 collateral_delta, bool is_long, address receiver) external returns (uint256);
+
+        function withdrawMargin(address collateral_token, uint256 amount, address receiver) external returns (uint256);
     }
 }
 
@@ -56,6 +78,7 @@ pub enum RouterError {
     InvalidTokenIn,
     InvalidTokenOut,
     InsufficientAmountOut,
+    ExcessiveAmountIn,
 }
 
 impl From<RouterError> for Vec<u8> {
@@ -72,119 +95,51 @@ impl From<RouterError> for Vec<u8> {
             E::InvalidTokenIn => "invalid token in",
             E::InvalidTokenOut => "invalid token out",
             E::InsufficientAmountOut => "insufficient amount out",
+            E::ExcessiveAmountIn => "excessive amount in",
         };
 
         format!("Router: {err}").into()
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum SwapPath {
-    /// transfer from token_in to token_out directly
-    Direct {
-        token_in: Address,
-        token_out: Address,
-    },
-    /// transfer from token_in to token_out via an intermediate token
-    Indirect {
-        token_in: Address,
-        token_out: Address,
-        intermediate: Address,
-    },
-}
+/// swap from the first token of the path to the last one through every token in between,
+/// the max path length is enforced by the swap router
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SwapPath(Vec<Address>);
 
 impl SwapPath {
     #[inline]
     pub fn from_arr(path: Vec<Address>) -> Result<Self, Vec<u8>> {
-        if path.len() == 2 {
-            Ok(SwapPath::Direct {
-                token_in: path[0],
-                token_out: path[1],
-            })
-        } else if path.len() == 3 {
-            Ok(SwapPath::Indirect {
-                token_in: path[0],
-                intermediate: path[1],
-                token_out: path[2],
-            })
-        } else {
-            Err(RouterError::InvalidPath.into())
+        if path.len() < 2 {
+            return Err(RouterError::InvalidPath.into());
         }
+
+        Ok(SwapPath(path))
     }
 
     pub fn to_vec(&self) -> Vec<Address> {
-        match self {
-            SwapPath::Direct {
-                token_in,
-                token_out,
-            } => vec![*token_in, *token_out],
-            SwapPath::Indirect {
-                token_in,
-                intermediate,
-                token_out,
-            } => vec![*token_in, *intermediate, *token_out],
-        }
+        self.0.clone()
     }
 
     pub fn token_in(&self) -> Address {
-        match self {
-            SwapPath::Direct { token_in, .. } => *token_in,
-            SwapPath::Indirect { token_in, .. } => *token_in,
-        }
+        self.0[0]
     }
 
     pub fn token_out(&self) -> Address {
-        match self {
-            SwapPath::Direct { token_out, .. } => *token_out,
-            SwapPath::Indirect { token_out, .. } => *token_out,
-        }
+        self.0[self.0.len() - 1]
     }
 
-    pub fn second_token(&self) -> Address {
-        match self {
-            SwapPath::Direct { token_out, .. } => *token_out,
-            SwapPath::Indirect { intermediate, .. } => *intermediate,
-        }
-    }
-
-    pub fn intermediate(&self) -> Option<Address> {
-        match self {
-            SwapPath::Direct { .. } => None,
-            SwapPath::Indirect { intermediate, .. } => Some(*intermediate),
-        }
-    }
-
-    pub fn is_indirect(&self) -> bool {
-        matches!(self, SwapPath::Indirect { .. })
+    /// tokens swapped through between token_in and token_out
+    pub fn intermediates(&self) -> &[Address] {
+        &self.0[1..self.0.len() - 1]
     }
 
     pub fn is_direct(&self) -> bool {
-        matches!(self, SwapPath::Direct { .. })
-    }
-
-    pub fn unwrap_direct(&self) -> (Address, Address) {
-        match self {
-            SwapPath::Direct {
-                token_in,
-                token_out,
-            } => (*token_in, *token_out),
-            SwapPath::Indirect { .. } => panic!("unwrap_direct called on indirect path"),
-        }
-    }
-
-    pub fn unwrap_indirect(&self) -> (Address, Address, Address) {
-        match self {
-            SwapPath::Direct { .. } => panic!("unwrap_indirect called on direct path"),
-            SwapPath::Indirect {
-                token_in,
-                token_out,
-                intermediate,
-            } => (*token_in, *intermediate, *token_out),
-        }
+        self.0.len() == 2
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum CollateralPath {
     Token(Address),
     Path(SwapPath),
@@ -215,10 +170,10 @@ impl CollateralPath {
         }
     }
 
-    pub fn unwrap_path(&self) -> SwapPath {
+    pub fn unwrap_path(&self) -> &SwapPath {
         match self {
             CollateralPath::Token(_) => panic!("unwrap_path called on token"),
-            CollateralPath::Path(path) => *path,
+            CollateralPath::Path(path) => path,
         }
     }
 
@@ -229,27 +184,6 @@ impl CollateralPath {
         }
     }
 
-    pub fn is_indirect(&self) -> bool {
-        match self {
-            CollateralPath::Token(_) => false,
-            CollateralPath::Path(path) => path.is_indirect(),
-        }
-    }
-
-    pub fn unwrap_direct(&self) -> (Address, Address) {
-        match self {
-            CollateralPath::Token(_) => panic!("unwrap_direct called on token"),
-            CollateralPath::Path(path) => path.unwrap_direct(),
-        }
-    }
-
-    pub fn unwrap_indirect(&self) -> (Address, Address, Address) {
-        match self {
-            CollateralPath::Token(_) => panic!("unwrap_indirect called on token"),
-            CollateralPath::Path(path) => path.unwrap_indirect(),
-        }
-    }
-
     pub fn token_in(&self) -> Address {
         match self {
             CollateralPath::Token(token) => *token,
@@ -4,9 +4,17 @@ use alloy_sol_types::{sol, SolError};
 use stylus_sdk::stylus_proc::sol_interface;
 
 sol! {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
     error Forbidden();
     error AlreadyInitialized();
     error NotInitialized();
+    error TransferFromZeroAddress();
+    error TransferToZeroAddress();
+    error ApproveToZeroAddress();
+    error InsufficientAllowance();
+    error CooldownNotPassed();
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -14,6 +22,11 @@ pub enum StakedOlpError {
     Forbidden,
     AlreadyInitialized,
     NotInitialized,
+    TransferFromZeroAddress,
+    TransferToZeroAddress,
+    ApproveToZeroAddress,
+    InsufficientAllowance,
+    CooldownNotPassed,
 }
 
 impl From<StakedOlpError> for Vec<u8> {
@@ -23,12 +36,35 @@ impl From<StakedOlpError> for Vec<u8> {
             E::Forbidden => Forbidden {}.encode(),
             E::AlreadyInitialized => AlreadyInitialized {}.encode(),
             E::NotInitialized => NotInitialized {}.encode(),
+            E::TransferFromZeroAddress => TransferFromZeroAddress {}.encode(),
+            E::TransferToZeroAddress => TransferToZeroAddress {}.encode(),
+            E::ApproveToZeroAddress => ApproveToZeroAddress {}.encode(),
+            E::InsufficientAllowance => InsufficientAllowance {}.encode(),
+            E::CooldownNotPassed => CooldownNotPassed {}.encode(),
         }
     }
 }
 
 sol_interface! {
     interface IStakedOlp {
-        function init() external;
+        function init(address olp, address olp_manager, address fee_olp_tracker, address fee_olp_tracker_staking, address staked_olp_tracker, address staked_olp_tracker_staking) external;
+
+        function name() external view returns (string memory);
+
+        function symbol() external view returns (string memory);
+
+        function decimals() external view returns (uint8);
+
+        function totalSupply() external view returns (uint256);
+
+        function balanceOf(address account) external view returns (uint256);
+
+        function allowance(address owner, address spender) external view returns (uint256);
+
+        function approve(address spender, uint256 amount) external returns (bool);
+
+        function transfer(address recipient, uint256 amount) external returns (bool);
+
+        function transferFrom(address sender, address recipient, uint256 amount) external returns (bool);
     }
 }
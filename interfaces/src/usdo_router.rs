@@ -0,0 +1,48 @@
+extern crate alloc;
+
+use alloy_sol_types::{sol, SolError};
+use stylus_sdk::stylus_proc::sol_interface;
+
+sol! {
+    event MintUsdo(address account, address token, uint256 amount_in, uint256 usdo_amount);
+    event RedeemUsdo(address account, address token, uint256 usdo_amount, uint256 amount_out);
+
+    error AlreadyInitialized();
+    error NotInitialized();
+    error NotStableToken();
+    error InsufficientAmountOut();
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum UsdoRouterError {
+    AlreadyInitialized,
+    NotInitialized,
+    NotStableToken,
+    InsufficientAmountOut,
+}
+
+impl From<UsdoRouterError> for Vec<u8> {
+    fn from(err: UsdoRouterError) -> Vec<u8> {
+        use UsdoRouterError as E;
+        match err {
+            E::AlreadyInitialized => AlreadyInitialized {}.encode(),
+            E::NotInitialized => NotInitialized {}.encode(),
+            E::NotStableToken => NotStableToken {}.encode(),
+            E::InsufficientAmountOut => InsufficientAmountOut {}.encode(),
+        }
+    }
+}
+
+sol_interface! {
+    interface IUsdoRouter {
+        function init(address usdo, address vault, address swap_manager) external;
+
+        function mintUsdo(address token, uint256 amount_in, uint256 min_out, address receiver) external returns (uint256);
+
+        function redeemUsdo(address token, uint256 usdo_amount, uint256 min_out, address receiver) external returns (uint256);
+
+        function getMintAmount(address token, uint256 amount_in) external view returns (uint256);
+
+        function getRedeemAmount(address token, uint256 usdo_amount) external view returns (uint256);
+    }
+}
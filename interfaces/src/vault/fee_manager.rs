@@ -18,6 +18,8 @@ sol_interface! {
 
         function getFundingFee(address collateral_token, uint256 size, uint256 entry_funding_rate) external view returns (uint256);
 
+        function getNextFundingFee(address collateral_token, uint256 size, uint256 entry_funding_rate) external view returns (uint256);
+
         function collectMarginFees(address collateral_token, uint256 size_delta, uint256 size, uint256 entry_funding_rate) external returns (uint256);
 
         function withdrawFees(address token, address receiver) external returns (uint256);
@@ -12,6 +12,8 @@ sol_interface! {
 
         function getNextFundingRate(address token) external view returns (uint256);
 
-        function updateCumulativeFundingRate(address collateral_token) external;
+        function getNextCumulativeFundingRate(address token) external view returns (uint256);
+
+        function updateCumulativeFundingRate(address collateral_token) external returns (uint256);
     }
 }
@@ -17,6 +17,7 @@ pub mod positions_manager_utils;
 pub mod shorts_tracker;
 #[allow(clippy::too_many_arguments)]
 pub mod swap_manager;
+pub mod token_info;
 #[allow(clippy::too_many_arguments)]
 pub mod vault_core;
 #[allow(clippy::too_many_arguments)]
@@ -32,6 +33,7 @@ pub use positions_manager::*;
 pub use positions_manager_utils::*;
 pub use shorts_tracker::*;
 pub use swap_manager::*;
+pub use token_info::*;
 pub use vault_core::*;
 pub use vault_utils::*;
 
@@ -52,7 +54,9 @@ sol! {
         uint256 size_delta,
         bool is_long,
         uint256 price,
-        uint256 fee
+        uint256 fee,
+        int256 price_impact,
+        uint256 execution_price
     );
     event DecreasePosition(
         address account,
@@ -63,8 +67,40 @@ sol! {
         bool is_long,
         uint256 price,
         uint256 fee,
-        uint256 usd_out
+        uint256 usd_out,
+        int256 price_impact,
+        uint256 execution_price
+    );
+    event DepositCollateral(
+        address account,
+        address collateral_token,
+        address index_token,
+        bool is_long,
+        uint256 collateral_delta,
+        uint256 fee
+    );
+    event WithdrawCollateral(
+        address account,
+        address collateral_token,
+        address index_token,
+        bool is_long,
+        uint256 collateral_delta,
+        uint256 fee
     );
+    event PositionTransferRequested(bytes32 key, address owner, address new_owner);
+    event PositionTransferred(bytes32 key, bytes32 new_key, address owner, address new_owner);
+    event SetPositionOperator(address owner, address operator, bool approved);
+    event ApprovePosition(bytes32 key, address owner, address operator);
+    event SetCrossMargin(address account, bool enabled);
+    event SetMaxGlobalSizes(address index_token, uint256 max_long_size, uint256 max_short_size);
+    event SetMinPosition(address index_token, uint256 min_size, uint256 min_collateral);
+    event SetBufferAmount(address token, uint256 amount);
+    event SetMaxUsdoAmount(address token, uint256 amount);
+    event SetPriceImpactDepth(address index_token, uint256 depth);
+    event UpdatePriceImpactPool(address index_token, address collateral_token, uint256 amount);
+    event DepositMargin(address account, address collateral_token, uint256 amount);
+    event WithdrawMargin(address account, address collateral_token, uint256 amount);
+    event SeizeMargin(address account, address collateral_token, uint256 amount);
     event LiquidatePosition(
         address account,
         address collateral_token,
@@ -76,6 +112,15 @@ sol! {
         int256 realised_pnl,
         uint256 mark_price
     );
+    event AutoDeleveragePosition(
+        address account,
+        address collateral_token,
+        address index_token,
+        bool is_long,
+        uint256 size_delta,
+        int256 realised_pnl,
+        uint256 mark_price
+    );
     event UpdatePosition(
         uint256 size,
         uint256 collateral,
@@ -161,6 +206,7 @@ pub enum VaultError {
     PnlOverflow,
     LossesExceedCollateral,
     MaxShortsExceeded,
+    MaxLongsExceeded,
     FeesExceedCollateral,
     FeesExceedAmountOut,
     LiquidationFeesExceedCollateral,
@@ -172,6 +218,25 @@ pub enum VaultError {
     FundingIntervalTooLow,
     FundingRateFactorTooHigh,
     TokenNotWhitelisted,
+    ArrayLengthMismatch,
+    AdlNotRequired,
+    AdlPositionNotProfitable,
+    AdlInvalidOrder,
+    AdlPositionBelowThreshold,
+    GlobalLongDataReady,
+    GlobalLongDataNotReady,
+    CrossMarginDisabled,
+    MarginBalanceExceeded,
+    MarginBalanceNotZero,
+    CrossMarginLiquidationOrder,
+    PositionNotFound,
+    PositionAlreadyExists,
+    InvalidPositionOwner,
+    CrossMarginPosition,
+    PositionLocked,
+    PositionOverflow,
+    PositionSizeBelowMinimum,
+    CollateralBelowMinimum,
 }
 
 impl From<VaultError> for Vec<u8> {
@@ -188,6 +253,7 @@ impl From<VaultError> for Vec<u8> {
             E::PoolLessThenReserved => "pool_amount < reserved",
             E::PoolExceeded => "pool_amount exceeded",
             E::MaxShortsExceeded => "max shorts exceeded",
+            E::MaxLongsExceeded => "max longs exceeded",
             E::PoolExceededBalance => "pool_amount exceeded balance",
             E::CollateralNotIndex => "collateral token should be same as index for long position",
             E::CollateralNotWhitelisted => "collateral token is not whitelisted",
@@ -221,6 +287,29 @@ impl From<VaultError> for Vec<u8> {
             E::FundingRateFactorTooHigh => "funding rate factor too high",
             E::TokenNotWhitelisted => "token not whitelisted",
             E::SameToken => "token in and token out are the same",
+            E::ArrayLengthMismatch => "array length mismatch",
+            E::AdlNotRequired => "auto-deleveraging not required",
+            E::AdlPositionNotProfitable => "auto-deleveraged position is not profitable",
+            E::AdlInvalidOrder => "positions are not ordered by pnl to collateral ratio",
+            E::AdlPositionBelowThreshold => {
+                "pnl to collateral ratio is below the auto-deleveraging threshold"
+            }
+            E::GlobalLongDataReady => "global long data is already set",
+            E::GlobalLongDataNotReady => "global long data is not set",
+            E::CrossMarginDisabled => "cross margin is disabled",
+            E::MarginBalanceExceeded => "margin balance exceeded",
+            E::MarginBalanceNotZero => "margin balance is not zero",
+            E::CrossMarginLiquidationOrder => {
+                "only the position with the largest loss of the margin account can be liquidated"
+            }
+            E::PositionNotFound => "position not found",
+            E::PositionAlreadyExists => "position already exists",
+            E::InvalidPositionOwner => "invalid position owner",
+            E::CrossMarginPosition => "cross margin positions are not transferable",
+            E::PositionLocked => "position is locked",
+            E::PositionOverflow => "position value overflow",
+            E::PositionSizeBelowMinimum => "position size below minimum",
+            E::CollateralBelowMinimum => "collateral below minimum",
         };
 
         format!("Vault: {err}").into()
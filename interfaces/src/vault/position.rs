@@ -1,4 +1,5 @@
 use alloy_primitives::{keccak256, Address, FixedBytes, I256, U256};
+use omx_common::{safe_add, safe_mul_ratio, BASIS_POINTS_DIVISOR};
 use stylus_sdk::hex::ToHex;
 
 use crate::vault::{validate, VaultError};
@@ -14,6 +15,21 @@ pub struct Position {
     pub last_increased_time: U256,
 }
 
+/// liquidation state of a position, matches the state returned by `validate_liquidation`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PositionHealth {
+    Healthy = 0,
+    Liquidatable = 1,
+    MaxLeverageExceeded = 2,
+}
+
+impl From<PositionHealth> for U256 {
+    fn from(value: PositionHealth) -> Self {
+        U256::from(value as u8)
+    }
+}
+
 /// - size
 /// - collateral
 /// - average_price
@@ -83,6 +99,93 @@ pub fn validate_position(size: U256, collateral: U256) -> Result<(), Vec<u8>> {
     Ok(())
 }
 
+/// validates the usd minimums of size and collateral of open positions
+pub fn validate_min_position(
+    size: U256,
+    collateral: U256,
+    min_size: U256,
+    min_collateral: U256,
+) -> Result<(), Vec<u8>> {
+    if size == U256::ZERO {
+        return Ok(());
+    }
+
+    validate(size >= min_size, VaultError::PositionSizeBelowMinimum)?;
+    validate(
+        collateral >= min_collateral,
+        VaultError::CollateralBelowMinimum,
+    )?;
+
+    Ok(())
+}
+
+/// size delta of the decrease, a remainder below the minimums closes the whole position
+pub fn get_decrease_size_delta(
+    position: &Position,
+    collateral_delta: U256,
+    size_delta: U256,
+    min_size: U256,
+    min_collateral: U256,
+) -> U256 {
+    if size_delta >= position.size {
+        return size_delta;
+    }
+
+    let size = position.size - size_delta;
+    let collateral = position.collateral.saturating_sub(collateral_delta);
+    if size < min_size || collateral < min_collateral {
+        return position.size;
+    }
+
+    size_delta
+}
+
+/// unrealised profit of the position at the price relative to its collateral in basis points,
+/// zero for positions in loss, used to rank positions for auto-deleveraging
+pub fn get_pnl_to_collateral_bps(
+    position: &Position,
+    price: U256,
+    is_long: bool,
+) -> Result<U256, Vec<u8>> {
+    let has_profit = if is_long {
+        price > position.average_price
+    } else {
+        position.average_price > price
+    };
+    if !has_profit || position.size == U256::ZERO || position.collateral == U256::ZERO {
+        return Ok(U256::ZERO);
+    }
+
+    let delta = safe_mul_ratio(
+        position.size,
+        price.abs_diff(position.average_price),
+        position.average_price,
+    )?;
+
+    safe_mul_ratio(delta, BASIS_POINTS_DIVISOR, position.collateral)
+}
+
+/// price at which the trade was effectively executed once the usd price impact is settled,
+/// a charged impact moves the price against the trader and a rebate in their favour.
+/// buying is increasing a long or decreasing a short
+pub fn get_execution_price(
+    price: U256,
+    size_delta: U256,
+    price_impact: I256,
+    is_buy: bool,
+) -> Result<U256, Vec<u8>> {
+    if size_delta == U256::ZERO || price_impact == I256::ZERO {
+        return Ok(price);
+    }
+
+    let price_delta = safe_mul_ratio(price, price_impact.unsigned_abs(), size_delta)?;
+    if is_buy != price_impact.is_negative() {
+        safe_add(price, price_delta)
+    } else {
+        Ok(price.saturating_sub(price_delta))
+    }
+}
+
 pub fn get_position_key(
     account: Address,
     collateral_token: Address,
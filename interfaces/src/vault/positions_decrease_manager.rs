@@ -9,5 +9,9 @@ sol_interface! {
         function setGov(address gov) external;
 
         function decreasePosition(address account, address collateral_token, address index_token, uint256 collateral_delta, uint256 size_delta, bool is_long, address receiver) external returns (uint256);
+
+        function withdrawCollateral(address account, address collateral_token, address index_token, uint256 collateral_delta, bool is_long, address receiver) external returns (uint256);
+
+        function withdrawMargin(address account, address collateral_token, uint256 amount, address receiver) external returns (uint256);
     }
 }
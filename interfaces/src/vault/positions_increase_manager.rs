@@ -9,5 +9,9 @@ sol_interface! {
         function setGov(address gov) external;
 
         function increasePosition(address account, address collateral_token, address index_token, uint256 size_delta, bool is_long) external;
+
+        function depositCollateral(address account, address collateral_token, address index_token, bool is_long) external;
+
+        function depositMargin(address account, address collateral_token) external;
     }
 }
@@ -11,5 +11,17 @@ sol_interface! {
         function setLiquidator(address liquidator, bool is_active) external;
 
         function liquidatePosition(address account, address collateral_token, address index_token, bool is_long, address fee_receiver) external;
+
+        function setAdlKeeper(address keeper, bool is_active) external;
+
+        function setMaxPnlToPoolBps(address index_token, bool is_long, uint256 max_pnl_to_pool_bps) external;
+
+        function setMinAdlPnlToCollateralBps(address index_token, uint256 min_pnl_to_collateral_bps) external;
+
+        function minAdlPnlToCollateralBps(address index_token) external view returns (uint256);
+
+        function getPnlToPoolBps(address index_token, bool is_long) external view returns (uint256);
+
+        function autoDeleverage(address[] memory accounts, address collateral_token, address index_token, bool is_long, uint256[] memory size_deltas) external;
     }
 }
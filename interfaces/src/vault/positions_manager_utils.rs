@@ -6,10 +6,22 @@ sol_interface! {
     interface IPositionsManagerUtils {
         function init(address positions_manager, address positions_decrease_manager, address vault, address fee_manager, address vault_utils) external;
 
-        function reduceCollateral(address account, address collateral_token, address index_token, uint256 collateral_delta, uint256 size_delta, bool is_long) external returns (uint256, uint256);
+        function reduceCollateral(address account, address collateral_token, address index_token, uint256 collateral_delta, uint256 size_delta, bool is_long) external returns (uint256, uint256, uint256, int256);
 
         function validateLiquidation(address account, address collateral_token, address index_token, bool is_long, bool raise) external view returns (uint256, uint256);
 
+        function getPositionHealth(address account, address collateral_token, address index_token, bool is_long) external view returns (uint8, uint256);
+
+        function getLiquidationPrice(address account, address collateral_token, address index_token, bool is_long) external view returns (uint256);
+
+        function validateCrossMargin(address account, address collateral_token, bool raise) external view returns (uint256);
+
+        function getWorstCrossMarginPosition(address account, address collateral_token) external view returns (address, uint256);
+
+        function quoteIncreasePosition(address account, address collateral_token, address index_token, uint256 amount_in, uint256 size_delta, bool is_long) external view returns (uint256, int256, uint256, uint256, uint256, uint256, uint256);
+
+        function quoteDecreasePosition(address account, address collateral_token, address index_token, uint256 collateral_delta, uint256 size_delta, bool is_long) external view returns (uint256, int256, uint256, uint256, uint256, uint256, uint256, uint256);
+
         function getNextAveragePrice(address index_token, uint256 size, uint256 average_price, bool is_long, uint256 next_price, uint256 size_delta, uint256 last_increased_time) external view returns (uint256);
     }
 }
@@ -12,10 +12,28 @@ sol_interface! {
 
         function usdoAmount(address token) external view returns (uint256);
 
+        function setBufferAmount(address token, uint256 amount) external;
+
+        function bufferAmount(address token) external view returns (uint256);
+
+        function setMaxUsdoAmount(address token, uint256 amount) external;
+
+        function maxUsdoAmount(address token) external view returns (uint256);
+
+        function getMaxUsdoMint(address token) external view returns (uint256);
+
+        function getMaxSwapOut(address token) external view returns (uint256);
+
+        function getAmountOut(address token_in, address token_out, uint256 amount_in) external view returns (uint256, uint256, uint256);
+
+        function getAmountIn(address token_in, address token_out, uint256 amount_out_after_fees) external view returns (uint256, uint256, uint256);
+
         function buyUsdo(address token, address receiver) external returns (uint256);
 
         function sellUsdo(address token, address receiver) external returns (uint256);
 
+        function sellUsdoBasket(address receiver) external returns (address[] memory, uint256[] memory);
+
         function swap(address token_in, address token_out, address receiver) external returns (uint256);
     }
 }
@@ -0,0 +1,54 @@
+use alloy_primitives::U256;
+use omx_common::safe_mul_ratio;
+
+/// snapshot of the vault state of a token returned by `Vault::get_token_info`
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TokenInfo {
+    pub price: U256,
+    pub decimals: u8,
+    pub pool_amount: U256,
+    pub reserved_amount: U256,
+}
+
+/// - price
+/// - decimals
+/// - pool_amount
+/// - reserved_amount
+pub type RawTokenInfo = (U256, u8, U256, U256);
+
+impl From<RawTokenInfo> for TokenInfo {
+    fn from(value: RawTokenInfo) -> Self {
+        Self {
+            price: value.0,
+            decimals: value.1,
+            pool_amount: value.2,
+            reserved_amount: value.3,
+        }
+    }
+}
+
+impl TokenInfo {
+    pub fn token_to_usd(&self, token_amount: U256) -> Result<U256, Vec<u8>> {
+        if token_amount == U256::ZERO {
+            return Ok(U256::ZERO);
+        }
+
+        safe_mul_ratio(
+            token_amount,
+            self.price,
+            U256::from(10).pow(U256::from(self.decimals)),
+        )
+    }
+
+    pub fn usd_to_token(&self, usd_amount: U256) -> Result<U256, Vec<u8>> {
+        if usd_amount == U256::ZERO {
+            return Ok(U256::ZERO);
+        }
+
+        safe_mul_ratio(
+            usd_amount,
+            U256::from(10).pow(U256::from(self.decimals)),
+            self.price,
+        )
+    }
+}
@@ -20,8 +20,11 @@ pub mod fee_manager;
 pub mod funding_rate_manager;
 pub mod olp_manager;
 pub mod olp_manager_utils;
+pub mod olp_oracle;
+pub mod olp_vault;
 pub mod orderbook_increase;
 pub mod orderbook_swap;
+pub mod position_nft;
 pub mod positions_decrease_manager;
 pub mod positions_decrease_router;
 pub mod positions_increase_manager;
@@ -29,13 +32,16 @@ pub mod positions_increase_router;
 pub mod positions_liquidation_manager;
 pub mod positions_manager;
 pub mod positions_manager_utils;
+pub mod reader;
 pub mod reward_distributor;
 pub mod reward_router;
 pub mod reward_tracker;
 pub mod reward_tracker_staking;
 pub mod shorts_tracker;
+pub mod staked_olp;
 pub mod swap_manager;
 pub mod swap_router;
+pub mod usdo_router;
 pub mod vault;
 pub mod vault_price_feed;
 pub mod vault_utils;
@@ -88,6 +94,8 @@ pub struct StakingAddresses {
     pub reward_router: Address,
     pub olp_manager: Address,
     pub olp_manager_utils: Address,
+    pub olp_vault: Address,
+    pub staked_olp: Address,
 
     pub staked_omx_tracker: Address,
     pub staked_omx_tracker_staking: Address,
@@ -142,6 +150,14 @@ pub struct RouterAddresses {
     pub positions_decrease: Address,
     pub positions_increase: Address,
     pub swap: Address,
+    pub usdo: Address,
+}
+
+#[derive(Clone, Debug, Copy, Serialize)]
+pub struct PeripheryAddresses {
+    pub position_nft: Address,
+    pub reader: Address,
+    pub olp_oracle: Address,
 }
 
 #[derive(Clone, Debug, Copy, Serialize)]
@@ -153,6 +169,7 @@ pub struct ContractAddresses {
     pub tokens: TokensAddresses,
     pub orderbook: OrderbookAddresses,
     pub staking: StakingAddresses,
+    pub periphery: PeripheryAddresses,
 }
 
 fn get_contract_path(contract: impl Display) -> String {
@@ -176,6 +193,8 @@ impl ContractAddresses {
                 shorts_tracker: deploy("shorts_tracker"),
                 olp_manager: deploy("olp_manager"),
                 olp_manager_utils: deploy("olp_manager_utils"),
+                olp_vault: deploy("olp_vault"),
+                staked_olp: deploy("staked_olp"),
                 bonus_omx_distributor: deploy("bonus_distributor"),
                 bonus_omx_tracker: deploy("reward_tracker"),
                 bonus_omx_tracker_staking: deploy("reward_tracker_staking"),
@@ -208,6 +227,7 @@ impl ContractAddresses {
 
             router: RouterAddresses {
                 swap: deploy("swap_router"),
+                usdo: deploy("usdo_router"),
                 positions_decrease: deploy("positions_decrease_router"),
                 positions_increase: deploy("positions_increase_router"),
             },
@@ -232,6 +252,12 @@ impl ContractAddresses {
                 swap: deploy("orderbook_swap"),
                 increase: deploy("orderbook_increase"),
             },
+
+            periphery: PeripheryAddresses {
+                position_nft: deploy("position_nft"),
+                reader: deploy("reader"),
+                olp_oracle: deploy("olp_oracle"),
+            },
         }
     }
 }
@@ -7,11 +7,16 @@ use super::{DeployContext, LiveClient};
 abigen!(
     OlpManagerUtils,
     r#"[
-        function init(address vault, address positions_manager, address shorts_tracker, address olp) external
+        function init(address gov, address vault, address positions_manager, address shorts_tracker, address olp, address usdo, address olp_manager) external
         function setShortsTrackerAveragePriceWeight(uint256 weight) external
-        function getPrice() external view returns (uint256)
-        function getAumInUsdo() external view returns (uint256)
-        function getAum() external view returns (uint256)
+        function setAumAdjustment(uint256 aum_addition, uint256 aum_deduction) external
+        function aumAddition() external view returns (uint256)
+        function aumDeduction() external view returns (uint256)
+        function getPrice(bool maximise) external view returns (uint256)
+        function getAums() external view returns (uint256, uint256)
+        function getAumInUsdo(bool maximise) external view returns (uint256)
+        function getUsdoLiability() external view returns (uint256)
+        function getAum(bool maximise) external view returns (uint256)
         function getGlobalShortDelta(address token, uint256 price, uint256 size) external view returns (uint256, bool)
         function getGlobalShortAveragePrice(address token) external view returns (uint256)
     ]"#
@@ -24,6 +29,8 @@ pub struct OlpManagerUtilsInitArgs {
     pub positions_manager: Address,
     pub shorts_tracker: Address,
     pub olp: Address,
+    pub usdo: Address,
+    pub olp_manager: Address,
 }
 
 impl OlpManagerUtilsInitArgs {
@@ -31,10 +38,13 @@ impl OlpManagerUtilsInitArgs {
         let contract = OlpManagerUtils::new(addr, ctx.client());
 
         send(contract.init(
+            self.gov,
             self.vault,
             self.positions_manager,
             self.shorts_tracker,
             self.olp,
+            self.usdo,
+            self.olp_manager,
         ))
         .await
         .unwrap();
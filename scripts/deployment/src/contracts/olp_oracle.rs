@@ -0,0 +1,28 @@
+use ethers::{prelude::abigen, types::Address};
+
+use crate::utils::contract_call_helper::send;
+
+use super::{DeployContext, LiveClient};
+
+abigen!(
+    OlpOracle,
+    r#"[
+        function init(address olp_manager_utils) external
+        function checkpoint() external returns (uint256)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct OlpOracleInitArgs {
+    pub olp_manager_utils: Address,
+}
+
+impl OlpOracleInitArgs {
+    pub async fn init(self, ctx: &DeployContext, addr: Address) -> OlpOracle<LiveClient> {
+        let contract = OlpOracle::new(addr, ctx.client.clone());
+
+        send(contract.init(self.olp_manager_utils)).await.unwrap();
+
+        contract
+    }
+}
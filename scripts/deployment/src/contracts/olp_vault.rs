@@ -0,0 +1,47 @@
+use ethers::{prelude::abigen, types::Address};
+
+use crate::utils::contract_call_helper::send;
+
+use super::{DeployContext, LiveClient};
+
+abigen!(
+    OlpVault,
+    r#"[
+        function init(address gov, address olp, address weth, address vault, address olp_manager, address olp_manager_utils, address reward_router, address staked_olp_tracker_staking) external
+        function setGov(address gov) external
+        function harvest() external returns (uint256)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct OlpVaultInitArgs {
+    pub gov: Address,
+    pub olp: Address,
+    pub weth: Address,
+    pub vault: Address,
+    pub olp_manager: Address,
+    pub olp_manager_utils: Address,
+    pub reward_router: Address,
+    pub staked_olp_tracker_staking: Address,
+}
+
+impl OlpVaultInitArgs {
+    pub async fn init(self, ctx: &DeployContext, addr: Address) -> OlpVault<LiveClient> {
+        let contract = OlpVault::new(addr, ctx.client.clone());
+
+        send(contract.init(
+            self.gov,
+            self.olp,
+            self.weth,
+            self.vault,
+            self.olp_manager,
+            self.olp_manager_utils,
+            self.reward_router,
+            self.staked_olp_tracker_staking,
+        ))
+        .await
+        .unwrap();
+
+        contract
+    }
+}
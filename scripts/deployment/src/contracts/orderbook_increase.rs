@@ -7,7 +7,7 @@ use super::{DeployContext, LiveClient};
 abigen!(
     OrderbookIncrease,
     r#"[
-        function init(address gov, address increase_router) external
+        function init(address gov, address increase_router, address positions_manager) external
         function getCurrentIndex(address account) external view returns (uint256)
         function createIncreaseOrder(uint256 collateral_amount, address collateral_token, address index_token, uint256 size_delta, bool is_long, uint256 trigger_price, bool trigger_above_threshold, uint256 execution_fee) external payable
         function cancelIncreaseOrder(uint256 order_index) external
@@ -19,13 +19,14 @@ abigen!(
 pub struct OrderbookIncreaseInitArgs {
     pub gov: Address,
     pub swap_router: Address,
+    pub positions_manager: Address,
 }
 
 impl OrderbookIncreaseInitArgs {
     pub async fn init(self, ctx: &DeployContext, addr: Address) -> OrderbookIncrease<LiveClient> {
         let contract = OrderbookIncrease::new(addr, ctx.client.clone());
 
-        send(contract.init(self.gov, self.swap_router))
+        send(contract.init(self.gov, self.swap_router, self.positions_manager))
             .await
             .unwrap();
 
@@ -0,0 +1,36 @@
+use ethers::{prelude::abigen, types::Address};
+
+use crate::utils::contract_call_helper::send;
+
+use super::{DeployContext, LiveClient};
+
+abigen!(
+    PositionNft,
+    r#"[
+        function init(address gov, address positions_manager, address vault_utils) external
+        function setGov(address gov) external
+        function setBaseUri(string calldata base_uri) external
+        function wrap(address collateral_token, address index_token, bool is_long) external returns (uint256)
+        function unwrap(uint256 token_id) external
+        function tokenOfPosition(bytes32 key) external view returns (uint256)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct PositionNftInitArgs {
+    pub gov: Address,
+    pub positions_manager: Address,
+    pub vault_utils: Address,
+}
+
+impl PositionNftInitArgs {
+    pub async fn init(self, ctx: &DeployContext, addr: Address) -> PositionNft<LiveClient> {
+        let contract = PositionNft::new(addr, ctx.client.clone());
+
+        send(contract.init(self.gov, self.positions_manager, self.vault_utils))
+            .await
+            .unwrap();
+
+        contract
+    }
+}
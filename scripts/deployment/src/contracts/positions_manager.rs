@@ -15,6 +15,7 @@ abigen!(
         function position(address account, address collateral_token, address index_token, bool is_long) external view returns (uint256, uint256, uint256, uint256, uint256, int256, uint256)
         function positionUpdate(address account, address collateral_token, address index_token, bool is_long, uint256 size, uint256 collateral, uint256 average_price, uint256 entry_funding_rate, uint256 reserve_amount, int256 realised_pnl, uint256 last_increased_time) external
         function getNextGlobalShortAveragePrice(address index_token, uint256 next_price, uint256 size_delta) external view returns (uint256)
+        function setPositionTransferAgent(address agent, bool is_active) external
     ]"#
 );
 
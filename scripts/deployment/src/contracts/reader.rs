@@ -0,0 +1,45 @@
+use ethers::{prelude::abigen, types::Address};
+
+use crate::utils::contract_call_helper::send;
+
+use super::{DeployContext, LiveClient};
+
+abigen!(
+    Reader,
+    r#"[
+        function init(address vault, address positions_manager, address vault_utils, address swap_manager, address funding_rate_manager, address fee_manager, address price_feed) external
+        function getPositions(address account, address[] memory collateral_tokens, address[] memory index_tokens, bool[] memory is_long) external view returns (uint256[] memory)
+        function getVaultTokenInfo(address[] memory tokens) external view returns (uint256[] memory)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct ReaderInitArgs {
+    pub vault: Address,
+    pub positions_manager: Address,
+    pub vault_utils: Address,
+    pub swap_manager: Address,
+    pub funding_rate_manager: Address,
+    pub fee_manager: Address,
+    pub price_feed: Address,
+}
+
+impl ReaderInitArgs {
+    pub async fn init(self, ctx: &DeployContext, addr: Address) -> Reader<LiveClient> {
+        let contract = Reader::new(addr, ctx.client.clone());
+
+        send(contract.init(
+            self.vault,
+            self.positions_manager,
+            self.vault_utils,
+            self.swap_manager,
+            self.funding_rate_manager,
+            self.fee_manager,
+            self.price_feed,
+        ))
+        .await
+        .unwrap();
+
+        contract
+    }
+}
@@ -7,8 +7,9 @@ use super::{DeployContext, LiveClient};
 abigen!(
     RewardRouter,
     r#"[
-        function init(address gov, address omx, address es_omx, address bn_omx, address olp, address staked_omx_tracker, address bonus_omx_tracker, address staked_omx_tracker_staking, address bonus_omx_tracker_staking, address fee_omx_tracker_staking, address fee_olp_tracker_staking, address staked_olp_tracker_staking, address olp_manager) external
+        function init(address gov, address omx, address es_omx, address bn_omx, address olp, address staked_omx_tracker, address bonus_omx_tracker, address fee_olp_tracker, address staked_omx_tracker_staking, address bonus_omx_tracker_staking, address fee_omx_tracker_staking, address fee_olp_tracker_staking, address staked_olp_tracker_staking, address olp_manager) external
         function setGov(address gov) external
+        function setOlpVault(address olp_vault) external
         function batchStakeOmxForAccount(address[] memory accounts, uint256[] memory amounts) external
         function stakeOmxForAccount(address account, uint256 amount) external
         function stakeOmx(uint256 amount) external
@@ -35,6 +36,7 @@ pub struct RewardRouterInitArgs {
     pub olp: Address,
     pub staked_omx_tracker: Address,
     pub bonus_omx_tracker: Address,
+    pub fee_olp_tracker: Address,
     pub staked_omx_tracker_staking: Address,
     pub bonus_omx_tracker_staking: Address,
     pub fee_omx_tracker_staking: Address,
@@ -55,6 +57,7 @@ impl RewardRouterInitArgs {
             self.olp,
             self.staked_omx_tracker,
             self.bonus_omx_tracker,
+            self.fee_olp_tracker,
             self.staked_omx_tracker_staking,
             self.bonus_omx_tracker_staking,
             self.fee_omx_tracker_staking,
@@ -0,0 +1,43 @@
+use ethers::{prelude::abigen, types::Address};
+
+use crate::utils::contract_call_helper::send;
+
+use super::{DeployContext, LiveClient};
+
+abigen!(
+    StakedOlp,
+    r#"[
+        function init(address olp, address olp_manager, address fee_olp_tracker, address fee_olp_tracker_staking, address staked_olp_tracker, address staked_olp_tracker_staking) external
+        function totalSupply() external view returns (uint256)
+        function balanceOf(address account) external view returns (uint256)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct StakedOlpInitArgs {
+    pub olp: Address,
+    pub olp_manager: Address,
+    pub fee_olp_tracker: Address,
+    pub fee_olp_tracker_staking: Address,
+    pub staked_olp_tracker: Address,
+    pub staked_olp_tracker_staking: Address,
+}
+
+impl StakedOlpInitArgs {
+    pub async fn init(self, ctx: &DeployContext, addr: Address) -> StakedOlp<LiveClient> {
+        let contract = StakedOlp::new(addr, ctx.client.clone());
+
+        send(contract.init(
+            self.olp,
+            self.olp_manager,
+            self.fee_olp_tracker,
+            self.fee_olp_tracker_staking,
+            self.staked_olp_tracker,
+            self.staked_olp_tracker_staking,
+        ))
+        .await
+        .unwrap();
+
+        contract
+    }
+}
@@ -7,10 +7,14 @@ use super::{DeployContext, LiveClient};
 abigen!(
     SwapRouter,
     r#"[
-        function init(address weth, address usdo, address vault, address swap_manager, address positions_router) external
+        function init(address gov, address weth, address usdo, address vault, address swap_manager, address positions_router) external
+        function setGov(address gov) external
+        function setMaxPathLength(uint256 max_path_length) external
+        function maxPathLength() external view returns (uint256)
         function directPoolDeposit(address token, uint256 amount) external
         function swapForPosition(address[] memory path, uint256 min_out, address receiver) external returns (uint256)
         function swap(address[] memory path, uint256 amount_in, uint256 min_out, address receiver) external
+        function swapExactOut(address[] memory path, uint256 amount_out, uint256 max_in, address receiver) external returns (uint256)
         function swapEthToTokens(address[] memory path, uint256 min_out, address receiver) external payable
         function swapToEth(address token_in, uint256 amount_in, uint256 min_out, address receiver) external
     ]"#
@@ -18,6 +22,7 @@ abigen!(
 
 #[derive(Clone, Debug)]
 pub struct SwapRouterInitArgs {
+    pub gov: Address,
     pub weth: Address,
     pub usdo: Address,
     pub vault: Address,
@@ -30,6 +35,7 @@ impl SwapRouterInitArgs {
         let swap_router = SwapRouter::new(addr, ctx.client.clone());
 
         send(swap_router.init(
+            self.gov,
             self.weth,
             self.usdo,
             self.vault,
@@ -0,0 +1,33 @@
+use ethers::{prelude::abigen, types::Address};
+
+use crate::utils::contract_call_helper::send;
+
+use super::{DeployContext, LiveClient};
+
+abigen!(
+    UsdoRouter,
+    r#"[
+        function init(address usdo, address vault, address swap_manager) external
+        function mintUsdo(address token, uint256 amount_in, uint256 min_out, address receiver) external returns (uint256)
+        function redeemUsdo(address token, uint256 usdo_amount, uint256 min_out, address receiver) external returns (uint256)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct UsdoRouterInitArgs {
+    pub usdo: Address,
+    pub vault: Address,
+    pub swap_manager: Address,
+}
+
+impl UsdoRouterInitArgs {
+    pub async fn init(self, ctx: &DeployContext, addr: Address) -> UsdoRouter<LiveClient> {
+        let contract = UsdoRouter::new(addr, ctx.client.clone());
+
+        send(contract.init(self.usdo, self.vault, self.swap_manager))
+            .await
+            .unwrap();
+
+        contract
+    }
+}
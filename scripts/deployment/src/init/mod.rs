@@ -10,6 +10,7 @@ use crate::{
 
 use self::{
     orderbook::{OrderbookContracts, OrderbookInitArgs},
+    periphery::{PeripheryContracts, PeripheryContractsInitArgs},
     price_feed::init_vault_price_feed,
     router::{RouterContracts, RouterContractsInitArgs},
     staking::{StakingContracts, StakingContractsInitArgs},
@@ -18,6 +19,7 @@ use self::{
 };
 
 pub mod orderbook;
+pub mod periphery;
 pub mod price_feed;
 pub mod router;
 pub mod staking;
@@ -33,6 +35,7 @@ pub struct Contracts {
     pub vault: VaultContracts,
     pub tokens: TokensContracts,
     pub staking: StakingContracts,
+    pub periphery: PeripheryContracts,
 }
 
 impl Contracts {
@@ -71,7 +74,9 @@ impl ContractsInitArgs {
             }
             .init(ctx, contracts)
             .await,
-            router: RouterContractsInitArgs {}.init(ctx, contracts).await,
+            router: RouterContractsInitArgs { gov: self.gov }
+                .init(ctx, contracts)
+                .await,
             tokens: TokensContractsInitArgs { gov: self.gov }
                 .init(ctx, contracts)
                 .await,
@@ -84,6 +89,9 @@ impl ContractsInitArgs {
             staking: StakingContractsInitArgs { gov: self.gov }
                 .init(ctx, contracts)
                 .await,
+            periphery: PeripheryContractsInitArgs { gov: self.gov }
+                .init(ctx, contracts)
+                .await,
         };
 
         println!("Update last distribution time...");
@@ -255,6 +263,60 @@ impl ContractsInitArgs {
         )
         .await
         .unwrap();
+        // the staking contract of fee_olp_tracker pulls and returns the olp
+        send(
+            contracts
+                .tokens
+                .olp
+                .set_handler(contracts.staking.fee_olp_tracker_staking.address(), true),
+        )
+        .await
+        .unwrap();
+        // allow olp_vault to stake the olp it holds and to move olp of its depositors
+        send(
+            contracts
+                .staking
+                .reward_router
+                .set_olp_vault(contracts.staking.olp_vault.address()),
+        )
+        .await
+        .unwrap();
+        send(
+            contracts
+                .tokens
+                .olp
+                .set_handler(contracts.staking.olp_vault.address(), true),
+        )
+        .await
+        .unwrap();
+        // allow staked_olp to move staked olp between accounts in both trackers
+        send(
+            contracts
+                .staking
+                .fee_olp_tracker
+                .set_handler(contracts.staking.staked_olp.address(), true),
+        )
+        .await
+        .unwrap();
+        send(
+            contracts
+                .staking
+                .staked_olp_tracker
+                .set_handler(contracts.staking.staked_olp.address(), true),
+        )
+        .await
+        .unwrap();
+
+        println!("Configure periphery...");
+        // allow position_nft to lock and move the wrapped positions
+        send(
+            contracts
+                .vault
+                .positions_manager
+                .set_position_transfer_agent(contracts.periphery.position_nft.address(), true),
+        )
+        .await
+        .unwrap();
 
         contracts
     }
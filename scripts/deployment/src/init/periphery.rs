@@ -0,0 +1,58 @@
+use ethers::types::Address;
+
+use crate::contracts::{
+    olp_oracle::{OlpOracle, OlpOracleInitArgs},
+    position_nft::{PositionNft, PositionNftInitArgs},
+    reader::{Reader, ReaderInitArgs},
+    ContractAddresses, DeployContext, LiveClient,
+};
+
+/// Periphery contracts init helper
+#[derive(Clone, Debug)]
+pub struct PeripheryContractsInitArgs {
+    pub gov: Address,
+}
+
+/// Contracts built on top of the vault
+#[derive(Clone, Debug)]
+pub struct PeripheryContracts {
+    pub position_nft: PositionNft<LiveClient>,
+    pub reader: Reader<LiveClient>,
+    pub olp_oracle: OlpOracle<LiveClient>,
+}
+
+impl PeripheryContractsInitArgs {
+    /// Initialize all periphery contracts
+    pub async fn init(
+        self,
+        ctx: &DeployContext,
+        contracts: &ContractAddresses,
+    ) -> PeripheryContracts {
+        println!("initializing periphery contracts");
+        PeripheryContracts {
+            position_nft: PositionNftInitArgs {
+                gov: self.gov,
+                positions_manager: contracts.vault.positions_manager,
+                vault_utils: contracts.vault.vault_utils,
+            }
+            .init(ctx, contracts.periphery.position_nft)
+            .await,
+            reader: ReaderInitArgs {
+                vault: contracts.vault.vault,
+                positions_manager: contracts.vault.positions_manager,
+                vault_utils: contracts.vault.vault_utils,
+                swap_manager: contracts.vault.swap_manager,
+                funding_rate_manager: contracts.vault.funding_rate_manager,
+                fee_manager: contracts.vault.fee_manager,
+                price_feed: contracts.vault_price_feed,
+            }
+            .init(ctx, contracts.periphery.reader)
+            .await,
+            olp_oracle: OlpOracleInitArgs {
+                olp_manager_utils: contracts.staking.olp_manager_utils,
+            }
+            .init(ctx, contracts.periphery.olp_oracle)
+            .await,
+        }
+    }
+}
@@ -1,13 +1,18 @@
+use ethers::types::Address;
+
 use crate::contracts::{
     positions_decrease_router::{PositionsDecreaseRouter, PositionsDecreaseRouterInitArgs},
     positions_increase_router::{PositionsIncreaseRouter, PositionsIncreaseRouterInitArgs},
     swap_router::{SwapRouter, SwapRouterInitArgs},
+    usdo_router::{UsdoRouter, UsdoRouterInitArgs},
     ContractAddresses, DeployContext, LiveClient,
 };
 
 /// Router contracts init helper
 #[derive(Clone, Debug)]
-pub struct RouterContractsInitArgs {}
+pub struct RouterContractsInitArgs {
+    pub gov: Address,
+}
 
 /// All vault contracts
 #[derive(Clone, Debug)]
@@ -15,6 +20,7 @@ pub struct RouterContracts {
     pub positions_decrease: PositionsDecreaseRouter<LiveClient>,
     pub positions_increase: PositionsIncreaseRouter<LiveClient>,
     pub swap: SwapRouter<LiveClient>,
+    pub usdo: UsdoRouter<LiveClient>,
 }
 
 impl RouterContractsInitArgs {
@@ -24,7 +30,7 @@ impl RouterContractsInitArgs {
         RouterContracts {
             positions_decrease: PositionsDecreaseRouterInitArgs {
                 positions_decrease_manager: contracts.vault.positions_decrease_manager,
-                swap_router: contracts.vault.swap_manager,
+                swap_router: contracts.router.swap,
                 vault: contracts.vault.vault,
                 weth: contracts.tokens.weth,
             }
@@ -32,13 +38,14 @@ impl RouterContractsInitArgs {
             .await,
             positions_increase: PositionsIncreaseRouterInitArgs {
                 positions_increase_manager: contracts.vault.positions_increase_manager,
-                swap_router: contracts.vault.swap_manager,
+                swap_router: contracts.router.swap,
                 vault: contracts.vault.vault,
                 weth: contracts.tokens.weth,
             }
             .init(&ctx, contracts.router.positions_increase)
             .await,
             swap: SwapRouterInitArgs {
+                gov: self.gov,
                 positions_router: contracts.router.positions_increase,
                 swap_manager: contracts.vault.swap_manager,
                 usdo: contracts.tokens.usdo,
@@ -47,6 +54,13 @@ impl RouterContractsInitArgs {
             }
             .init(&ctx, contracts.router.swap)
             .await,
+            usdo: UsdoRouterInitArgs {
+                usdo: contracts.tokens.usdo,
+                vault: contracts.vault.vault,
+                swap_manager: contracts.vault.swap_manager,
+            }
+            .init(&ctx, contracts.router.usdo)
+            .await,
         }
     }
 }
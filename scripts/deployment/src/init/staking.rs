@@ -5,12 +5,14 @@ use paste::paste;
 use crate::contracts::{
     olp_manager::{OlpManager, OlpManagerInitArgs},
     olp_manager_utils::{OlpManagerUtils, OlpManagerUtilsInitArgs},
+    olp_vault::{OlpVault, OlpVaultInitArgs},
     bonus_distributor::{BonusDistributor, BonusDistributorInitArgs},
     reward_distributor::{RewardDistributor, RewardDistributorInitArgs},
     reward_router::{RewardRouter, RewardRouterInitArgs},
     reward_tracker::{RewardTracker, RewardTrackerInitArgs},
     reward_tracker_staking::{RewardTrackerStaking, RewardTrackerStakingInitArgs},
     shorts_tracker::{ShortsTracker, ShortsTrackerInitArgs},
+    staked_olp::{StakedOlp, StakedOlpInitArgs},
     ContractAddresses, DeployContext, LiveClient,
 };
 
@@ -26,6 +28,8 @@ pub struct StakingContracts {
     pub reward_router: RewardRouter<LiveClient>,
     pub olp_manager: OlpManager<LiveClient>,
     pub olp_manager_utils: OlpManagerUtils<LiveClient>,
+    pub olp_vault: OlpVault<LiveClient>,
+    pub staked_olp: StakedOlp<LiveClient>,
     pub shorts_tracker: ShortsTracker<LiveClient>,
 
     pub staked_omx_tracker: RewardTracker<LiveClient>,
@@ -140,6 +144,7 @@ impl StakingContractsInitArgs {
                 bn_omx: contracts.tokens.bn_omx,
                 olp_manager: contracts.staking.olp_manager,
                 bonus_omx_tracker: contracts.staking.bonus_omx_tracker,
+                fee_olp_tracker: contracts.staking.fee_olp_tracker,
                 staked_omx_tracker: contracts.staking.staked_omx_tracker,
                 fee_olp_tracker_staking: contracts.staking.fee_olp_tracker_staking,
                 bonus_omx_tracker_staking: contracts.staking.bonus_omx_tracker_staking,
@@ -167,9 +172,33 @@ impl StakingContractsInitArgs {
                 positions_manager: contracts.vault.positions_manager,
                 shorts_tracker: contracts.staking.shorts_tracker,
                 vault: contracts.vault.vault,
+                usdo: contracts.tokens.usdo,
+                olp_manager: contracts.staking.olp_manager,
             }
             .init(&ctx, contracts.staking.olp_manager_utils)
             .await,
+            olp_vault: OlpVaultInitArgs {
+                gov: self.gov,
+                olp: contracts.tokens.olp,
+                weth: contracts.tokens.weth,
+                vault: contracts.vault.vault,
+                olp_manager: contracts.staking.olp_manager,
+                olp_manager_utils: contracts.staking.olp_manager_utils,
+                reward_router: contracts.staking.reward_router,
+                staked_olp_tracker_staking: contracts.staking.staked_olp_tracker_staking,
+            }
+            .init(&ctx, contracts.staking.olp_vault)
+            .await,
+            staked_olp: StakedOlpInitArgs {
+                olp: contracts.tokens.olp,
+                olp_manager: contracts.staking.olp_manager,
+                fee_olp_tracker: contracts.staking.fee_olp_tracker,
+                fee_olp_tracker_staking: contracts.staking.fee_olp_tracker_staking,
+                staked_olp_tracker: contracts.staking.staked_olp_tracker,
+                staked_olp_tracker_staking: contracts.staking.staked_olp_tracker_staking,
+            }
+            .init(&ctx, contracts.staking.staked_olp)
+            .await,
             shorts_tracker: ShortsTrackerInitArgs {
                 gov: self.gov,
                 positions_manager: contracts.vault.positions_manager,
@@ -14,6 +14,7 @@ abigen!(
         function collectSwapFees(address token, uint256 amount, uint256 fee_basis_points) external returns (uint256)
         function getPositionFee(uint256 size_delta) external view returns (uint256)
         function getFundingFee(address collateral_token, uint256 size, uint256 entry_funding_rate) external view returns (uint256)
+        function getNextFundingFee(address collateral_token, uint256 size, uint256 entry_funding_rate) external view returns (uint256)
         function collectMarginFees(address collateral_token, uint256 size_delta, uint256 size, uint256 entry_funding_rate) external returns (uint256)
         function withdrawFees(address token, address receiver) external returns (uint256)
         function getSwapFeeBasisPoints(address token_in, address token_out) external view returns (uint256)
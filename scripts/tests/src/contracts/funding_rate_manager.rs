@@ -11,7 +11,8 @@ abigen!(
         function setGov(address gov) external
         function cumulativeFundingRate(address token) external view returns (uint256)
         function getNextFundingRate(address token) external view returns (uint256)
-        function updateCumulativeFundingRate(address collateral_token) external
+        function getNextCumulativeFundingRate(address token) external view returns (uint256)
+        function updateCumulativeFundingRate(address collateral_token) external returns (uint256)
     ]"#
 );
 
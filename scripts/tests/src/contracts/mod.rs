@@ -19,8 +19,11 @@ pub mod fee_manager;
 pub mod funding_rate_manager;
 pub mod olp_manager;
 pub mod olp_manager_utils;
+pub mod olp_oracle;
+pub mod olp_vault;
 pub mod orderbook_increase;
 pub mod orderbook_swap;
+pub mod position_nft;
 pub mod positions_decrease_manager;
 pub mod positions_decrease_router;
 pub mod positions_increase_manager;
@@ -28,13 +31,16 @@ pub mod positions_increase_router;
 pub mod positions_liquidation_manager;
 pub mod positions_manager;
 pub mod positions_manager_utils;
+pub mod reader;
 pub mod reward_distributor;
 pub mod reward_router;
 pub mod reward_tracker;
 pub mod reward_tracker_staking;
 pub mod shorts_tracker;
+pub mod staked_olp;
 pub mod swap_manager;
 pub mod swap_router;
+pub mod usdo_router;
 pub mod vault;
 pub mod vault_price_feed;
 pub mod vault_utils;
@@ -56,6 +62,8 @@ pub struct StakingAddresses {
     pub reward_router: Address,
     pub olp_manager: Address,
     pub olp_manager_utils: Address,
+    pub olp_vault: Address,
+    pub staked_olp: Address,
 
     pub staked_omx_tracker: Address,
     pub staked_omx_tracker_staking: Address,
@@ -112,6 +120,14 @@ pub struct RouterAddresses {
     pub positions_decrease: Address,
     pub positions_increase: Address,
     pub swap: Address,
+    pub usdo: Address,
+}
+
+#[derive(Clone, Debug, Copy, Serialize)]
+pub struct PeripheryAddresses {
+    pub position_nft: Address,
+    pub reader: Address,
+    pub olp_oracle: Address,
 }
 
 #[derive(Clone, Debug, Copy, Serialize)]
@@ -123,9 +139,10 @@ pub struct ContractAddresses {
     pub vault: VaultAddresses,
     pub tokens: TokensAddresses,
     pub orderbook: OrderbookAddresses,
+    pub periphery: PeripheryAddresses,
 }
 
-fn get_contract_path(contract: impl Display) -> String {
+pub fn get_contract_path(contract: impl Display) -> String {
     format!("../../{}/omx_{}.wasm", ARTIFACTS_DIR, contract)
 }
 
@@ -162,6 +179,8 @@ impl ContractAddresses {
                     None,
                 ),
                 olp_manager: deploy("olp_manager", olp_manager::OLPMANAGER_ABI.clone(), None),
+                olp_vault: deploy("olp_vault", olp_vault::OLPVAULT_ABI.clone(), None),
+                staked_olp: deploy("staked_olp", staked_olp::STAKEDOLP_ABI.clone(), None),
                 olp_manager_utils: deploy(
                     "olp_manager_utils",
                     olp_manager_utils::OLPMANAGERUTILS_ABI.clone(),
@@ -283,6 +302,7 @@ impl ContractAddresses {
 
             router: RouterAddresses {
                 swap: deploy("swap_router", swap_router::SWAPROUTER_ABI.clone(), None),
+                usdo: deploy("usdo_router", usdo_router::USDOROUTER_ABI.clone(), None),
                 positions_decrease: deploy(
                     "positions_decrease_router",
                     positions_decrease_router::POSITIONSDECREASEROUTER_ABI.clone(),
@@ -345,6 +365,12 @@ impl ContractAddresses {
                     None,
                 ),
             },
+
+            periphery: PeripheryAddresses {
+                position_nft: deploy("position_nft", position_nft::POSITIONNFT_ABI.clone(), None),
+                reader: deploy("reader", reader::READER_ABI.clone(), None),
+                olp_oracle: deploy("olp_oracle", olp_oracle::OLPORACLE_ABI.clone(), None),
+            },
         }
     }
 }
@@ -7,15 +7,29 @@ use crate::stylus_testing::provider::TestClient;
 abigen!(
     OlpManager,
     r#"[
+        error InvalidCooldownDuration(uint256 max_duration)
+        error CooldownNotPassed()
+        error InsufficientOutput(uint256 amount_out)
+        error ArrayLengthMismatch()
+        error TokenListMismatch()
         function init(address gov, address olp_manager_utils, address vault, address swap_manager, address positions_manager, address shorts_tracker, address usdo, address olp) external
         function isHandler(address account) external view returns (bool)
         function setGov(address gov) external
         function setInPrivateMode(bool in_private_mode) external
         function setHandler(address handler, bool is_active) external
+        function setCooldownDuration(uint256 cooldown_duration) external
+        function cooldownDuration() external view returns (uint256)
+        function lastAddedAt(address account) external view returns (uint256)
+        function setCooldownExempt(address account, bool is_exempt) external
+        function isCooldownExempt(address account) external view returns (bool)
         function addLiquidity(address token, uint256 amount, uint256 min_usdo, uint256 min_olp) external returns (uint256)
         function addLiquidityForAccount(address funding_account, address account, address token, uint256 amount, uint256 min_usdo, uint256 min_olp) external returns (uint256)
         function removeLiquidity(address token_out, uint256 olp_amount, uint256 min_amount, address recipient) external returns (uint256)
         function removeLiquidityForAccount(address account, address token_out, uint256 olp_amount, uint256 min_amount, address recipient) external returns (uint256)
+        function addLiquidityBasket(address[] tokens, uint256[] amounts, uint256 min_usdo, uint256 min_olp) external returns (uint256)
+        function addLiquidityBasketForAccount(address funding_account, address account, address[] tokens, uint256[] amounts, uint256 min_usdo, uint256 min_olp) external returns (uint256)
+        function removeLiquidityBasket(uint256 olp_amount, address[] tokens, uint256[] min_amounts, address recipient) external returns (uint256[])
+        function removeLiquidityBasketForAccount(address account, uint256 olp_amount, address[] tokens, uint256[] min_amounts, address recipient) external returns (uint256[])
     ]"#
 );
 
@@ -7,11 +7,18 @@ use crate::stylus_testing::provider::TestClient;
 abigen!(
     OlpManagerUtils,
     r#"[
-        function init(address vault, address positions_manager, address shorts_tracker, address olp) external
+        error InvalidAumAdjustment(uint256 max_adjustment)
+        error InvalidWeight(uint256 max_weight)
+        function init(address gov, address vault, address positions_manager, address shorts_tracker, address olp, address usdo, address olp_manager) external
         function setShortsTrackerAveragePriceWeight(uint256 weight) external
-        function getPrice() external view returns (uint256)
-        function getAumInUsdo() external view returns (uint256)
-        function getAum() external view returns (uint256)
+        function setAumAdjustment(uint256 aum_addition, uint256 aum_deduction) external
+        function aumAddition() external view returns (uint256)
+        function aumDeduction() external view returns (uint256)
+        function getPrice(bool maximise) external view returns (uint256)
+        function getAums() external view returns (uint256, uint256)
+        function getAumInUsdo(bool maximise) external view returns (uint256)
+        function getUsdoLiability() external view returns (uint256)
+        function getAum(bool maximise) external view returns (uint256)
         function getGlobalShortDelta(address token, uint256 price, uint256 size) external view returns (uint256, bool)
         function getGlobalShortAveragePrice(address token) external view returns (uint256)
     ]"#
@@ -23,6 +30,8 @@ pub struct OlpManagerUtilsInitArgs {
     pub positions_manager: Address,
     pub shorts_tracker: Address,
     pub olp: Address,
+    pub usdo: Address,
+    pub olp_manager: Address,
 }
 
 impl OlpManagerUtilsInitArgs {
@@ -31,10 +40,13 @@ impl OlpManagerUtilsInitArgs {
 
         contract
             .init(
+                gov.address(),
                 self.vault,
                 self.positions_manager,
                 self.shorts_tracker,
                 self.olp,
+                self.usdo,
+                self.olp_manager,
             )
             .await
             .unwrap();
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use ethers::{prelude::abigen, types::Address};
+
+use crate::stylus_testing::provider::TestClient;
+
+abigen!(
+    OlpOracle,
+    r#"[
+        error AlreadyInitialized()
+        error NotInitialized()
+        error CheckpointTooEarly(uint256 next_checkpoint_at)
+        error SnapshotNotFound()
+        error InvalidPeriod()
+        error StaleSnapshot(uint256 latest_timestamp)
+        function init(address olp_manager_utils) external
+        function getPrice(bool maximise) external view returns (uint256)
+        function checkpoint() external returns (uint256)
+        function snapshotsCount() external view returns (uint256)
+        function getSnapshot(uint256 index) external view returns (uint256, uint256, uint256)
+        function priceAt(uint256 timestamp, bool maximise) external view returns (uint256)
+        function getTwap(uint256 period, bool maximise) external view returns (uint256)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct OlpOracleInitArgs {
+    pub olp_manager_utils: Address,
+}
+
+impl OlpOracleInitArgs {
+    pub async fn init(self, gov: Arc<TestClient>, addr: Address) -> OlpOracle<TestClient> {
+        let contract = OlpOracle::new(addr, gov.clone());
+
+        contract.init(self.olp_manager_utils).await.unwrap();
+
+        contract
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use ethers::{prelude::abigen, types::Address};
+
+use crate::stylus_testing::provider::TestClient;
+
+abigen!(
+    OlpVault,
+    r#"[
+        error Forbidden()
+        error ZeroAmount()
+        error InsufficientBalance()
+        error InsufficientAllowance()
+        error CooldownNotPassed()
+        function init(address gov, address olp, address weth, address vault, address olp_manager, address olp_manager_utils, address reward_router, address staked_olp_tracker_staking) external
+        function setGov(address gov) external
+        function totalSupply() external view returns (uint256)
+        function balanceOf(address account) external view returns (uint256)
+        function approve(address spender, uint256 amount) external returns (bool)
+        function transfer(address recipient, uint256 amount) external returns (bool)
+        function asset() external view returns (address)
+        function totalAssets() external view returns (uint256)
+        function convertToShares(uint256 assets) external view returns (uint256)
+        function convertToAssets(uint256 shares) external view returns (uint256)
+        function maxWithdraw(address owner) external view returns (uint256)
+        function previewDeposit(uint256 assets) external view returns (uint256)
+        function previewRedeem(uint256 shares) external view returns (uint256)
+        function deposit(uint256 assets, address receiver) external returns (uint256)
+        function mint(uint256 shares, address receiver) external returns (uint256)
+        function withdraw(uint256 assets, address receiver, address owner) external returns (uint256)
+        function redeem(uint256 shares, address receiver, address owner) external returns (uint256)
+        function harvest() external returns (uint256)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct OlpVaultInitArgs {
+    pub olp: Address,
+    pub weth: Address,
+    pub vault: Address,
+    pub olp_manager: Address,
+    pub olp_manager_utils: Address,
+    pub reward_router: Address,
+    pub staked_olp_tracker_staking: Address,
+}
+
+impl OlpVaultInitArgs {
+    pub async fn init(self, gov: Arc<TestClient>, addr: Address) -> OlpVault<TestClient> {
+        let contract = OlpVault::new(addr, gov.clone());
+
+        contract
+            .init(
+                gov.address(),
+                self.olp,
+                self.weth,
+                self.vault,
+                self.olp_manager,
+                self.olp_manager_utils,
+                self.reward_router,
+                self.staked_olp_tracker_staking,
+            )
+            .await
+            .unwrap();
+
+        contract
+    }
+}
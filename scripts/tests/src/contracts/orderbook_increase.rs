@@ -7,7 +7,11 @@ use crate::stylus_testing::provider::TestClient;
 abigen!(
     OrderbookIncrease,
     r#"[
-        function init(address gov, address increase_router) external
+        error Forbidden()
+        error MaxOpenInterestExceeded(uint256 available)
+        error PositionsManagerNotSet()
+        function init(address gov, address increase_router, address positions_manager) external
+        function setPositionsManager(address positions_manager) external
         function getCurrentIndex(address account) external view returns (uint256)
         function createIncreaseOrder(uint256 collateral_amount, address collateral_token, address index_token, uint256 size_delta, bool is_long, uint256 trigger_price, bool trigger_above_threshold, uint256 execution_fee) external payable
         function cancelIncreaseOrder(uint256 order_index) external
@@ -19,13 +23,17 @@ abigen!(
 pub struct OrderbookIncreaseInitArgs {
     pub gov: Address,
     pub swap_router: Address,
+    pub positions_manager: Address,
 }
 
 impl OrderbookIncreaseInitArgs {
     pub async fn init(self, gov: Arc<TestClient>, addr: Address) -> OrderbookIncrease<TestClient> {
         let contract = OrderbookIncrease::new(addr, gov.clone());
 
-        contract.init(self.gov, self.swap_router).await.unwrap();
+        contract
+            .init(self.gov, self.swap_router, self.positions_manager)
+            .await
+            .unwrap();
 
         contract
     }
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use ethers::{prelude::abigen, types::Address};
+
+use crate::stylus_testing::provider::TestClient;
+
+abigen!(
+    PositionNft,
+    r#"[
+        error AlreadyInitialized()
+        error NotInitialized()
+        error Forbidden()
+        error TokenNotFound()
+        error TransferToZeroAddress()
+        error NotTokenOwner()
+        error PositionNotFound()
+        error PositionAlreadyWrapped()
+        error OperatorNotApproved()
+        error InvalidReceiver()
+        function init(address gov, address positions_manager, address vault_utils) external
+        function setGov(address gov) external
+        function setBaseUri(string calldata base_uri) external
+        function name() external view returns (string memory)
+        function symbol() external view returns (string memory)
+        function tokenURI(uint256 token_id) external view returns (string memory)
+        function totalSupply() external view returns (uint256)
+        function balanceOf(address owner) external view returns (uint256)
+        function ownerOf(uint256 token_id) external view returns (address)
+        function getApproved(uint256 token_id) external view returns (address)
+        function isApprovedForAll(address owner, address operator) external view returns (bool)
+        function approve(address approved, uint256 token_id) external
+        function setApprovalForAll(address operator, bool approved) external
+        function transferFrom(address from, address to, uint256 token_id) external
+        function safeTransferFrom(address from, address to, uint256 token_id, bytes calldata data) external
+        function safeTransferFrom(address from, address to, uint256 token_id) external
+        function supportsInterface(bytes4 interface_id) external view returns (bool)
+        function wrap(address collateral_token, address index_token, bool is_long) external returns (uint256)
+        function unwrap(uint256 token_id) external
+        function tokenOfPosition(bytes32 key) external view returns (uint256)
+        function tokenPosition(uint256 token_id) external view returns (bytes32, address, address, bool)
+        function positionSize(uint256 token_id) external view returns (uint256)
+        function positionCollateral(uint256 token_id) external view returns (uint256)
+        function positionEntryPrice(uint256 token_id) external view returns (uint256)
+        function positionPnl(uint256 token_id) external view returns (bool, uint256)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct PositionNftInitArgs {
+    pub gov: Address,
+    pub positions_manager: Address,
+    pub vault_utils: Address,
+}
+
+impl PositionNftInitArgs {
+    pub async fn init(self, gov: Arc<TestClient>, addr: Address) -> PositionNft<TestClient> {
+        let contract = PositionNft::new(addr, gov.clone());
+
+        contract
+            .init(self.gov, self.positions_manager, self.vault_utils)
+            .await
+            .unwrap();
+
+        contract
+    }
+}
@@ -10,6 +10,8 @@ abigen!(
         function init(address gov, address vault, address funding_rate_manager, address decrease_router, address positions_manager, address positions_liquidation_manager, address positions_manager_utils) external
         function setGov(address gov) external
         function decreasePosition(address account, address collateral_token, address index_token, uint256 collateral_delta, uint256 size_delta, bool is_long, address receiver) external returns (uint256)
+        function withdrawCollateral(address account, address collateral_token, address index_token, uint256 collateral_delta, bool is_long, address receiver) external returns (uint256)
+        function withdrawMargin(address account, address collateral_token, uint256 amount, address receiver) external returns (uint256)
     ]"#
 );
 
@@ -10,6 +10,9 @@ abigen!(
         function init(address weth, address vault, address positions_decrease_manager, address swap_router) external
         function decreasePosition(address collateral_token, address index_token, uint256 collateral_delta, uint256 size_delta, bool is_long, address receiver, uint256 price) external returns (uint256)
         function decreasePositionEth(address collateral_token, address index_token, uint256 collateral_delta, uint256 size_delta, bool is_long, address receiver, uint256 price) external returns (uint256)
+        function withdrawCollateral(address collateral_token, address index_token, uint256 collateral_delta, bool is_long, address receiver) external returns (uint256)
+        function withdrawCollateralEth(address collateral_token, address index_token, uint256 collateral_delta, bool is_long, address receiver) external returns (uint256)
+        function withdrawMargin(address collateral_token, uint256 amount, address receiver) external returns (uint256)
     ]"#
 );
 
@@ -10,6 +10,8 @@ abigen!(
         function init(address weth, address vault, address positions_increase_manager, address swap_router) external
         function increasePosition(address[] memory path, address index_token, uint256 amount_in, uint256 min_out, uint256 size_delta, bool is_long, uint256 price) external
         function increasePositionEth(address[] memory path, address index_token, uint256 min_out, uint256 size_delta, bool is_long, uint256 price) external payable
+        function depositCollateral(address[] memory path, address index_token, uint256 amount_in, uint256 min_out, bool is_long) external
+        function depositMargin(address collateral_token, uint256 amount_in) external
     ]"#
 );
 
@@ -16,7 +16,54 @@ abigen!(
         function position(address account, address collateral_token, address index_token, bool is_long) external view returns (uint256, uint256, uint256, uint256, uint256, int256, uint256)
         function positionUpdate(address account, address collateral_token, address index_token, bool is_long, uint256 size, uint256 collateral, uint256 average_price, uint256 entry_funding_rate, uint256 reserve_amount, int256 realised_pnl, uint256 last_increased_time) external
         function getNextGlobalShortAveragePrice(address index_token, uint256 next_price, uint256 size_delta) external view returns (uint256)
+        function setMaxGlobalSizes(address index_token, uint256 max_long_size, uint256 max_short_size) external
+        function maxGlobalLongSize(address token) external view returns (uint256)
+        function maxGlobalShortSize(address token) external view returns (uint256)
+        function availableOpenInterest(address index_token, bool is_long) external view returns (uint256)
+        function setMinPosition(address index_token, uint256 min_size, uint256 min_collateral) external
+        function minPosition(address index_token) external view returns (uint256, uint256)
+        function setPriceImpactDepth(address index_token, uint256 depth) external
+        function priceImpactDepth(address index_token) external view returns (uint256)
+        function priceImpactPool(address index_token, address collateral_token) external view returns (uint256)
+        function getPriceImpact(address index_token, bool is_long, uint256 size_delta, bool is_increase) external view returns (int256)
+        function applyPriceImpact(address index_token, address collateral_token, bool is_long, uint256 size_delta, bool is_increase, uint256 max_charge, uint256 collateral_price, uint8 collateral_decimals) external returns (int256)
         function globalShortSize(address token) external view returns (uint256)
+        function globalShortAveragePrice(address token) external view returns (uint256)
+        function afterLongIncrease(address index_token, uint256 price, uint256 size_delta) external
+        function decreaseGlobalLongSize(address token, uint256 amount) external
+        function getNextGlobalLongAveragePrice(address index_token, uint256 next_price, uint256 size_delta) external view returns (uint256)
+        function globalLongSize(address token) external view returns (uint256)
+        function globalLongAveragePrice(address token) external view returns (uint256)
+        function globalPosition(address index_token, bool is_long) external view returns (uint256, uint256)
+        function setGlobalLongData(address[] memory tokens, uint256[] memory sizes, uint256[] memory average_prices) external
+        function isGlobalLongDataReady() external view returns (bool)
+        function getPositionKey(address account, address collateral_token, address index_token, bool is_long) external view returns (bytes32)
+        function positionByKey(bytes32 key) external view returns (uint256, uint256, uint256, uint256, uint256, int256, uint256)
+        function migratePositions(address[] memory accounts, address[] memory collateral_tokens, address[] memory index_tokens, bool[] memory is_long) external
+        function registerPositions(address[] memory accounts, address[] memory collateral_tokens, address[] memory index_tokens, bool[] memory is_long) external
+        function isPositionMigrated(bytes32 key) external view returns (bool)
+        function positionInfo(bytes32 key) external view returns (address, address, address, bool)
+        function accountPositionsLength(address account) external view returns (uint256)
+        function accountPositionKey(address account, uint64 index) external view returns (bytes32)
+        function accountPositions(address account) external view returns (address[] memory, address[] memory, bool[] memory)
+        function openPositionsLength() external view returns (uint256)
+        function getOpenPositions(uint256 offset, uint256 limit) external view returns (bytes32[] memory, address[] memory, uint8[] memory)
+        function pendingPositionTransfer(bytes32 key) external view returns (address)
+        function transferPosition(bytes32 key, address new_owner) external
+        function acceptPosition(bytes32 key) external
+        function transferPositionFrom(address owner, bytes32 key, address new_owner) external returns (bytes32)
+        function setPositionTransferAgent(address agent, bool is_active) external
+        function lockPosition(bytes32 key) external
+        function unlockPosition(bytes32 key) external
+        function positionLock(bytes32 key) external view returns (address)
+        function isPositionTransferAgent(address agent) external view returns (bool)
+        function setPositionOperator(address operator, bool approved) external
+        function isPositionOperator(address owner, address operator) external view returns (bool)
+        function approvePosition(bytes32 key, address operator) external
+        function positionApproval(bytes32 key) external view returns (address)
+        function setCrossMarginEnabled(bool enabled) external
+        function crossMarginEnabled(address account) external view returns (bool)
+        function crossMarginBalance(address account, address collateral_token) external view returns (uint256)
     ]"#
 );
 
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use ethers::{prelude::abigen, types::Address};
+
+use crate::stylus_testing::provider::TestClient;
+
+abigen!(
+    Reader,
+    r#"[
+        function init(address vault, address positions_manager, address vault_utils, address swap_manager, address funding_rate_manager, address fee_manager, address price_feed) external
+        function getPositions(address account, address[] memory collateral_tokens, address[] memory index_tokens, bool[] memory is_long) external view returns (uint256[] memory)
+        function getVaultTokenInfo(address[] memory tokens) external view returns (uint256[] memory)
+        function getTokenBalancesWithSupplies(address account, address[] memory tokens) external view returns (uint256[] memory)
+        function getFees(address[] memory tokens) external view returns (uint256[] memory)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct ReaderInitArgs {
+    pub vault: Address,
+    pub positions_manager: Address,
+    pub vault_utils: Address,
+    pub swap_manager: Address,
+    pub funding_rate_manager: Address,
+    pub fee_manager: Address,
+    pub price_feed: Address,
+}
+
+impl ReaderInitArgs {
+    pub async fn init(self, gov: Arc<TestClient>, addr: Address) -> Reader<TestClient> {
+        let contract = Reader::new(addr, gov.clone());
+
+        contract
+            .init(
+                self.vault,
+                self.positions_manager,
+                self.vault_utils,
+                self.swap_manager,
+                self.funding_rate_manager,
+                self.fee_manager,
+                self.price_feed,
+            )
+            .await
+            .unwrap();
+
+        contract
+    }
+}
@@ -17,16 +17,21 @@ abigen!(
         error InvalidAmount()
         error InvalidValue()
         error InvalidOlpAmount()
-        function init(address gov, address omx, address es_omx, address bn_omx, address olp, address staked_omx_tracker, address bonus_omx_tracker, address staked_omx_tracker_staking, address bonus_omx_tracker_staking, address fee_omx_tracker_staking, address fee_olp_tracker_staking, address staked_olp_tracker_staking, address olp_manager) external
+        function init(address gov, address omx, address es_omx, address bn_omx, address olp, address staked_omx_tracker, address bonus_omx_tracker, address fee_olp_tracker, address staked_omx_tracker_staking, address bonus_omx_tracker_staking, address fee_omx_tracker_staking, address fee_olp_tracker_staking, address staked_olp_tracker_staking, address olp_manager) external
         function setGov(address gov) external
+        function setOlpVault(address olp_vault) external
         function batchStakeOmxForAccount(address[] memory accounts, uint256[] memory amounts) external
         function stakeOmxForAccount(address account, uint256 amount) external
         function stakeOmx(uint256 amount) external
         function stakeEsOmx(uint256 amount) external
         function unstakeOmx(uint256 amount) external
         function unstakeEsOmx(uint256 amount) external
+        function stakeOlp(uint256 amount) external
+        function unstakeOlp(uint256 amount) external
         function mintAndStakeOlp(address token, uint256 amount, uint256 min_usdo, uint256 min_olp) external returns (uint256)
         function unstakeAndRedeemOlp(address token_out, uint256 olp_amount, uint256 min_out, address receiver) external returns (uint256)
+        function mintAndStakeOlpBasket(address[] tokens, uint256[] amounts, uint256 min_usdo, uint256 min_olp) external returns (uint256)
+        function unstakeAndRedeemOlpBasket(uint256 olp_amount, address[] tokens, uint256[] min_amounts, address receiver) external returns (uint256[])
         function claim() external
         function claimEsOmx() external
         function claimFees() external
@@ -44,6 +49,7 @@ pub struct RewardRouterInitArgs {
     pub olp: Address,
     pub staked_omx_tracker: Address,
     pub bonus_omx_tracker: Address,
+    pub fee_olp_tracker: Address,
     pub staked_omx_tracker_staking: Address,
     pub bonus_omx_tracker_staking: Address,
     pub fee_omx_tracker_staking: Address,
@@ -65,6 +71,7 @@ impl RewardRouterInitArgs {
                 self.olp,
                 self.staked_omx_tracker,
                 self.bonus_omx_tracker,
+                self.fee_olp_tracker,
                 self.staked_omx_tracker_staking,
                 self.bonus_omx_tracker_staking,
                 self.fee_omx_tracker_staking,
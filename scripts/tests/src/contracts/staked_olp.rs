@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use ethers::{prelude::abigen, types::Address};
+
+use crate::stylus_testing::provider::TestClient;
+
+abigen!(
+    StakedOlp,
+    r#"[
+        error TransferToZeroAddress()
+        error ApproveToZeroAddress()
+        error InsufficientAllowance()
+        error CooldownNotPassed()
+        function init(address olp, address olp_manager, address fee_olp_tracker, address fee_olp_tracker_staking, address staked_olp_tracker, address staked_olp_tracker_staking) external
+        function totalSupply() external view returns (uint256)
+        function balanceOf(address account) external view returns (uint256)
+        function allowance(address owner, address spender) external view returns (uint256)
+        function approve(address spender, uint256 amount) external returns (bool)
+        function transfer(address recipient, uint256 amount) external returns (bool)
+        function transferFrom(address sender, address recipient, uint256 amount) external returns (bool)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct StakedOlpInitArgs {
+    pub olp: Address,
+    pub olp_manager: Address,
+    pub fee_olp_tracker: Address,
+    pub fee_olp_tracker_staking: Address,
+    pub staked_olp_tracker: Address,
+    pub staked_olp_tracker_staking: Address,
+}
+
+impl StakedOlpInitArgs {
+    pub async fn init(self, gov: Arc<TestClient>, addr: Address) -> StakedOlp<TestClient> {
+        let contract = StakedOlp::new(addr, gov.clone());
+
+        contract
+            .init(
+                self.olp,
+                self.olp_manager,
+                self.fee_olp_tracker,
+                self.fee_olp_tracker_staking,
+                self.staked_olp_tracker,
+                self.staked_olp_tracker_staking,
+            )
+            .await
+            .unwrap();
+
+        contract
+    }
+}
@@ -7,12 +7,18 @@ use crate::stylus_testing::provider::TestClient;
 abigen!(
     SwapRouter,
     r#"[
-        function init(address weth, address usdo, address vault, address swap_manager, address positions_router) external
+        function init(address gov, address weth, address usdo, address vault, address swap_manager, address positions_router) external
+        function setGov(address gov) external
+        function setMaxPathLength(uint256 max_path_length) external
+        function maxPathLength() external view returns (uint256)
         function directPoolDeposit(address token, uint256 amount) external
         function swapForPosition(address[] memory path, uint256 min_out, address receiver) external returns (uint256)
         function swap(address[] memory path, uint256 amount_in, uint256 min_out, address receiver) external
+        function swapExactOut(address[] memory path, uint256 amount_out, uint256 max_in, address receiver) external returns (uint256)
         function swapEthToTokens(address[] memory path, uint256 min_out, address receiver) external payable
         function swapToEth(address token_in, uint256 amount_in, uint256 min_out, address receiver) external
+        function getAmountOut(address[] memory path, uint256 amount_in) external view returns (uint256)
+        function getAmountIn(address[] memory path, uint256 amount_out) external view returns (uint256)
     ]"#
 );
 
@@ -31,6 +37,7 @@ impl SwapRouterInitArgs {
 
         contract
             .init(
+                gov.address(),
                 self.weth,
                 self.usdo,
                 self.vault,
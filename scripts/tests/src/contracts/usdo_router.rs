@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use ethers::{prelude::abigen, types::Address};
+
+use crate::stylus_testing::provider::TestClient;
+
+abigen!(
+    UsdoRouter,
+    r#"[
+        error AlreadyInitialized()
+        error NotInitialized()
+        error NotStableToken()
+        error InsufficientAmountOut()
+        function init(address usdo, address vault, address swap_manager) external
+        function mintUsdo(address token, uint256 amount_in, uint256 min_out, address receiver) external returns (uint256)
+        function redeemUsdo(address token, uint256 usdo_amount, uint256 min_out, address receiver) external returns (uint256)
+        function getMintAmount(address token, uint256 amount_in) external view returns (uint256)
+        function getRedeemAmount(address token, uint256 usdo_amount) external view returns (uint256)
+    ]"#
+);
+
+#[derive(Clone, Debug)]
+pub struct UsdoRouterInitArgs {
+    pub usdo: Address,
+    pub vault: Address,
+    pub swap_manager: Address,
+}
+
+impl UsdoRouterInitArgs {
+    pub async fn init(self, gov: Arc<TestClient>, addr: Address) -> UsdoRouter<TestClient> {
+        let contract = UsdoRouter::new(addr, gov.clone());
+
+        contract
+            .init(self.usdo, self.vault, self.swap_manager)
+            .await
+            .unwrap();
+
+        contract
+    }
+}
@@ -25,6 +25,9 @@ abigen!(
         function increasePoolAmount(address token, uint256 amount) external
         function updateTokenBalance(address token) external
         function getPrice(address token) external view returns (uint256)
+        function getMaxPrice(address token) external view returns (uint256)
+        function getMinPrice(address token) external view returns (uint256)
+        function getTokenInfo(address token) external view returns (uint256, uint8, uint256, uint256)
         function setTokenConfig(address token, uint8 token_decimals, uint256 token_weight, uint256 min_profit_basis_points, bool is_stable, bool is_shortable) external
         function clearTokenConfig(address token) external
         function directPoolDeposit(address token) external
@@ -9,6 +9,7 @@ use crate::{
 
 use self::{
     orderbook::{OrderbookContracts, OrderbookInitArgs},
+    periphery::{PeripheryContracts, PeripheryContractsInitArgs},
     price_feed::init_vault_price_feed,
     router::{RouterContracts, RouterContractsInitArgs},
     staking::{StakingContracts, StakingContractsInitArgs},
@@ -17,6 +18,7 @@ use self::{
 };
 
 pub mod orderbook;
+pub mod periphery;
 pub mod price_feed;
 pub mod router;
 pub mod staking;
@@ -32,6 +34,7 @@ pub struct Contracts {
     pub vault: VaultContracts,
     pub tokens: TokensContracts,
     pub staking: StakingContracts,
+    pub periphery: PeripheryContracts,
 }
 
 impl Contracts {
@@ -108,6 +111,9 @@ impl ContractsInitArgs {
             staking: StakingContractsInitArgs {}
                 .init(client.clone(), contracts)
                 .await,
+            periphery: PeripheryContractsInitArgs { gov: self.gov }
+                .init(client.clone(), contracts)
+                .await,
         };
 
         log::debug!("Update last distribution time...");
@@ -315,6 +321,48 @@ impl ContractsInitArgs {
             .set_handler(contracts.staking.fee_olp_tracker.address(), true)
             .await
             .unwrap();
+        // the staking contract of fee_olp_tracker pulls and returns the olp
+        contracts
+            .tokens
+            .olp
+            .set_handler(contracts.staking.fee_olp_tracker_staking.address(), true)
+            .await
+            .unwrap();
+        // allow olp_vault to stake the olp it holds and to move olp of its depositors
+        contracts
+            .staking
+            .reward_router
+            .set_olp_vault(contracts.staking.olp_vault.address())
+            .await
+            .unwrap();
+        contracts
+            .tokens
+            .olp
+            .set_handler(contracts.staking.olp_vault.address(), true)
+            .await
+            .unwrap();
+        // allow staked_olp to move staked olp between accounts in both trackers
+        contracts
+            .staking
+            .fee_olp_tracker
+            .set_handler(contracts.staking.staked_olp.address(), true)
+            .await
+            .unwrap();
+        contracts
+            .staking
+            .staked_olp_tracker
+            .set_handler(contracts.staking.staked_olp.address(), true)
+            .await
+            .unwrap();
+
+        log::debug!("Configure periphery...");
+        // allow position_nft to lock and move the wrapped positions
+        contracts
+            .vault
+            .positions_manager
+            .set_position_transfer_agent(contracts.periphery.position_nft.address(), true)
+            .await
+            .unwrap();
 
         contracts
     }
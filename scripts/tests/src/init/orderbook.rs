@@ -41,6 +41,7 @@ impl OrderbookInitArgs {
             increase: OrderbookIncreaseInitArgs {
                 gov: self.gov,
                 swap_router: self.swap_router,
+                positions_manager: contracts.vault.positions_manager,
             }
             .init(client.clone(), contracts.orderbook.increase)
             .await,
@@ -5,6 +5,7 @@ use crate::{
         positions_decrease_router::{PositionsDecreaseRouter, PositionsDecreaseRouterInitArgs},
         positions_increase_router::{PositionsIncreaseRouter, PositionsIncreaseRouterInitArgs},
         swap_router::{SwapRouter, SwapRouterInitArgs},
+        usdo_router::{UsdoRouter, UsdoRouterInitArgs},
         ContractAddresses,
     },
     stylus_testing::provider::TestClient,
@@ -20,6 +21,7 @@ pub struct RouterContracts {
     pub positions_decrease: PositionsDecreaseRouter<TestClient>,
     pub positions_increase: PositionsIncreaseRouter<TestClient>,
     pub swap: SwapRouter<TestClient>,
+    pub usdo: UsdoRouter<TestClient>,
 }
 
 impl RouterContractsInitArgs {
@@ -31,7 +33,7 @@ impl RouterContractsInitArgs {
         RouterContracts {
             positions_decrease: PositionsDecreaseRouterInitArgs {
                 positions_decrease_manager: contracts.vault.positions_decrease_manager,
-                swap_router: contracts.vault.swap_manager,
+                swap_router: contracts.router.swap,
                 vault: contracts.vault.vault,
                 weth: contracts.tokens.weth,
             }
@@ -39,7 +41,7 @@ impl RouterContractsInitArgs {
             .await,
             positions_increase: PositionsIncreaseRouterInitArgs {
                 positions_increase_manager: contracts.vault.positions_increase_manager,
-                swap_router: contracts.vault.swap_manager,
+                swap_router: contracts.router.swap,
                 vault: contracts.vault.vault,
                 weth: contracts.tokens.weth,
             }
@@ -54,6 +56,13 @@ impl RouterContractsInitArgs {
             }
             .init(client.clone(), contracts.router.swap)
             .await,
+            usdo: UsdoRouterInitArgs {
+                usdo: contracts.tokens.usdo,
+                vault: contracts.vault.vault,
+                swap_manager: contracts.vault.swap_manager,
+            }
+            .init(client.clone(), contracts.router.usdo)
+            .await,
         }
     }
 }
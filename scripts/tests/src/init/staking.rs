@@ -6,12 +6,14 @@ use crate::{
     contracts::{
         olp_manager::{OlpManager, OlpManagerInitArgs},
         olp_manager_utils::{OlpManagerUtils, OlpManagerUtilsInitArgs},
+        olp_vault::{OlpVault, OlpVaultInitArgs},
         bonus_distributor::{BonusDistributor, BonusDistributorInitArgs},
         reward_distributor::{RewardDistributor, RewardDistributorInitArgs},
         reward_router::{RewardRouter, RewardRouterInitArgs},
         reward_tracker::{RewardTracker, RewardTrackerInitArgs},
         reward_tracker_staking::{RewardTrackerStaking, RewardTrackerStakingInitArgs},
         shorts_tracker::{ShortsTracker, ShortsTrackerInitArgs},
+        staked_olp::{StakedOlp, StakedOlpInitArgs},
         ContractAddresses,
     },
     stylus_testing::provider::TestClient,
@@ -27,6 +29,8 @@ pub struct StakingContracts {
     pub reward_router: RewardRouter<TestClient>,
     pub olp_manager: OlpManager<TestClient>,
     pub olp_manager_utils: OlpManagerUtils<TestClient>,
+    pub olp_vault: OlpVault<TestClient>,
+    pub staked_olp: StakedOlp<TestClient>,
     pub shorts_tracker: ShortsTracker<TestClient>,
 
     pub staked_omx_tracker: RewardTracker<TestClient>,
@@ -136,6 +140,7 @@ impl StakingContractsInitArgs {
                 bn_omx: contracts.tokens.bn_omx,
                 olp_manager: contracts.staking.olp_manager,
                 bonus_omx_tracker: contracts.staking.bonus_omx_tracker,
+                fee_olp_tracker: contracts.staking.fee_olp_tracker,
                 staked_omx_tracker: contracts.staking.staked_omx_tracker,
                 fee_olp_tracker_staking: contracts.staking.fee_olp_tracker_staking,
                 bonus_omx_tracker_staking: contracts.staking.bonus_omx_tracker_staking,
@@ -161,9 +166,32 @@ impl StakingContractsInitArgs {
                 positions_manager: contracts.vault.positions_manager,
                 shorts_tracker: contracts.staking.shorts_tracker,
                 vault: contracts.vault.vault,
+                usdo: contracts.tokens.usdo,
+                olp_manager: contracts.staking.olp_manager,
             }
             .init(client.clone(), contracts.staking.olp_manager_utils)
             .await,
+            olp_vault: OlpVaultInitArgs {
+                olp: contracts.tokens.olp,
+                weth: contracts.tokens.weth,
+                vault: contracts.vault.vault,
+                olp_manager: contracts.staking.olp_manager,
+                olp_manager_utils: contracts.staking.olp_manager_utils,
+                reward_router: contracts.staking.reward_router,
+                staked_olp_tracker_staking: contracts.staking.staked_olp_tracker_staking,
+            }
+            .init(client.clone(), contracts.staking.olp_vault)
+            .await,
+            staked_olp: StakedOlpInitArgs {
+                olp: contracts.tokens.olp,
+                olp_manager: contracts.staking.olp_manager,
+                fee_olp_tracker: contracts.staking.fee_olp_tracker,
+                fee_olp_tracker_staking: contracts.staking.fee_olp_tracker_staking,
+                staked_olp_tracker: contracts.staking.staked_olp_tracker,
+                staked_olp_tracker_staking: contracts.staking.staked_olp_tracker_staking,
+            }
+            .init(client.clone(), contracts.staking.staked_olp)
+            .await,
             shorts_tracker: ShortsTrackerInitArgs {
                 positions_manager: contracts.vault.positions_manager,
                 vault: contracts.vault.vault,
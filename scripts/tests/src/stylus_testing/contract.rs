@@ -59,10 +59,36 @@ impl From<ContractCallError> for HttpClientError {
     }
 }
 
+/// Host calls made by a contract, used to measure the cost of a call
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContractCounters {
+    /// Number of times the contract was called
+    pub calls: u64,
+    /// Number of calls per function selector
+    pub selector_calls: HashMap<[u8; 4], u64>,
+    /// Number of storage slots loaded
+    pub storage_loads: u64,
+    /// Number of storage slots stored
+    pub storage_stores: u64,
+}
+
+impl ContractCounters {
+    /// Number of calls to the function with the given signature,
+    /// e.g. `positionUpdate(address,address,address,bool,uint256)`
+    pub fn calls_to(&self, signature: &str) -> u64 {
+        self.selector_calls
+            .get(&ethers::utils::id(signature))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ContractState {
     /// Counter for reentrant calls
     reentrant_counter: u32,
+    /// Counters of calls and storage accesses since the last reset
+    counters: ContractCounters,
     /// Contract binary
     binary: Vec<u8>,
     /// Contract storage. Before each call this storage is copied to temporary
@@ -91,6 +117,7 @@ impl ContractState {
             abi,
             binary: binary.to_vec(),
             reentrant_counter: 0,
+            counters: ContractCounters::default(),
             storage_bytes32: HashMap::new(),
             result: Vec::new(),
             return_data: Vec::new(),
@@ -106,6 +133,14 @@ impl ContractState {
         self.reentrant_counter += 1;
     }
 
+    pub fn counters(&self) -> ContractCounters {
+        self.counters.clone()
+    }
+
+    pub fn reset_counters(&mut self) {
+        self.counters = ContractCounters::default();
+    }
+
     pub fn reset_result(&mut self) {
         self.result = Vec::new();
     }
@@ -276,6 +311,10 @@ impl ContractCall {
         let mut state = env.state.lock().unwrap();
         state.reset_result();
         state.inc_reentrant_counter();
+        state.counters.calls += 1;
+        if let Ok(selector) = <[u8; 4]>::try_from(&data[..data.len().min(4)]) {
+            *state.counters.selector_calls.entry(selector).or_default() += 1;
+        }
 
         if env.value > U256::zero() {
             env.provider.send_eth(env.sender, env.address, env.value);
@@ -416,7 +455,8 @@ impl Env {
     }
 
     pub fn storage_bytes32_get(&self, key: U256) -> U256 {
-        let state = self.state.lock().unwrap();
+        let mut state = self.state.lock().unwrap();
+        state.counters.storage_loads += 1;
 
         state
             .transactions_storages
@@ -433,6 +473,7 @@ impl Env {
 
     pub fn storage_bytes32_insert(&mut self, key: U256, value: U256) {
         let mut state = self.state.lock().unwrap();
+        state.counters.storage_stores += 1;
 
         state
             .transactions_storages
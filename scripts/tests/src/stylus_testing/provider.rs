@@ -20,7 +20,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use super::{
     constants::CHAIN_ID,
-    contract::{ContractCall, ContractState},
+    contract::{ContractCall, ContractCounters, ContractState},
     transaction::TransactionKey,
 };
 
@@ -290,6 +290,11 @@ pub trait TestProvider {
     fn advance_block_timestamp(&self, seconds: u64);
 
     fn reset_reentrant_counter(&self);
+
+    /// Returns calls and storage accesses of the contract since the last reset
+    fn counters(&self, address: Address) -> ContractCounters;
+
+    fn reset_counters(&self);
 }
 
 impl TestProvider for TestInnerProvider {
@@ -319,6 +324,20 @@ impl TestProvider for TestInnerProvider {
         });
     }
 
+    fn counters(&self, address: Address) -> ContractCounters {
+        self.contract(address)
+            .map(|c| c.lock().unwrap().counters())
+            .unwrap_or_default()
+    }
+
+    fn reset_counters(&self) {
+        self.contracts.lock().unwrap().iter().for_each(|(_, c)| {
+            let mut c = c.lock().unwrap();
+
+            c.reset_counters();
+        });
+    }
+
     fn advance_block_timestamp(&self, seconds: u64) {
         let mut block_timestamp = self.block_timestamp.lock().unwrap();
 
@@ -423,6 +442,16 @@ impl TestProvider for Arc<TestClient> {
         p.reset_reentrant_counter();
     }
 
+    fn counters(&self, address: Address) -> ContractCounters {
+        let p: TestInnerProvider = self.provider().as_ref().clone();
+        p.counters(address)
+    }
+
+    fn reset_counters(&self) {
+        let p: TestInnerProvider = self.provider().as_ref().clone();
+        p.reset_counters();
+    }
+
     fn advance_block_timestamp(&self, seconds: u64) {
         let p: TestInnerProvider = self.provider().as_ref().clone();
         p.advance_block_timestamp(seconds)
@@ -1,3 +1,7 @@
 pub mod orderbook;
 pub mod test_alp_manager;
+pub mod test_olp_oracle;
+pub mod test_position_nft;
+pub mod test_reader;
+pub mod test_usdo_router;
 pub mod vault;
@@ -5,12 +5,17 @@ use omx_tests::{
     constants::ETH_DECIMALS,
     contracts::{
         erc20::{Erc20InitArgs, ERC20_ABI},
-        orderbook_increase::{OrderbookIncreaseInitArgs, ORDERBOOKINCREASE_ABI},
+        get_contract_path,
+        orderbook_increase::{
+            Forbidden, OrderbookIncreaseInitArgs, PositionsManagerNotSet, ORDERBOOKINCREASE_ABI,
+        },
+        positions_manager::POSITIONSMANAGER_ABI,
     },
     stylus_testing::provider::TestProvider,
     utils::{
+        errors::ContractRevertExt,
         prices::expand_decimals,
-        test_helpers::{create_gov, create_user},
+        test_helpers::{create_gov, create_user, ConnectAcc},
     },
 };
 
@@ -24,9 +29,20 @@ async fn test_increase_order_two_traders() {
     let orderbook_bin = fs::read("../../artifacts/omx_orderbook_increase.jos")
         .expect("read increase_orderbook binaries");
     let orderbook = gov.deploy_contract(&orderbook_bin, ORDERBOOKINCREASE_ABI.clone(), "orderbook");
+
+    // an unconfigured positions manager has no open interest caps
+    let path = get_contract_path("positions_manager");
+    let positions_manager_bin = fs::read(&path).expect(&format!("file should exist {}", path));
+    let positions_manager = gov.deploy_contract(
+        &positions_manager_bin,
+        POSITIONSMANAGER_ABI.clone(),
+        "positions_manager",
+    );
+
     let orderbook = OrderbookIncreaseInitArgs {
         gov: gov.address(),
         swap_router: Address::default(),
+        positions_manager,
     }
     .init(gov.clone(), orderbook)
     .await;
@@ -126,3 +142,51 @@ async fn test_increase_order_two_traders() {
     //     ),
     // );
 }
+
+#[tokio::test]
+async fn test_increase_order_requires_positions_manager() {
+    let gov = create_gov();
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let path = get_contract_path("orderbook_increase");
+    let orderbook_bin = fs::read(&path).expect(&format!("file should exist {}", path));
+    let orderbook = gov.deploy_contract(&orderbook_bin, ORDERBOOKINCREASE_ABI.clone(), "orderbook");
+    let orderbook = OrderbookIncreaseInitArgs {
+        gov: gov.address(),
+        swap_router: Address::default(),
+        positions_manager: Address::default(),
+    }
+    .init(gov.clone(), orderbook)
+    .await;
+
+    let user = create_user(gov.clone(), 1, "100").await;
+
+    let execution_fee = U256::from_dec_str("10000000000000000").unwrap();
+
+    // without a positions manager the open interest caps can not be checked
+    orderbook
+        .create_increase_order(
+            expand_decimals(1, 18),
+            Address::from_low_u64_be(123),
+            Address::from_low_u64_be(456),
+            expand_decimals(10, 18),
+            true,
+            U256::from(12345),
+            true,
+            execution_fee,
+        )
+        .value(execution_fee)
+        .await
+        .assert_revert(PositionsManagerNotSet {});
+
+    orderbook
+        .connect_acc(user.clone())
+        .set_positions_manager(Address::from_low_u64_be(789))
+        .await
+        .assert_revert(Forbidden {});
+    orderbook
+        .set_positions_manager(Address::from_low_u64_be(789))
+        .await
+        .unwrap();
+}
@@ -119,6 +119,7 @@ async fn test_increase_order() {
     let orderbook = OrderbookIncreaseInitArgs {
         gov: gov.address(),
         swap_router: Address::default(),
+        positions_manager: Address::default(),
     }
     .init(gov.clone(), orderbook)
     .await;
@@ -2,19 +2,27 @@ use std::sync::Arc;
 
 use ethers::types::U256;
 
+use log::LevelFilter;
 use omx_tests::{
-    constants::ETH_DECIMALS,
-    contracts::{reward_router::Forbidden, ContractAddresses},
+    constants::{BTC_DECIMALS, DAI_DECIMALS, ETH_DECIMALS},
+    contracts::{
+        olp_manager::{
+            ArrayLengthMismatch, CooldownNotPassed, InsufficientOutput, InvalidCooldownDuration,
+            TokenListMismatch,
+        },
+        olp_manager_utils::{InvalidAumAdjustment, InvalidWeight},
+        reward_router::Forbidden,
+        ContractAddresses,
+    },
     init::{Contracts, ContractsInitArgs},
     stylus_testing::provider::{TestClient, TestProvider},
     utils::{
         errors::ContractRevertExt,
         logs::configure_logs,
-        prices::expand_decimals,
+        prices::{expand_decimals, to_price},
         test_helpers::{create_gov, create_user, ConnectAcc},
     },
 };
-use log::LevelFilter;
 
 pub async fn init() -> (Contracts, Arc<TestClient>) {
     configure_logs(LevelFilter::Info);
@@ -85,3 +93,434 @@ async fn test_set_handler() {
         true
     );
 }
+
+#[tokio::test]
+async fn test_set_cooldown_duration() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let olp_manager = &contracts.staking.olp_manager;
+    assert_eq!(
+        olp_manager.cooldown_duration().await.unwrap(),
+        U256::from(24 * 60 * 60)
+    );
+
+    olp_manager
+        .connect_acc(user0.clone())
+        .set_cooldown_duration(U256::from(60 * 60))
+        .await
+        .assert_revert(Forbidden {});
+    olp_manager
+        .set_cooldown_duration(U256::from(48 * 60 * 60 + 1))
+        .await
+        .assert_revert(InvalidCooldownDuration {
+            max_duration: U256::from(48 * 60 * 60),
+        });
+    olp_manager
+        .set_cooldown_duration(U256::from(60 * 60))
+        .await
+        .unwrap();
+    assert_eq!(
+        olp_manager.cooldown_duration().await.unwrap(),
+        U256::from(60 * 60)
+    );
+
+    olp_manager
+        .connect_acc(user0.clone())
+        .set_cooldown_exempt(user1.address(), true)
+        .await
+        .assert_revert(Forbidden {});
+    olp_manager
+        .set_cooldown_exempt(user1.address(), true)
+        .await
+        .unwrap();
+    assert!(olp_manager
+        .is_cooldown_exempt(user1.address())
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn test_cooldown_exempt_account() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let olp_manager = &contracts.staking.olp_manager;
+    let dai = contracts.tokens.dai.address();
+
+    contracts.set_price(dai, to_price(1)).await;
+    contracts.vault.set_dai_config(dai).await;
+
+    olp_manager.set_in_private_mode(false).await.unwrap();
+
+    contracts
+        .tokens
+        .mint_dai(user0.address(), expand_decimals(100, DAI_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .dai
+        .connect_acc(user0.clone())
+        .approve(olp_manager.address(), U256::MAX)
+        .await
+        .unwrap();
+    let olp_amount = olp_manager
+        .connect_acc(user0.clone())
+        .add_liquidity(
+            dai,
+            expand_decimals(100, DAI_DECIMALS),
+            U256::zero(),
+            U256::zero(),
+        )
+        .await
+        .unwrap();
+
+    // exempting a handler does not let the accounts it serves skip the cooldown
+    olp_manager.set_handler(gov.address(), true).await.unwrap();
+    olp_manager
+        .set_cooldown_exempt(gov.address(), true)
+        .await
+        .unwrap();
+    olp_manager
+        .remove_liquidity_for_account(
+            user0.address(),
+            dai,
+            olp_amount,
+            U256::zero(),
+            user0.address(),
+        )
+        .await
+        .assert_revert(CooldownNotPassed {});
+
+    olp_manager
+        .set_cooldown_exempt(user0.address(), true)
+        .await
+        .unwrap();
+    olp_manager
+        .connect_acc(user0.clone())
+        .remove_liquidity(dai, olp_amount, U256::zero(), user0.address())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_set_aum_adjustment() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let olp_manager_utils = &contracts.staking.olp_manager_utils;
+
+    olp_manager_utils
+        .connect_acc(user0.clone())
+        .set_aum_adjustment(to_price(10), U256::zero())
+        .await
+        .assert_revert(Forbidden {});
+    olp_manager_utils
+        .set_aum_adjustment(to_price(10_000_001), U256::zero())
+        .await
+        .assert_revert(InvalidAumAdjustment {
+            max_adjustment: to_price(10_000_000),
+        });
+
+    olp_manager_utils
+        .set_aum_adjustment(to_price(10), to_price(4))
+        .await
+        .unwrap();
+    assert_eq!(
+        olp_manager_utils.get_aums().await.unwrap(),
+        (to_price(6), to_price(6))
+    );
+
+    // the deduction can not push the aum below zero
+    olp_manager_utils
+        .set_aum_adjustment(U256::zero(), to_price(4))
+        .await
+        .unwrap();
+    assert_eq!(olp_manager_utils.get_aum(true).await.unwrap(), U256::zero());
+}
+
+#[tokio::test]
+async fn test_global_short_average_price() {
+    let (contracts, _gov) = init().await;
+
+    let olp_manager_utils = &contracts.staking.olp_manager_utils;
+    let shorts_tracker = &contracts.staking.shorts_tracker;
+    let btc = contracts.tokens.btc.address();
+
+    olp_manager_utils
+        .set_shorts_tracker_average_price_weight(U256::from(10001))
+        .await
+        .assert_revert(InvalidWeight {
+            max_weight: U256::from(10000),
+        });
+
+    // until the tracker data is ready the positions manager price is used
+    olp_manager_utils
+        .set_shorts_tracker_average_price_weight(U256::from(10000))
+        .await
+        .unwrap();
+    assert_eq!(
+        olp_manager_utils
+            .get_global_short_average_price(btc)
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+
+    shorts_tracker
+        .set_init_data(vec![btc], vec![to_price(50000)])
+        .await
+        .unwrap();
+
+    // the weight is in basis points, the full weight only reads the tracker
+    assert_eq!(
+        olp_manager_utils
+            .get_global_short_average_price(btc)
+            .await
+            .unwrap(),
+        to_price(50000)
+    );
+
+    olp_manager_utils
+        .set_shorts_tracker_average_price_weight(U256::from(5000))
+        .await
+        .unwrap();
+    assert_eq!(
+        olp_manager_utils
+            .get_global_short_average_price(btc)
+            .await
+            .unwrap(),
+        to_price(25000)
+    );
+
+    olp_manager_utils
+        .set_shorts_tracker_average_price_weight(U256::zero())
+        .await
+        .unwrap();
+    assert_eq!(
+        olp_manager_utils
+            .get_global_short_average_price(btc)
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+}
+
+#[tokio::test]
+async fn test_liquidity_basket_validation() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let olp_manager = &contracts.staking.olp_manager;
+    let bnb = contracts.tokens.bnb.address();
+
+    olp_manager
+        .add_liquidity_basket(vec![], vec![], U256::zero(), U256::zero())
+        .await
+        .assert_revert(ArrayLengthMismatch {});
+    olp_manager
+        .add_liquidity_basket(
+            vec![bnb],
+            vec![U256::from(1), U256::from(1)],
+            U256::zero(),
+            U256::zero(),
+        )
+        .await
+        .assert_revert(ArrayLengthMismatch {});
+    olp_manager
+        .connect_acc(user0.clone())
+        .add_liquidity_basket_for_account(
+            user0.address(),
+            user0.address(),
+            vec![bnb],
+            vec![U256::from(1)],
+            U256::zero(),
+            U256::zero(),
+        )
+        .await
+        .assert_revert(Forbidden {});
+
+    // every token of the basket needs a min amount
+    olp_manager
+        .remove_liquidity_basket(
+            U256::from(1),
+            vec![bnb],
+            vec![U256::zero(); 2],
+            user0.address(),
+        )
+        .await
+        .assert_revert(ArrayLengthMismatch {});
+    olp_manager
+        .connect_acc(user0.clone())
+        .remove_liquidity_basket_for_account(
+            user0.address(),
+            U256::from(1),
+            vec![bnb],
+            vec![U256::zero()],
+            user0.address(),
+        )
+        .await
+        .assert_revert(Forbidden {});
+}
+
+#[tokio::test]
+async fn test_remove_liquidity_basket_without_fees() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let olp_manager = &contracts.staking.olp_manager;
+    let vault = &contracts.vault.vault;
+    let btc = contracts.tokens.btc.address();
+    let dai = contracts.tokens.dai.address();
+
+    contracts.set_price(dai, to_price(1)).await;
+    contracts.vault.set_dai_config(dai).await;
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    olp_manager.set_in_private_mode(false).await.unwrap();
+    olp_manager
+        .set_cooldown_duration(U256::zero())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, BTC_DECIMALS - 2))
+        .await;
+    contracts
+        .tokens
+        .mint_dai(user0.address(), expand_decimals(400, DAI_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .approve(olp_manager.address(), U256::MAX)
+        .await
+        .unwrap();
+    contracts
+        .tokens
+        .dai
+        .connect_acc(user0.clone())
+        .approve(olp_manager.address(), U256::MAX)
+        .await
+        .unwrap();
+
+    olp_manager
+        .connect_acc(user0.clone())
+        .add_liquidity_basket(
+            vec![btc, dai],
+            vec![
+                expand_decimals(1, BTC_DECIMALS - 2),
+                expand_decimals(400, DAI_DECIMALS),
+            ],
+            U256::zero(),
+            U256::zero(),
+        )
+        .await
+        .unwrap();
+
+    let olp_amount = contracts
+        .tokens
+        .olp
+        .balance_of(user0.address())
+        .await
+        .unwrap();
+
+    // the whole pool is owned by user0, a fee-free pro-rata exit drains every token
+    let tokens_length = vault.all_whitelisted_tokens_length().await.unwrap();
+    let mut tokens = vec![];
+    let mut pool_amounts = vec![];
+    for i in 0..tokens_length.as_u64() {
+        let token = vault.all_whitelisted_tokens(i).await.unwrap();
+        pool_amounts.push(vault.pool_amount(token).await.unwrap());
+        tokens.push(token);
+    }
+
+    // the min amounts are matched against the tokens in the order of the vault
+    let mut reversed_tokens = tokens.clone();
+    reversed_tokens.reverse();
+    olp_manager
+        .connect_acc(user0.clone())
+        .remove_liquidity_basket(
+            olp_amount,
+            reversed_tokens,
+            vec![U256::zero(); tokens.len()],
+            user0.address(),
+        )
+        .await
+        .assert_revert(TokenListMismatch {});
+
+    // a basket exit can not drain a token below its buffer
+    let swap_manager = &contracts.vault.swap_manager;
+    swap_manager
+        .set_buffer_amount(btc, U256::one())
+        .await
+        .unwrap();
+    olp_manager
+        .connect_acc(user0.clone())
+        .remove_liquidity_basket(
+            olp_amount,
+            tokens.clone(),
+            vec![U256::zero(); tokens.len()],
+            user0.address(),
+        )
+        .await
+        .assert_revert_str("Vault: pool_amount < buffer");
+    swap_manager
+        .set_buffer_amount(btc, U256::zero())
+        .await
+        .unwrap();
+
+    let mut min_amounts = pool_amounts.clone();
+    let btc_index = tokens.iter().position(|token| *token == btc).unwrap();
+    min_amounts[btc_index] += U256::one();
+    olp_manager
+        .connect_acc(user0.clone())
+        .remove_liquidity_basket(olp_amount, tokens.clone(), min_amounts, user0.address())
+        .await
+        .assert_revert(InsufficientOutput {
+            amount_out: pool_amounts[btc_index],
+        });
+
+    let amounts_out = olp_manager
+        .connect_acc(user0.clone())
+        .remove_liquidity_basket(
+            olp_amount,
+            tokens.clone(),
+            pool_amounts.clone(),
+            user0.address(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(amounts_out, pool_amounts);
+
+    for token in tokens {
+        assert_eq!(vault.pool_amount(token).await.unwrap(), U256::zero());
+        assert_eq!(
+            contracts
+                .vault
+                .swap_manager
+                .usdo_amount(token)
+                .await
+                .unwrap(),
+            U256::zero()
+        );
+    }
+    assert_eq!(
+        contracts
+            .tokens
+            .btc
+            .balance_of(user0.address())
+            .await
+            .unwrap(),
+        pool_amounts[btc_index]
+    );
+}
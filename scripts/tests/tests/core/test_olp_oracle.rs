@@ -0,0 +1,232 @@
+use std::sync::Arc;
+
+use ethers::types::U256;
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS},
+    contracts::{olp_oracle, ContractAddresses},
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+const HOUR: u64 = 60 * 60;
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+/// mints olp against btc so the olp price is defined
+async fn add_liquidity(contracts: &Contracts, account: Arc<TestClient>) {
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .staking
+        .olp_manager
+        .set_in_private_mode(false)
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .mint_btc(account.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(account.clone())
+        .approve(contracts.staking.olp_manager.address(), U256::MAX)
+        .await
+        .unwrap();
+    contracts
+        .staking
+        .olp_manager
+        .connect_acc(account.clone())
+        .add_liquidity(btc, U256::from(250000), U256::zero(), U256::zero())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_checkpoint_is_permissionless() {
+    let (contracts, gov) = init().await;
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    add_liquidity(&contracts, user0.clone()).await;
+
+    let oracle = &contracts.periphery.olp_oracle;
+
+    let index = oracle
+        .connect_acc(user1.clone())
+        .checkpoint()
+        .await
+        .unwrap();
+    assert_eq!(index, U256::zero());
+    assert_eq!(oracle.snapshots_count().await.unwrap(), U256::from(1));
+
+    gov.advance_block_timestamp(HOUR);
+
+    let index = oracle
+        .connect_acc(user0.clone())
+        .checkpoint()
+        .await
+        .unwrap();
+    assert_eq!(index, U256::from(1));
+}
+
+#[tokio::test]
+async fn test_stale_snapshot() {
+    let (contracts, gov) = init().await;
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    add_liquidity(&contracts, user0.clone()).await;
+
+    let oracle = &contracts.periphery.olp_oracle;
+
+    let t0 = gov.block_timestamp().as_u64();
+    oracle.checkpoint().await.unwrap();
+    let price0 = oracle.get_price(true).await.unwrap();
+
+    gov.advance_block_timestamp(2 * HOUR);
+    assert_eq!(oracle.price_at(U256::from(t0), true).await.unwrap(), price0);
+    assert_eq!(
+        oracle.get_twap(U256::from(HOUR), true).await.unwrap(),
+        price0
+    );
+
+    // no checkpoint for more than two hours
+    gov.advance_block_timestamp(1);
+    oracle
+        .price_at(U256::from(t0), true)
+        .await
+        .assert_revert(olp_oracle::StaleSnapshot {
+            latest_timestamp: U256::from(t0),
+        });
+    oracle
+        .get_twap(U256::from(HOUR), true)
+        .await
+        .assert_revert(olp_oracle::StaleSnapshot {
+            latest_timestamp: U256::from(t0),
+        });
+
+    oracle.checkpoint().await.unwrap();
+    assert_eq!(
+        oracle.get_twap(U256::from(HOUR), true).await.unwrap(),
+        price0
+    );
+}
+
+#[tokio::test]
+async fn test_snapshots_and_twap() {
+    let (contracts, gov) = init().await;
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let btc = contracts.tokens.btc.address();
+
+    add_liquidity(&contracts, user0.clone()).await;
+
+    let oracle = &contracts.periphery.olp_oracle;
+    let utils = &contracts.staking.olp_manager_utils;
+
+    oracle
+        .price_at(U256::from(gov.block_timestamp().as_u64()), true)
+        .await
+        .assert_revert(olp_oracle::SnapshotNotFound {});
+
+    let price0 = utils.get_price(true).await.unwrap();
+    assert_eq!(oracle.get_price(true).await.unwrap(), price0);
+
+    let t0 = gov.block_timestamp().as_u64();
+    oracle.checkpoint().await.unwrap();
+
+    let next_checkpoint_at = (t0 / HOUR + 1) * HOUR;
+    oracle
+        .checkpoint()
+        .await
+        .assert_revert(olp_oracle::CheckpointTooEarly {
+            next_checkpoint_at: U256::from(next_checkpoint_at),
+        });
+
+    // the olp price follows btc, the pool only holds btc
+    contracts.set_price(btc, to_price(44000)).await;
+    let price1 = utils.get_price(true).await.unwrap();
+    assert_eq!(price1, price0 * 11 / 10);
+
+    gov.advance_block_timestamp(next_checkpoint_at - t0);
+    let t1 = gov.block_timestamp().as_u64();
+    assert_eq!(t1, next_checkpoint_at);
+
+    assert_eq!(oracle.checkpoint().await.unwrap(), U256::from(1));
+
+    let (timestamp, max_price, min_price) = oracle.get_snapshot(U256::zero()).await.unwrap();
+    assert_eq!(timestamp, U256::from(t0));
+    assert_eq!(max_price, price0);
+    assert_eq!(min_price, price0);
+
+    let (timestamp, max_price, _) = oracle.get_snapshot(U256::from(1)).await.unwrap();
+    assert_eq!(timestamp, U256::from(t1));
+    assert_eq!(max_price, price1);
+
+    oracle
+        .get_snapshot(U256::from(2))
+        .await
+        .assert_revert(olp_oracle::SnapshotNotFound {});
+
+    oracle
+        .price_at(U256::from(t0 - 1), true)
+        .await
+        .assert_revert(olp_oracle::SnapshotNotFound {});
+    assert_eq!(oracle.price_at(U256::from(t0), true).await.unwrap(), price0);
+    assert_eq!(
+        oracle.price_at(U256::from(t1 - 1), true).await.unwrap(),
+        price0
+    );
+    assert_eq!(oracle.price_at(U256::from(t1), true).await.unwrap(), price1);
+
+    gov.advance_block_timestamp(HOUR);
+    let t2 = gov.block_timestamp().as_u64();
+
+    // each snapshot price is held until the next snapshot
+    let period = t2 - t0;
+    let expected = (price0 * (t1 - t0) + price1 * HOUR) / period;
+    assert_eq!(
+        oracle.get_twap(U256::from(period), true).await.unwrap(),
+        expected
+    );
+    assert_eq!(
+        oracle.get_twap(U256::from(HOUR), true).await.unwrap(),
+        price1
+    );
+
+    oracle
+        .get_twap(U256::zero(), true)
+        .await
+        .assert_revert(olp_oracle::InvalidPeriod {});
+    oracle
+        .get_twap(U256::from(period + 1), true)
+        .await
+        .assert_revert(olp_oracle::SnapshotNotFound {});
+}
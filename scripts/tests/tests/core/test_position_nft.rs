@@ -0,0 +1,284 @@
+use std::sync::Arc;
+
+use ethers::types::{Address, U256};
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS},
+    contracts::{
+        position_nft::{NotTokenOwner, OperatorNotApproved},
+        ContractAddresses,
+    },
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_wrap_transfer_unwrap() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user0.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    let positions_manager = &contracts.vault.positions_manager;
+    let position_nft = &contracts.periphery.position_nft;
+
+    let key = positions_manager
+        .get_position_key(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    let new_key = positions_manager
+        .get_position_key(user1.address(), btc, btc, true)
+        .await
+        .unwrap();
+
+    position_nft
+        .connect_acc(user0.clone())
+        .wrap(btc, btc, true)
+        .await
+        .assert_revert(OperatorNotApproved {});
+
+    positions_manager
+        .connect_acc(user0.clone())
+        .approve_position(key, position_nft.address())
+        .await
+        .unwrap();
+    position_nft
+        .connect_acc(user0.clone())
+        .wrap(btc, btc, true)
+        .await
+        .unwrap();
+
+    let token_id = U256::one();
+    assert_eq!(
+        position_nft.owner_of(token_id).await.unwrap(),
+        user0.address()
+    );
+    assert_eq!(position_nft.token_of_position(key).await.unwrap(), token_id);
+    assert_eq!(
+        positions_manager.position_lock(key).await.unwrap(),
+        position_nft.address()
+    );
+
+    // the wrapped position can only move with the token
+    positions_manager
+        .connect_acc(user0.clone())
+        .transfer_position(key, user1.address())
+        .await
+        .assert_revert_str("Vault: position is locked");
+    positions_manager
+        .connect_acc(user0.clone())
+        .approve_position(key, Address::zero())
+        .await
+        .assert_revert_str("Vault: position is locked");
+    positions_manager
+        .connect_acc(user0.clone())
+        .set_position_operator(position_nft.address(), false)
+        .await
+        .unwrap();
+
+    position_nft
+        .connect_acc(user0.clone())
+        .transfer_from(user0.address(), user1.address(), token_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        position_nft.owner_of(token_id).await.unwrap(),
+        user1.address()
+    );
+    assert_eq!(
+        position_nft.token_of_position(new_key).await.unwrap(),
+        token_id
+    );
+    assert_eq!(
+        positions_manager.position_info(new_key).await.unwrap(),
+        (user1.address(), btc, btc, true)
+    );
+    assert_eq!(
+        positions_manager.position_lock(new_key).await.unwrap(),
+        position_nft.address()
+    );
+    assert_eq!(
+        positions_manager.position_lock(key).await.unwrap(),
+        Address::zero()
+    );
+
+    position_nft
+        .connect_acc(user0.clone())
+        .unwrap(token_id)
+        .await
+        .assert_revert(NotTokenOwner {});
+    position_nft
+        .connect_acc(user1.clone())
+        .unwrap(token_id)
+        .await
+        .unwrap();
+
+    // unwrapping releases the lock and revokes the approval of the nft contract
+    assert_eq!(position_nft.total_supply().await.unwrap(), U256::zero());
+    assert_eq!(
+        positions_manager.position_lock(new_key).await.unwrap(),
+        Address::zero()
+    );
+    assert_eq!(
+        positions_manager.position_approval(new_key).await.unwrap(),
+        Address::zero()
+    );
+    positions_manager
+        .connect_acc(user1.clone())
+        .transfer_position(new_key, user0.address())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_safe_transfer_without_data() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user0.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    let positions_manager = &contracts.vault.positions_manager;
+    let position_nft = &contracts.periphery.position_nft;
+
+    // erc-165, erc-721 and the erc-721 metadata extension
+    for interface_id in [
+        [0x01, 0xff, 0xc9, 0xa7],
+        [0x80, 0xac, 0x58, 0xcd],
+        [0x5b, 0x5e, 0x13, 0x9f],
+    ] {
+        assert!(position_nft.supports_interface(interface_id).await.unwrap());
+    }
+    assert!(!position_nft
+        .supports_interface([0xff, 0xff, 0xff, 0xff])
+        .await
+        .unwrap());
+
+    let key = positions_manager
+        .get_position_key(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    positions_manager
+        .connect_acc(user0.clone())
+        .approve_position(key, position_nft.address())
+        .await
+        .unwrap();
+    position_nft
+        .connect_acc(user0.clone())
+        .wrap(btc, btc, true)
+        .await
+        .unwrap();
+
+    let token_id = U256::one();
+    position_nft
+        .connect_acc(user0.clone())
+        .safe_transfer_from(user0.address(), user1.address(), token_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        position_nft.owner_of(token_id).await.unwrap(),
+        user1.address()
+    );
+}
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use ethers::types::{Address, U256};
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS, USD_DECIMALS},
+    contracts::ContractAddresses,
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_reader() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+    let reader = &contracts.periphery.reader;
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user0.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    contracts.set_price(btc, to_price(41000)).await;
+
+    let position = contracts
+        .vault
+        .positions_manager
+        .position(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+
+    // the second position is not open and is reported as zeros
+    let positions = reader
+        .get_positions(
+            user0.address(),
+            vec![btc, btc],
+            vec![btc, btc],
+            vec![true, false],
+        )
+        .await
+        .unwrap();
+    assert_eq!(positions.len(), 18);
+    assert_eq!(
+        positions[..9],
+        [
+            to_price(90),
+            expand_decimals(991, USD_DECIMALS - 2),
+            to_price(40000),
+            position.3,
+            U256::zero(),
+            U256::zero(),
+            position.6,
+            U256::one(),
+            expand_decimals(225, USD_DECIMALS - 2),
+        ]
+    );
+    assert!(positions[9..].iter().all(|value| value.is_zero()));
+
+    let vault = &contracts.vault;
+    let info = reader.get_vault_token_info(vec![btc]).await.unwrap();
+    assert_eq!(
+        info,
+        vec![
+            vault.vault.pool_amount(btc).await.unwrap(),
+            vault.vault.reserved_amount(btc).await.unwrap(),
+            vault.swap_manager.usdo_amount(btc).await.unwrap(),
+            vault.vault.token_weight(btc).await.unwrap(),
+            to_price(41000),
+            to_price(41000),
+            vault
+                .funding_rate_manager
+                .cumulative_funding_rate(btc)
+                .await
+                .unwrap(),
+            vault
+                .funding_rate_manager
+                .get_next_funding_rate(btc)
+                .await
+                .unwrap(),
+            vault.positions_manager.guaranteed_usd(btc).await.unwrap(),
+        ]
+    );
+    assert_eq!(info[1], U256::from(225000));
+    assert_eq!(info[8], expand_decimals(8009, USD_DECIMALS - 2));
+
+    // the zero address reports the native balance without a supply
+    let balances = reader
+        .get_token_balances_with_supplies(user0.address(), vec![btc, Address::zero()])
+        .await
+        .unwrap();
+    assert_eq!(
+        balances,
+        vec![
+            contracts
+                .tokens
+                .btc
+                .balance_of(user0.address())
+                .await
+                .unwrap(),
+            contracts.tokens.btc.total_supply().await.unwrap(),
+            gov.balance(user0.address()),
+            U256::zero(),
+        ]
+    );
+
+    assert_eq!(
+        reader.get_fees(vec![btc]).await.unwrap(),
+        vec![vault.fee_manager.get_fee_reserve(btc).await.unwrap()]
+    );
+
+    reader
+        .get_positions(user0.address(), vec![btc], vec![], vec![true])
+        .await
+        .assert_revert_str("Vault: array length mismatch");
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use ethers::types::U256;
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS, USDC_DECIMALS},
+    contracts::{usdo_router, ContractAddresses},
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_mint_redeem_usdo() {
+    let (contracts, gov) = init().await;
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+    let usdc = contracts.tokens.usdc.address();
+    let usdo_router = &contracts.router.usdo;
+    let olp_manager_utils = &contracts.staking.olp_manager_utils;
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+    contracts.set_price(usdc, to_price(1)).await;
+    contracts.vault.set_usdc_config(usdc).await;
+
+    // olp liquidity
+    contracts
+        .staking
+        .olp_manager
+        .set_in_private_mode(false)
+        .await
+        .unwrap();
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .approve(contracts.staking.olp_manager.address(), U256::MAX)
+        .await
+        .unwrap();
+    contracts
+        .staking
+        .olp_manager
+        .connect_acc(user0.clone())
+        .add_liquidity(btc, U256::from(250000), U256::zero(), U256::zero())
+        .await
+        .unwrap();
+
+    let aum = olp_manager_utils.get_aum(true).await.unwrap();
+    let olp_price = olp_manager_utils.get_price(true).await.unwrap();
+    assert_eq!(
+        olp_manager_utils.get_usdo_liability().await.unwrap(),
+        U256::zero()
+    );
+
+    let amount_in = expand_decimals(1000, USDC_DECIMALS);
+    contracts.tokens.mint_usdc(user1.address(), amount_in).await;
+    contracts
+        .tokens
+        .usdc
+        .connect_acc(user1.clone())
+        .approve(usdo_router.address(), U256::MAX)
+        .await
+        .unwrap();
+
+    usdo_router
+        .connect_acc(user1.clone())
+        .mint_usdo(btc, U256::from(1000), U256::zero(), user1.address())
+        .await
+        .assert_revert(usdo_router::NotStableToken {});
+
+    let usdo_amount = usdo_router.get_mint_amount(usdc, amount_in).await.unwrap();
+    usdo_router
+        .connect_acc(user1.clone())
+        .mint_usdo(usdc, amount_in, usdo_amount + U256::one(), user1.address())
+        .await
+        .assert_revert(usdo_router::InsufficientAmountOut {});
+    usdo_router
+        .connect_acc(user1.clone())
+        .mint_usdo(usdc, amount_in, usdo_amount, user1.address())
+        .await
+        .unwrap();
+    assert_eq!(
+        contracts
+            .balance(contracts.tokens.usdo.address(), user1.address())
+            .await,
+        usdo_amount
+    );
+
+    // the deposited stables back the usdo, not the olp
+    assert_eq!(
+        olp_manager_utils.get_usdo_liability().await.unwrap(),
+        usdo_amount * expand_decimals(1, 12)
+    );
+    assert_eq!(olp_manager_utils.get_aum(true).await.unwrap(), aum);
+    assert_eq!(olp_manager_utils.get_price(true).await.unwrap(), olp_price);
+
+    contracts
+        .tokens
+        .usdo
+        .connect_acc(user1.clone())
+        .approve(usdo_router.address(), U256::MAX)
+        .await
+        .unwrap();
+
+    let amount_out = usdo_router
+        .get_redeem_amount(usdc, usdo_amount)
+        .await
+        .unwrap();
+    usdo_router
+        .connect_acc(user1.clone())
+        .redeem_usdo(usdc, usdo_amount, amount_out + U256::one(), user1.address())
+        .await
+        .assert_revert(usdo_router::InsufficientAmountOut {});
+    usdo_router
+        .connect_acc(user1.clone())
+        .redeem_usdo(usdc, usdo_amount, amount_out, user1.address())
+        .await
+        .unwrap();
+    assert_eq!(contracts.balance(usdc, user1.address()).await, amount_out);
+
+    assert_eq!(
+        olp_manager_utils.get_usdo_liability().await.unwrap(),
+        U256::zero()
+    );
+    assert_eq!(olp_manager_utils.get_aum(true).await.unwrap(), aum);
+    assert_eq!(olp_manager_utils.get_price(true).await.unwrap(), olp_price);
+}
@@ -1,4 +1,13 @@
+pub mod test_auto_deleverage;
 pub mod test_buy_usdo;
 pub mod test_close_long_position;
+pub mod test_cross_margin;
+pub mod test_open_interest;
+pub mod test_position_collateral;
+pub mod test_position_index;
+pub mod test_position_quote;
+pub mod test_position_storage;
+pub mod test_position_transfer;
+pub mod test_price_impact;
 pub mod test_sell_usdo;
 pub mod test_swap;
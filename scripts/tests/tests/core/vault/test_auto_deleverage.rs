@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use ethers::types::U256;
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS},
+    contracts::ContractAddresses,
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_auto_deleverage_ranking() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+    let user2 = create_user(gov.clone(), 2, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user2.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user2.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user2.address())
+        .await
+        .unwrap();
+
+    // same size, user0 has half of the collateral of user1 and so twice the pnl to collateral ratio
+    for (user, collateral) in [(user0.clone(), 25000), (user1.clone(), 50000)] {
+        contracts
+            .tokens
+            .btc
+            .connect_acc(user2.clone())
+            .transfer(contracts.vault.vault.address(), U256::from(collateral))
+            .await
+            .unwrap();
+        contracts
+            .vault
+            .positions_increase_manager
+            .connect_acc(user.clone())
+            .increase_position(user.address(), btc, btc, to_price(90), true)
+            .await
+            .unwrap();
+    }
+
+    contracts.set_price(btc, to_price(45100)).await;
+
+    let liquidation_manager = &contracts.vault.positions_liquidation_manager;
+
+    assert!(
+        liquidation_manager
+            .get_pnl_to_pool_bps(btc, true)
+            .await
+            .unwrap()
+            > U256::from(100)
+    );
+
+    liquidation_manager
+        .auto_deleverage(vec![user0.address()], btc, btc, true, vec![to_price(90)])
+        .await
+        .assert_revert_str("Vault: forbidden");
+
+    liquidation_manager
+        .set_adl_keeper(gov.address(), true)
+        .await
+        .unwrap();
+
+    liquidation_manager
+        .auto_deleverage(vec![user0.address()], btc, btc, true, vec![to_price(90)])
+        .await
+        .assert_revert_str("Vault: auto-deleveraging not required");
+
+    liquidation_manager
+        .set_max_pnl_to_pool_bps(btc, true, U256::from(100))
+        .await
+        .unwrap();
+
+    // the keeper ranks the positions off-chain, the threshold bounds which positions it may pick
+    liquidation_manager
+        .connect_acc(user0.clone())
+        .set_min_adl_pnl_to_collateral_bps(btc, U256::from(8000))
+        .await
+        .assert_revert_str("Vault: forbidden");
+    liquidation_manager
+        .set_min_adl_pnl_to_collateral_bps(btc, U256::from(8000))
+        .await
+        .unwrap();
+    assert_eq!(
+        liquidation_manager
+            .min_adl_pnl_to_collateral_bps(btc)
+            .await
+            .unwrap(),
+        U256::from(8000)
+    );
+
+    // user1 has half of the pnl to collateral ratio of user0
+    liquidation_manager
+        .auto_deleverage(vec![user1.address()], btc, btc, true, vec![to_price(90)])
+        .await
+        .assert_revert_str(
+            "Vault: pnl to collateral ratio is below the auto-deleveraging threshold",
+        );
+
+    liquidation_manager
+        .set_min_adl_pnl_to_collateral_bps(btc, U256::zero())
+        .await
+        .unwrap();
+    liquidation_manager
+        .auto_deleverage(
+            vec![user1.address(), user0.address()],
+            btc,
+            btc,
+            true,
+            vec![to_price(90), to_price(90)],
+        )
+        .await
+        .assert_revert_str("Vault: positions are not ordered by pnl to collateral ratio");
+
+    liquidation_manager
+        .set_min_adl_pnl_to_collateral_bps(btc, U256::from(8000))
+        .await
+        .unwrap();
+    liquidation_manager
+        .auto_deleverage(vec![user0.address()], btc, btc, true, vec![to_price(90)])
+        .await
+        .unwrap();
+
+    let position = contracts
+        .vault
+        .positions_manager
+        .position(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(position.0, U256::zero());
+    let position = contracts
+        .vault
+        .positions_manager
+        .position(user1.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(position.0, to_price(90));
+}
+
+#[tokio::test]
+async fn test_global_long_data() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+    let positions_manager = &contracts.vault.positions_manager;
+
+    // fresh deployments track the global long sizes from the start
+    assert!(positions_manager.is_global_long_data_ready().await.unwrap());
+
+    positions_manager
+        .connect_acc(user0.clone())
+        .set_global_long_data(vec![btc], vec![to_price(100)], vec![to_price(40000)])
+        .await
+        .assert_revert_str("Vault: forbidden");
+    positions_manager
+        .set_global_long_data(vec![btc], vec![to_price(100)], vec![to_price(40000)])
+        .await
+        .assert_revert_str("Vault: global long data is already set");
+}
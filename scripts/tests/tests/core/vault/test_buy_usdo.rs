@@ -204,14 +204,15 @@ pub async fn test_buy_usdo() {
     contracts
         .validate_vault_balance(contracts.tokens.bnb.address(), U256::zero())
         .await;
+    // the pool only backs the usdo minted to user1
     assert_eq!(
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
-        U256::from(29700)
+        U256::zero()
     );
 }
 
@@ -611,3 +612,53 @@ async fn test_buy_usdo_adjusts_for_decimals() {
         .validate_vault_balance(contracts.tokens.btc.address(), U256::zero())
         .await;
 }
+
+#[tokio::test]
+async fn test_aum_uses_price_spread() {
+    let (contracts, _) = init().await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .deposit_to_vault(btc, expand_decimals(1, BTC_DECIMALS))
+        .await;
+
+    assert_eq!(
+        contracts
+            .staking
+            .olp_manager_utils
+            .get_aums()
+            .await
+            .unwrap(),
+        (to_price(40000), to_price(40000))
+    );
+
+    contracts
+        .vault_price_feed
+        .set_spread_basis_points(btc, U256::from(10))
+        .await
+        .unwrap();
+
+    // mints are priced with the max price and redemptions with the min price
+    assert_eq!(
+        contracts
+            .staking
+            .olp_manager_utils
+            .get_aums()
+            .await
+            .unwrap(),
+        (to_price(40040), to_price(39960))
+    );
+    assert_eq!(
+        contracts
+            .staking
+            .olp_manager_utils
+            .get_aum_in_usdo(false)
+            .await
+            .unwrap(),
+        expand_decimals(39960, USDO_DECIMALS)
+    );
+}
@@ -561,3 +561,141 @@ async fn test_close_long_position_with_loss() {
         .validate_vault_balance(contracts.tokens.btc.address(), U256::from(1))
         .await;
 }
+
+#[tokio::test]
+async fn test_decrease_long_position_writes_once() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user1.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user1.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    let position_update = "positionUpdate(address,address,address,bool,uint256,uint256,uint256,uint256,uint256,int256,uint256)";
+    let positions_manager = &contracts.vault.positions_manager;
+
+    gov.reset_counters();
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user0.clone())
+        .decrease_position(
+            user0.address(),
+            btc,
+            btc,
+            to_price(3),
+            to_price(10),
+            true,
+            user0.address(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        gov.counters(positions_manager.address())
+            .calls_to(position_update),
+        1
+    );
+
+    // the guaranteed usd of a single long is its size minus its collateral
+    let position = positions_manager
+        .position(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(position.0, to_price(80));
+    assert_eq!(
+        positions_manager.guaranteed_usd(btc).await.unwrap(),
+        position.0 - position.1
+    );
+
+    gov.reset_counters();
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user0.clone())
+        .withdraw_collateral(
+            user0.address(),
+            btc,
+            btc,
+            to_price(1),
+            true,
+            user0.address(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        gov.counters(positions_manager.address())
+            .calls_to(position_update),
+        1
+    );
+
+    let position = positions_manager
+        .position(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(
+        positions_manager.guaranteed_usd(btc).await.unwrap(),
+        position.0 - position.1
+    );
+
+    gov.reset_counters();
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user0.clone())
+        .decrease_position(
+            user0.address(),
+            btc,
+            btc,
+            U256::zero(),
+            to_price(80),
+            true,
+            user0.address(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        gov.counters(positions_manager.address())
+            .calls_to(position_update),
+        1
+    );
+    assert_eq!(
+        positions_manager.guaranteed_usd(btc).await.unwrap(),
+        U256::zero()
+    );
+}
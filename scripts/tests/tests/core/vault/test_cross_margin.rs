@@ -0,0 +1,410 @@
+use std::sync::Arc;
+
+use ethers::types::U256;
+use log::LevelFilter;
+use omx_tests::{
+    constants::{DAI_DECIMALS, ETH_DECIMALS, USD_DECIMALS},
+    contracts::ContractAddresses,
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_cross_margin_balance_in_tokens() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let dai = contracts.tokens.dai.address();
+
+    contracts.set_price(dai, to_price(1)).await;
+    contracts.vault.set_dai_config(dai).await;
+
+    contracts
+        .tokens
+        .mint_dai(user0.address(), expand_decimals(100, DAI_DECIMALS))
+        .await;
+
+    contracts
+        .tokens
+        .dai
+        .connect_acc(user0.clone())
+        .transfer(
+            contracts.vault.vault.address(),
+            expand_decimals(20, DAI_DECIMALS),
+        )
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .deposit_margin(user0.address(), dai)
+        .await
+        .assert_revert_str("Vault: cross margin is disabled");
+
+    contracts
+        .vault
+        .positions_manager
+        .connect_acc(user0.clone())
+        .set_cross_margin_enabled(true)
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .deposit_margin(user0.address(), dai)
+        .await
+        .unwrap();
+
+    // the balance is kept in tokens, so price moves of the collateral do not change it
+    contracts.set_price(dai, expand_decimals(101, 28)).await;
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager
+            .cross_margin_balance(user0.address(), dai)
+            .await
+            .unwrap(),
+        expand_decimals(20, DAI_DECIMALS)
+    );
+
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user0.clone())
+        .withdraw_margin(
+            user0.address(),
+            dai,
+            expand_decimals(21, DAI_DECIMALS),
+            user0.address(),
+        )
+        .await
+        .assert_revert_str("Vault: margin balance exceeded");
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user0.clone())
+        .withdraw_margin(
+            user0.address(),
+            dai,
+            expand_decimals(20, DAI_DECIMALS),
+            user0.address(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        contracts
+            .tokens
+            .dai
+            .balance_of(user0.address())
+            .await
+            .unwrap(),
+        expand_decimals(100, DAI_DECIMALS)
+    );
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager
+            .cross_margin_balance(user0.address(), dai)
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+}
+
+#[tokio::test]
+async fn test_liquidation_seizes_margin_shortfall() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let dai = contracts.tokens.dai.address();
+    let btc = contracts.tokens.btc.address();
+    let bnb = contracts.tokens.bnb.address();
+
+    contracts.set_price(dai, to_price(1)).await;
+    contracts.vault.set_dai_config(dai).await;
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+    contracts.set_price(bnb, to_price(300)).await;
+    contracts.vault.set_bnb_config(bnb).await;
+
+    contracts
+        .tokens
+        .mint_dai(user1.address(), expand_decimals(1000, DAI_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .dai
+        .connect_acc(user1.clone())
+        .transfer(
+            contracts.vault.vault.address(),
+            expand_decimals(500, DAI_DECIMALS),
+        )
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(dai, user1.address())
+        .await
+        .unwrap();
+
+    contracts
+        .vault
+        .positions_manager
+        .connect_acc(user0.clone())
+        .set_cross_margin_enabled(true)
+        .await
+        .unwrap();
+    contracts
+        .tokens
+        .dai
+        .connect_acc(user1.clone())
+        .transfer(
+            contracts.vault.vault.address(),
+            expand_decimals(16, DAI_DECIMALS),
+        )
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .deposit_margin(user0.address(), dai)
+        .await
+        .unwrap();
+
+    // two shorts with $9.91 of collateral each after the position fee
+    for index_token in [btc, bnb] {
+        contracts
+            .tokens
+            .dai
+            .connect_acc(user1.clone())
+            .transfer(
+                contracts.vault.vault.address(),
+                expand_decimals(10, DAI_DECIMALS),
+            )
+            .await
+            .unwrap();
+        contracts
+            .vault
+            .positions_increase_manager
+            .connect_acc(user0.clone())
+            .increase_position(user0.address(), dai, index_token, to_price(90), false)
+            .await
+            .unwrap();
+    }
+
+    // both shorts lose $18, the $16 margin cannot cover both of them
+    contracts.set_price(btc, to_price(48000)).await;
+    contracts.set_price(bnb, to_price(360)).await;
+
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager_utils
+            .validate_cross_margin(user0.address(), dai, false)
+            .await
+            .unwrap(),
+        U256::one()
+    );
+
+    contracts
+        .vault
+        .positions_liquidation_manager
+        .liquidate_position(user0.address(), dai, btc, false, user1.address())
+        .await
+        .unwrap();
+
+    // only the $18 loss with the $0.09 position fee and the $5 liquidation fee
+    // exceeding the $9.91 of collateral are seized
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager
+            .cross_margin_balance(user0.address(), dai)
+            .await
+            .unwrap(),
+        expand_decimals(282, DAI_DECIMALS - 2)
+    );
+
+    let position = contracts
+        .vault
+        .positions_manager
+        .position(user0.address(), dai, bnb, false)
+        .await
+        .unwrap();
+    assert_eq!(position.0, to_price(90));
+}
+
+#[tokio::test]
+async fn test_liquidation_skips_profitable_positions() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let dai = contracts.tokens.dai.address();
+    let btc = contracts.tokens.btc.address();
+    let bnb = contracts.tokens.bnb.address();
+
+    contracts.set_price(dai, to_price(1)).await;
+    contracts.vault.set_dai_config(dai).await;
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+    contracts.set_price(bnb, to_price(300)).await;
+    contracts.vault.set_bnb_config(bnb).await;
+
+    contracts
+        .tokens
+        .mint_dai(user1.address(), expand_decimals(1000, DAI_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .dai
+        .connect_acc(user1.clone())
+        .transfer(
+            contracts.vault.vault.address(),
+            expand_decimals(500, DAI_DECIMALS),
+        )
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(dai, user1.address())
+        .await
+        .unwrap();
+
+    contracts
+        .vault
+        .positions_manager
+        .connect_acc(user0.clone())
+        .set_cross_margin_enabled(true)
+        .await
+        .unwrap();
+    contracts
+        .tokens
+        .dai
+        .connect_acc(user1.clone())
+        .transfer(
+            contracts.vault.vault.address(),
+            expand_decimals(16, DAI_DECIMALS),
+        )
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .deposit_margin(user0.address(), dai)
+        .await
+        .unwrap();
+
+    for index_token in [btc, bnb] {
+        contracts
+            .tokens
+            .dai
+            .connect_acc(user1.clone())
+            .transfer(
+                contracts.vault.vault.address(),
+                expand_decimals(10, DAI_DECIMALS),
+            )
+            .await
+            .unwrap();
+        contracts
+            .vault
+            .positions_increase_manager
+            .connect_acc(user0.clone())
+            .increase_position(user0.address(), dai, index_token, to_price(90), false)
+            .await
+            .unwrap();
+    }
+
+    // the btc short gains $9 while the bnb short loses $36
+    contracts.set_price(btc, to_price(36000)).await;
+    contracts.set_price(bnb, to_price(420)).await;
+
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager_utils
+            .validate_cross_margin(user0.address(), dai, false)
+            .await
+            .unwrap(),
+        U256::one()
+    );
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager_utils
+            .get_worst_cross_margin_position(user0.address(), dai)
+            .await
+            .unwrap(),
+        (bnb, to_price(36))
+    );
+
+    contracts
+        .vault
+        .positions_liquidation_manager
+        .liquidate_position(user0.address(), dai, btc, false, user1.address())
+        .await
+        .assert_revert_str(
+            "Vault: only the position with the largest loss of the margin account can be liquidated",
+        );
+
+    contracts
+        .vault
+        .positions_liquidation_manager
+        .liquidate_position(user0.address(), dai, bnb, false, user1.address())
+        .await
+        .unwrap();
+
+    // the profitable short keeps its collateral and its profit
+    let position = contracts
+        .vault
+        .positions_manager
+        .position(user0.address(), dai, btc, false)
+        .await
+        .unwrap();
+    assert_eq!(position.0, to_price(90));
+    assert_eq!(position.1, expand_decimals(991, USD_DECIMALS - 2));
+
+    let position = contracts
+        .vault
+        .positions_manager
+        .position(user0.address(), dai, bnb, false)
+        .await
+        .unwrap();
+    assert_eq!(position.0, U256::zero());
+}
@@ -0,0 +1,395 @@
+use std::sync::Arc;
+
+use ethers::types::U256;
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS},
+    contracts::ContractAddresses,
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_max_global_long_size() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    contracts
+        .set_price(contracts.tokens.btc.address(), to_price(40000))
+        .await;
+    contracts
+        .vault
+        .set_btc_config(contracts.tokens.btc.address())
+        .await;
+
+    contracts
+        .vault
+        .positions_manager
+        .connect_acc(user0.clone())
+        .set_max_global_sizes(contracts.tokens.btc.address(), to_price(100), U256::zero())
+        .await
+        .assert_revert_str("Vault: forbidden");
+
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager
+            .available_open_interest(contracts.tokens.btc.address(), true)
+            .await
+            .unwrap(),
+        U256::MAX
+    );
+
+    contracts
+        .vault
+        .positions_manager
+        .set_max_global_sizes(contracts.tokens.btc.address(), to_price(100), U256::zero())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager
+            .available_open_interest(contracts.tokens.btc.address(), true)
+            .await
+            .unwrap(),
+        to_price(100)
+    );
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager
+            .available_open_interest(contracts.tokens.btc.address(), false)
+            .await
+            .unwrap(),
+        U256::MAX
+    );
+
+    contracts
+        .tokens
+        .mint_btc(user1.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(contracts.tokens.btc.address(), user1.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(
+            user0.address(),
+            contracts.tokens.btc.address(),
+            contracts.tokens.btc.address(),
+            to_price(90),
+            true,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager
+            .available_open_interest(contracts.tokens.btc.address(), true)
+            .await
+            .unwrap(),
+        to_price(10)
+    );
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(
+            user0.address(),
+            contracts.tokens.btc.address(),
+            contracts.tokens.btc.address(),
+            to_price(20),
+            true,
+        )
+        .await
+        .assert_revert_str("Vault: max longs exceeded");
+
+    // removing the cap allows the increase
+    contracts
+        .vault
+        .positions_manager
+        .set_max_global_sizes(contracts.tokens.btc.address(), U256::zero(), U256::zero())
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(
+            user0.address(),
+            contracts.tokens.btc.address(),
+            contracts.tokens.btc.address(),
+            to_price(20),
+            true,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager
+            .global_long_size(contracts.tokens.btc.address())
+            .await
+            .unwrap(),
+        to_price(110)
+    );
+}
+
+#[tokio::test]
+async fn test_min_position() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .vault
+        .positions_manager
+        .connect_acc(user0.clone())
+        .set_min_position(btc, to_price(100), to_price(5))
+        .await
+        .assert_revert_str("Vault: forbidden");
+    contracts
+        .vault
+        .positions_manager
+        .set_min_position(btc, to_price(100), to_price(5))
+        .await
+        .unwrap();
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager
+            .min_position(btc)
+            .await
+            .unwrap(),
+        (to_price(100), to_price(5))
+    );
+
+    contracts
+        .tokens
+        .mint_btc(user1.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user1.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .assert_revert_str("Vault: position size below minimum");
+
+    contracts
+        .vault
+        .positions_manager
+        .set_min_position(btc, to_price(50), to_price(5))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    // the $40 remainder is below the minimum size, so the whole position is closed
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user0.clone())
+        .decrease_position(
+            user0.address(),
+            btc,
+            btc,
+            U256::zero(),
+            to_price(50),
+            true,
+            user0.address(),
+        )
+        .await
+        .unwrap();
+
+    let position = contracts
+        .vault
+        .positions_manager
+        .position(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(position.0, U256::zero());
+    assert_eq!(position.1, U256::zero());
+    assert_eq!(
+        contracts
+            .tokens
+            .btc
+            .balance_of(user0.address())
+            .await
+            .unwrap(),
+        U256::from(24550)
+    );
+}
+
+#[tokio::test]
+async fn test_min_collateral_after_losses() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .vault
+        .positions_manager
+        .set_min_position(btc, to_price(10), to_price(5))
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .mint_btc(user1.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user1.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    contracts.set_price(btc, to_price(38000)).await;
+
+    // $9.91 - $4 passes the estimate, but the $2 of realised losses
+    // leave $3.91 of collateral below the $5 minimum
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user0.clone())
+        .decrease_position(
+            user0.address(),
+            btc,
+            btc,
+            to_price(4),
+            to_price(40),
+            true,
+            user0.address(),
+        )
+        .await
+        .assert_revert_str("Vault: collateral below minimum");
+
+    let position = contracts
+        .vault
+        .positions_manager
+        .position(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(position.0, to_price(90));
+}
@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use ethers::types::U256;
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS, USD_DECIMALS},
+    contracts::ContractAddresses,
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_deposit_and_withdraw_collateral() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+    let user2 = create_user(gov.clone(), 2, 0).await;
+
+    contracts
+        .set_price(contracts.tokens.btc.address(), to_price(40000))
+        .await;
+    contracts
+        .vault
+        .set_btc_config(contracts.tokens.btc.address())
+        .await;
+
+    contracts
+        .tokens
+        .mint_btc(user1.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(contracts.tokens.btc.address(), user1.address())
+        .await
+        .unwrap();
+
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .deposit_collateral(
+            user0.address(),
+            contracts.tokens.btc.address(),
+            contracts.tokens.btc.address(),
+            true,
+        )
+        .await
+        .assert_revert_str("Vault: zero size");
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(
+            user0.address(),
+            contracts.tokens.btc.address(),
+            contracts.tokens.btc.address(),
+            to_price(90),
+            true,
+        )
+        .await
+        .unwrap();
+
+    // deposit $10 of collateral, no margin fee is charged
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .deposit_collateral(
+            user0.address(),
+            contracts.tokens.btc.address(),
+            contracts.tokens.btc.address(),
+            true,
+        )
+        .await
+        .unwrap();
+
+    let position = contracts
+        .vault
+        .positions_manager
+        .position(
+            user0.address(),
+            contracts.tokens.btc.address(),
+            contracts.tokens.btc.address(),
+            true,
+        )
+        .await
+        .unwrap();
+    assert_eq!(position.0, to_price(90));
+    assert_eq!(position.1, expand_decimals(1991, USD_DECIMALS - 2));
+    assert_eq!(position.4, U256::from(225000));
+
+    assert_eq!(
+        contracts
+            .vault
+            .fee_manager
+            .get_fee_reserve(contracts.tokens.btc.address())
+            .await
+            .unwrap(),
+        U256::from(975)
+    );
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager
+            .guaranteed_usd(contracts.tokens.btc.address())
+            .await
+            .unwrap(),
+        expand_decimals(7009, USD_DECIMALS - 2)
+    );
+    assert_eq!(
+        contracts
+            .vault
+            .vault
+            .pool_amount(contracts.tokens.btc.address())
+            .await
+            .unwrap(),
+        U256::from(299025)
+    );
+
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user0.clone())
+        .withdraw_collateral(
+            user0.address(),
+            contracts.tokens.btc.address(),
+            contracts.tokens.btc.address(),
+            to_price(19),
+            true,
+            user2.address(),
+        )
+        .await
+        .assert_revert_str("Vault: max leverage exceeded");
+
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user0.clone())
+        .withdraw_collateral(
+            user0.address(),
+            contracts.tokens.btc.address(),
+            contracts.tokens.btc.address(),
+            to_price(5),
+            true,
+            user2.address(),
+        )
+        .await
+        .unwrap();
+
+    let position = contracts
+        .vault
+        .positions_manager
+        .position(
+            user0.address(),
+            contracts.tokens.btc.address(),
+            contracts.tokens.btc.address(),
+            true,
+        )
+        .await
+        .unwrap();
+    assert_eq!(position.0, to_price(90));
+    assert_eq!(position.1, expand_decimals(1491, USD_DECIMALS - 2));
+    assert_eq!(position.4, U256::from(225000));
+
+    assert_eq!(
+        contracts
+            .vault
+            .positions_manager
+            .guaranteed_usd(contracts.tokens.btc.address())
+            .await
+            .unwrap(),
+        expand_decimals(7509, USD_DECIMALS - 2)
+    );
+    assert_eq!(
+        contracts
+            .vault
+            .vault
+            .pool_amount(contracts.tokens.btc.address())
+            .await
+            .unwrap(),
+        U256::from(286525)
+    );
+    assert_eq!(
+        contracts
+            .tokens
+            .btc
+            .balance_of(user2.address())
+            .await
+            .unwrap(),
+        U256::from(12500)
+    );
+
+    contracts
+        .validate_vault_balance(contracts.tokens.btc.address(), U256::zero())
+        .await;
+}
@@ -0,0 +1,256 @@
+use std::sync::Arc;
+
+use ethers::types::U256;
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS},
+    contracts::ContractAddresses,
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_account_positions() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user0.address())
+        .await
+        .unwrap();
+
+    let positions_manager = &contracts.vault.positions_manager;
+    assert_eq!(
+        positions_manager
+            .account_positions_length(user0.address())
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    let key = positions_manager
+        .get_position_key(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(
+        positions_manager
+            .account_positions_length(user0.address())
+            .await
+            .unwrap(),
+        U256::one()
+    );
+    assert_eq!(
+        positions_manager
+            .account_position_key(user0.address(), 0)
+            .await
+            .unwrap(),
+        key
+    );
+    assert_eq!(
+        positions_manager
+            .account_positions(user0.address())
+            .await
+            .unwrap(),
+        (vec![btc], vec![btc], vec![true])
+    );
+
+    // the key follows the position to the new owner
+    positions_manager
+        .connect_acc(user0.clone())
+        .transfer_position(key, user1.address())
+        .await
+        .unwrap();
+    positions_manager
+        .connect_acc(user1.clone())
+        .accept_position(key)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        positions_manager
+            .account_positions_length(user0.address())
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+    assert_eq!(
+        positions_manager
+            .account_positions(user1.address())
+            .await
+            .unwrap(),
+        (vec![btc], vec![btc], vec![true])
+    );
+
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user1.clone())
+        .decrease_position(
+            user1.address(),
+            btc,
+            btc,
+            U256::zero(),
+            to_price(90),
+            true,
+            user1.address(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        positions_manager
+            .account_positions_length(user1.address())
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+}
+
+#[tokio::test]
+async fn test_open_positions() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user0.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    let positions_manager = &contracts.vault.positions_manager;
+    let key = positions_manager
+        .get_position_key(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(
+        positions_manager.open_positions_length().await.unwrap(),
+        U256::one()
+    );
+    assert_eq!(
+        positions_manager
+            .get_open_positions(U256::zero(), U256::from(10))
+            .await
+            .unwrap(),
+        (vec![key], vec![user0.address()], vec![0])
+    );
+    assert_eq!(
+        positions_manager
+            .get_open_positions(U256::one(), U256::from(10))
+            .await
+            .unwrap(),
+        (vec![], vec![], vec![])
+    );
+
+    contracts.set_price(btc, to_price(35000)).await;
+    assert_eq!(
+        positions_manager
+            .get_open_positions(U256::zero(), U256::from(10))
+            .await
+            .unwrap(),
+        (vec![key], vec![user0.address()], vec![1])
+    );
+
+    contracts
+        .vault
+        .positions_liquidation_manager
+        .liquidate_position(user0.address(), btc, btc, true, user0.address())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        positions_manager.open_positions_length().await.unwrap(),
+        U256::zero()
+    );
+}
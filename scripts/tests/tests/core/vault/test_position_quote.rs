@@ -0,0 +1,250 @@
+use std::sync::Arc;
+
+use ethers::types::{I256, U256};
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS, USD_DECIMALS},
+    contracts::ContractAddresses,
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_quote_matches_execution() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+    let user2 = create_user(gov.clone(), 2, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user1.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user1.address())
+        .await
+        .unwrap();
+
+    let (fee, price_impact, size, collateral, average_price, leverage, liquidation_price) =
+        contracts
+            .vault
+            .positions_manager_utils
+            .quote_increase_position(
+                user0.address(),
+                btc,
+                btc,
+                U256::from(25000),
+                to_price(90),
+                true,
+            )
+            .await
+            .unwrap();
+    assert_eq!(fee, expand_decimals(9, USD_DECIMALS - 2));
+    assert_eq!(price_impact, I256::zero());
+    assert_eq!(leverage, U256::from(90817));
+    // the max leverage requirement of $1.8 is above the liquidation fees
+    assert_eq!(
+        liquidation_price,
+        to_price(40000) * expand_decimals(8189, USD_DECIMALS - 2) / to_price(90)
+    );
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    let position = contracts
+        .vault
+        .positions_manager
+        .position(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(position.0, size);
+    assert_eq!(position.1, collateral);
+    assert_eq!(position.2, average_price);
+
+    let (fee, _, usd_out, usd_out_after_fee, size, collateral, leverage, liquidation_price) =
+        contracts
+            .vault
+            .positions_manager_utils
+            .quote_decrease_position(user0.address(), btc, btc, U256::zero(), to_price(90), true)
+            .await
+            .unwrap();
+    assert_eq!(fee, expand_decimals(9, USD_DECIMALS - 2));
+    assert_eq!(usd_out, expand_decimals(991, USD_DECIMALS - 2));
+    assert_eq!(usd_out_after_fee, expand_decimals(982, USD_DECIMALS - 2));
+    assert_eq!(size, U256::zero());
+    assert_eq!(collateral, U256::zero());
+    assert_eq!(leverage, U256::zero());
+    assert_eq!(liquidation_price, U256::zero());
+
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user0.clone())
+        .decrease_position(
+            user0.address(),
+            btc,
+            btc,
+            U256::zero(),
+            to_price(90),
+            true,
+            user2.address(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        contracts
+            .tokens
+            .btc
+            .balance_of(user2.address())
+            .await
+            .unwrap(),
+        U256::from(24550)
+    );
+}
+
+#[tokio::test]
+async fn test_position_health() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user1.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user1.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    // $9.91 of collateral above the $1.8 max leverage requirement
+    let (health, buffer) = contracts
+        .vault
+        .positions_manager_utils
+        .get_position_health(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(health, 0);
+    assert_eq!(buffer, expand_decimals(811, USD_DECIMALS - 2));
+
+    let liquidation_price = contracts
+        .vault
+        .positions_manager_utils
+        .get_liquidation_price(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(
+        liquidation_price,
+        to_price(40000) * expand_decimals(8189, USD_DECIMALS - 2) / to_price(90)
+    );
+
+    let (state, _) = contracts
+        .vault
+        .positions_manager_utils
+        .validate_liquidation(user0.address(), btc, btc, true, false)
+        .await
+        .unwrap();
+    assert_eq!(state, U256::zero());
+
+    // $9 loss leaves $0.91 of collateral for the $90 position
+    contracts.set_price(btc, to_price(36000)).await;
+    let (health, buffer) = contracts
+        .vault
+        .positions_manager_utils
+        .get_position_health(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(health, 2);
+    assert_eq!(buffer, U256::zero());
+
+    contracts.set_price(btc, to_price(35000)).await;
+    let (health, _) = contracts
+        .vault
+        .positions_manager_utils
+        .get_position_health(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(health, 1);
+}
@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use ethers::types::{Address, I256, U256};
+use log::LevelFilter;
+use omx_tests::{
+    constants::ETH_DECIMALS,
+    contracts::{
+        get_contract_path,
+        positions_manager::{PositionsManager, PositionsManagerInitArgs, POSITIONSMANAGER_ABI},
+    },
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::create_gov,
+    },
+};
+
+/// deploys a standalone positions manager with gov as the increase manager,
+/// so positions can be written directly
+pub async fn init() -> (PositionsManager<TestClient>, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let path = get_contract_path("positions_manager");
+    let bytes = std::fs::read(&path).expect(&format!("file should exist {}", path));
+    let address = gov.deploy_contract(&bytes, POSITIONSMANAGER_ABI.clone(), "positions_manager");
+
+    let positions_manager = PositionsManagerInitArgs {
+        gov: gov.address(),
+        vault_utils: Address::zero(),
+        fee_manager: Address::zero(),
+        positions_decrease_manager: Address::zero(),
+        positions_increase_manager: gov.address(),
+        positions_liquidation_manager: Address::zero(),
+        positions_manager_utils: Address::zero(),
+    }
+    .init(gov.clone(), address)
+    .await;
+
+    (positions_manager, gov)
+}
+
+#[tokio::test]
+async fn test_packed_position_round_trip() {
+    let (positions_manager, _gov) = init().await;
+
+    let account = Address::random();
+    let collateral_token = Address::random();
+    let index_token = Address::random();
+
+    let size = U256::from(u128::MAX);
+    let collateral = to_price(1000);
+    let average_price = to_price(40000);
+    let entry_funding_rate = U256::from(123);
+    let reserve_amount = U256::from(225000);
+    let realised_pnl = I256::from(-5);
+    let last_increased_time = U256::from(u64::MAX);
+
+    positions_manager
+        .position_update(
+            account,
+            collateral_token,
+            index_token,
+            true,
+            size,
+            collateral,
+            average_price,
+            entry_funding_rate,
+            reserve_amount,
+            realised_pnl,
+            last_increased_time,
+        )
+        .await
+        .unwrap();
+
+    let key = positions_manager
+        .get_position_key(account, collateral_token, index_token, true)
+        .await
+        .unwrap();
+    assert!(positions_manager.is_position_migrated(key).await.unwrap());
+    assert_eq!(
+        positions_manager.position_by_key(key).await.unwrap(),
+        (
+            size,
+            collateral,
+            average_price,
+            entry_funding_rate,
+            reserve_amount,
+            realised_pnl,
+            last_increased_time,
+        )
+    );
+    assert_eq!(
+        positions_manager.position_info(key).await.unwrap(),
+        (account, collateral_token, index_token, true)
+    );
+
+    // size, collateral and average price are capped at u128::MAX (~3.4e8 usd)
+    positions_manager
+        .position_update(
+            account,
+            collateral_token,
+            index_token,
+            true,
+            size + U256::one(),
+            collateral,
+            average_price,
+            entry_funding_rate,
+            reserve_amount,
+            realised_pnl,
+            last_increased_time,
+        )
+        .await
+        .assert_revert_str("Vault: position value overflow");
+    positions_manager
+        .position_update(
+            account,
+            collateral_token,
+            index_token,
+            true,
+            size,
+            collateral,
+            average_price,
+            entry_funding_rate,
+            reserve_amount,
+            realised_pnl,
+            last_increased_time + U256::one(),
+        )
+        .await
+        .assert_revert_str("Vault: position value overflow");
+
+    positions_manager
+        .position_update(
+            account,
+            collateral_token,
+            index_token,
+            true,
+            U256::zero(),
+            U256::zero(),
+            U256::zero(),
+            U256::zero(),
+            U256::zero(),
+            I256::zero(),
+            U256::zero(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        positions_manager.position_by_key(key).await.unwrap(),
+        (
+            U256::zero(),
+            U256::zero(),
+            U256::zero(),
+            U256::zero(),
+            U256::zero(),
+            I256::zero(),
+            U256::zero(),
+        )
+    );
+    assert_eq!(
+        positions_manager.position_info(key).await.unwrap(),
+        (Address::zero(), Address::zero(), Address::zero(), false)
+    );
+}
+
+#[tokio::test]
+async fn test_packed_position_storage_writes() {
+    let (positions_manager, gov) = init().await;
+
+    let account = Address::random();
+    let collateral_token = Address::random();
+    let index_token = Address::random();
+
+    // the legacy layout wrote its 7 slots on every open, update and close,
+    // opening now writes the 4 packed slots, the 4 position info slots
+    // and appends the key to the account and open position sets (3 slots each), +7
+    gov.reset_counters();
+    positions_manager
+        .position_update(
+            account,
+            collateral_token,
+            index_token,
+            false,
+            to_price(90),
+            to_price(10),
+            to_price(40000),
+            U256::zero(),
+            U256::from(90),
+            I256::zero(),
+            U256::from(100),
+        )
+        .await
+        .unwrap();
+    assert_eq!(gov.counters(positions_manager.address()).storage_stores, 14);
+
+    // updating an open position rewrites the 4 packed slots, -3
+    gov.reset_counters();
+    positions_manager
+        .position_update(
+            account,
+            collateral_token,
+            index_token,
+            false,
+            to_price(180),
+            to_price(20),
+            to_price(41000),
+            U256::from(10),
+            U256::from(180),
+            I256::from(1),
+            U256::from(200),
+        )
+        .await
+        .unwrap();
+    assert_eq!(gov.counters(positions_manager.address()).storage_stores, 4);
+
+    // already packed and registered positions are skipped by the migration
+    gov.reset_counters();
+    positions_manager
+        .migrate_positions(
+            vec![account],
+            vec![collateral_token],
+            vec![index_token],
+            vec![false],
+        )
+        .await
+        .unwrap();
+    assert_eq!(gov.counters(positions_manager.address()).storage_stores, 0);
+
+    // closing clears the 4 packed slots, the 4 position info slots
+    // and removes the key from both sets (3 slots each), +7,
+    // a full open, update and close cycle writes 32 slots against the 21 of the legacy layout
+    gov.reset_counters();
+    positions_manager
+        .position_update(
+            account,
+            collateral_token,
+            index_token,
+            false,
+            U256::zero(),
+            U256::zero(),
+            U256::zero(),
+            U256::zero(),
+            U256::zero(),
+            I256::zero(),
+            U256::zero(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(gov.counters(positions_manager.address()).storage_stores, 14);
+
+    positions_manager
+        .migrate_positions(vec![account], vec![], vec![index_token], vec![false])
+        .await
+        .assert_revert_str("Vault: array length mismatch");
+}
@@ -0,0 +1,323 @@
+use std::sync::Arc;
+
+use ethers::types::{Address, U256};
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS, USD_DECIMALS},
+    contracts::ContractAddresses,
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_transfer_position_and_operator() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+    let user2 = create_user(gov.clone(), 2, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user0.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    let positions_manager = &contracts.vault.positions_manager;
+    let key = positions_manager
+        .get_position_key(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(
+        positions_manager.position_info(key).await.unwrap(),
+        (user0.address(), btc, btc, true)
+    );
+
+    // only the owner can start the transfer and only the recipient can accept it
+    positions_manager
+        .connect_acc(user1.clone())
+        .transfer_position(key, user1.address())
+        .await
+        .assert_revert_str("Vault: forbidden");
+    positions_manager
+        .connect_acc(user0.clone())
+        .transfer_position(key, user1.address())
+        .await
+        .unwrap();
+    positions_manager
+        .connect_acc(user2.clone())
+        .accept_position(key)
+        .await
+        .assert_revert_str("Vault: forbidden");
+    positions_manager
+        .connect_acc(user1.clone())
+        .accept_position(key)
+        .await
+        .unwrap();
+
+    let position = positions_manager
+        .position(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(position.0, U256::zero());
+    assert_eq!(
+        positions_manager.position_info(key).await.unwrap(),
+        (Address::zero(), Address::zero(), Address::zero(), false)
+    );
+
+    let position = positions_manager
+        .position(user1.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(position.0, to_price(90));
+    assert_eq!(position.1, expand_decimals(991, USD_DECIMALS - 2));
+    assert_eq!(position.4, U256::from(225000));
+
+    // operators can manage the position, but the proceeds go to the owner
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user2.clone())
+        .decrease_position(
+            user1.address(),
+            btc,
+            btc,
+            U256::zero(),
+            to_price(90),
+            true,
+            user2.address(),
+        )
+        .await
+        .assert_revert_str("Vault: forbidden");
+
+    positions_manager
+        .connect_acc(user1.clone())
+        .set_position_operator(user2.address(), true)
+        .await
+        .unwrap();
+    assert!(positions_manager
+        .is_position_operator(user1.address(), user2.address())
+        .await
+        .unwrap());
+
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user2.clone())
+        .decrease_position(
+            user1.address(),
+            btc,
+            btc,
+            U256::zero(),
+            to_price(90),
+            true,
+            user2.address(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        contracts
+            .tokens
+            .btc
+            .balance_of(user1.address())
+            .await
+            .unwrap(),
+        U256::from(24550)
+    );
+    assert_eq!(
+        contracts
+            .tokens
+            .btc
+            .balance_of(user2.address())
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+}
+
+#[tokio::test]
+async fn test_approve_position() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user0.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    let positions_manager = &contracts.vault.positions_manager;
+    let key = positions_manager
+        .get_position_key(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+
+    // registering an already tracked position is a no-op
+    positions_manager
+        .connect_acc(user0.clone())
+        .register_positions(vec![user0.address()], vec![btc], vec![btc], vec![true])
+        .await
+        .assert_revert_str("Vault: forbidden");
+    positions_manager
+        .register_positions(vec![user0.address()], vec![btc], vec![], vec![true])
+        .await
+        .assert_revert_str("Vault: array length mismatch");
+    positions_manager
+        .register_positions(vec![user0.address()], vec![btc], vec![btc], vec![true])
+        .await
+        .unwrap();
+    assert_eq!(
+        positions_manager
+            .account_positions_length(user0.address())
+            .await
+            .unwrap(),
+        U256::one()
+    );
+
+    positions_manager
+        .connect_acc(user1.clone())
+        .approve_position(key, user1.address())
+        .await
+        .assert_revert_str("Vault: forbidden");
+    positions_manager
+        .connect_acc(user0.clone())
+        .approve_position(key, user1.address())
+        .await
+        .unwrap();
+    assert_eq!(
+        positions_manager.position_approval(key).await.unwrap(),
+        user1.address()
+    );
+
+    // the approval only covers the approved position
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user1.clone())
+        .withdraw_margin(user0.address(), btc, U256::one(), user1.address())
+        .await
+        .assert_revert_str("Vault: forbidden");
+
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user1.clone())
+        .decrease_position(
+            user0.address(),
+            btc,
+            btc,
+            U256::zero(),
+            to_price(90),
+            true,
+            user1.address(),
+        )
+        .await
+        .unwrap();
+
+    // proceeds go to the owner and the approval is cleared with the position
+    assert_eq!(
+        contracts
+            .tokens
+            .btc
+            .balance_of(user1.address())
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+    assert_eq!(
+        positions_manager.position_approval(key).await.unwrap(),
+        Address::zero()
+    );
+}
@@ -0,0 +1,225 @@
+use std::sync::Arc;
+
+use ethers::types::U256;
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, DAI_DECIMALS, ETH_DECIMALS, USD_DECIMALS},
+    contracts::ContractAddresses,
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_price_impact_pool_per_collateral() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let dai = contracts.tokens.dai.address();
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(dai, to_price(1)).await;
+    contracts.vault.set_dai_config(dai).await;
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_dai(user1.address(), expand_decimals(1000, DAI_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .mint_btc(user1.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .dai
+        .connect_acc(user1.clone())
+        .transfer(
+            contracts.vault.vault.address(),
+            expand_decimals(500, DAI_DECIMALS),
+        )
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(dai, user1.address())
+        .await
+        .unwrap();
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(250000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .swap_manager
+        .buy_usdo(btc, user1.address())
+        .await
+        .unwrap();
+
+    let positions_manager = &contracts.vault.positions_manager;
+    positions_manager
+        .set_price_impact_depth(btc, to_price(1000))
+        .await
+        .unwrap();
+
+    // the long moves the skew from 0 to $90 and is charged 90^2 / 2000 = $4.05
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user1.clone())
+        .transfer(contracts.vault.vault.address(), U256::from(25000))
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), btc, btc, to_price(90), true)
+        .await
+        .unwrap();
+
+    let position = positions_manager
+        .position(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(position.1, expand_decimals(586, USD_DECIMALS - 2));
+    // the impact is settled with the collateral, the average price stays at the oracle price
+    assert_eq!(position.2, to_price(40000));
+    assert_eq!(
+        positions_manager.price_impact_pool(btc, btc).await.unwrap(),
+        U256::from(10125)
+    );
+
+    // the short brings the skew back to 0, but nothing was charged in dai yet,
+    // so there is nothing to rebate from the dai pool
+    contracts
+        .tokens
+        .dai
+        .connect_acc(user1.clone())
+        .transfer(
+            contracts.vault.vault.address(),
+            expand_decimals(10, DAI_DECIMALS),
+        )
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), dai, btc, to_price(90), false)
+        .await
+        .unwrap();
+
+    let position = positions_manager
+        .position(user0.address(), dai, btc, false)
+        .await
+        .unwrap();
+    assert_eq!(position.1, expand_decimals(991, USD_DECIMALS - 2));
+    assert_eq!(
+        positions_manager.price_impact_pool(btc, dai).await.unwrap(),
+        U256::zero()
+    );
+    assert_eq!(
+        positions_manager.price_impact_pool(btc, btc).await.unwrap(),
+        U256::from(10125)
+    );
+
+    // growing the short moves the skew from 0 to -$90, the charge goes to the dai pool
+    contracts
+        .tokens
+        .dai
+        .connect_acc(user1.clone())
+        .transfer(
+            contracts.vault.vault.address(),
+            expand_decimals(10, DAI_DECIMALS),
+        )
+        .await
+        .unwrap();
+    contracts
+        .vault
+        .positions_increase_manager
+        .connect_acc(user0.clone())
+        .increase_position(user0.address(), dai, btc, to_price(90), false)
+        .await
+        .unwrap();
+
+    let position = positions_manager
+        .position(user0.address(), dai, btc, false)
+        .await
+        .unwrap();
+    assert_eq!(position.1, expand_decimals(1577, USD_DECIMALS - 2));
+    assert_eq!(
+        positions_manager.price_impact_pool(btc, dai).await.unwrap(),
+        expand_decimals(405, DAI_DECIMALS - 2)
+    );
+
+    // reducing the short brings the skew back to 0 and is rebated from the dai pool only
+    let pool_amount = contracts.vault.vault.pool_amount(dai).await.unwrap();
+    contracts
+        .vault
+        .positions_decrease_manager
+        .connect_acc(user0.clone())
+        .decrease_position(
+            user0.address(),
+            dai,
+            btc,
+            U256::zero(),
+            to_price(90),
+            false,
+            user0.address(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        positions_manager.price_impact_pool(btc, dai).await.unwrap(),
+        U256::zero()
+    );
+    assert_eq!(
+        positions_manager.price_impact_pool(btc, btc).await.unwrap(),
+        U256::from(10125)
+    );
+    assert_eq!(
+        contracts.vault.vault.pool_amount(dai).await.unwrap(),
+        pool_amount - expand_decimals(405, DAI_DECIMALS - 2)
+    );
+    assert_eq!(
+        contracts
+            .tokens
+            .dai
+            .balance_of(user0.address())
+            .await
+            .unwrap(),
+        expand_decimals(405, DAI_DECIMALS - 2)
+    );
+}
@@ -100,7 +100,7 @@ pub async fn test_sell_usdo() {
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
         U256::zero()
@@ -231,10 +231,10 @@ pub async fn test_sell_usdo() {
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
-        U256::from(29700)
+        U256::zero()
     );
 
     contracts
@@ -358,10 +358,10 @@ pub async fn test_sell_usdo() {
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
-        U256::from(29700 - 15000)
+        U256::zero()
     );
 }
 
@@ -389,7 +389,7 @@ pub async fn test_sell_usdo_after_price_increase() {
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
         U256::zero()
@@ -523,10 +523,10 @@ pub async fn test_sell_usdo_after_price_increase() {
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
-        U256::from(29700)
+        U256::zero()
     );
 
     contracts
@@ -537,10 +537,10 @@ pub async fn test_sell_usdo_after_price_increase() {
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
-        U256::from(39600)
+        U256::from(39600 - 29700)
     );
 
     contracts
@@ -625,10 +625,10 @@ pub async fn test_sell_usdo_after_price_increase() {
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
-        U256::from(24800)
+        U256::from(24800 - (29700 - 15000))
     );
 }
 
@@ -711,7 +711,7 @@ pub async fn test_sell_usdo_redeem_based_on_price() {
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
         U256::zero()
@@ -737,7 +737,7 @@ pub async fn test_sell_usdo_redeem_based_on_price() {
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
         expand_decimals(119640, USDO_DECIMALS)
@@ -815,7 +815,7 @@ pub async fn test_sell_usdo_redeem_based_on_price() {
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
         expand_decimals(159520, USDO_DECIMALS)
@@ -869,7 +869,7 @@ pub async fn test_sell_usdo_redeem_based_on_price() {
         contracts
             .staking
             .olp_manager_utils
-            .get_aum_in_usdo()
+            .get_aum_in_usdo(true)
             .await
             .unwrap(),
         expand_decimals(149520, USDO_DECIMALS)
@@ -550,3 +550,477 @@ async fn test_swap_tokens_to_eth() {
         U256::from_dec_str("997000000000000000").unwrap(),
     );
 }
+
+#[tokio::test]
+async fn test_swap_caps() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let bnb = contracts.tokens.bnb.address();
+    let btc = contracts.tokens.btc.address();
+    let swap_manager = &contracts.vault.swap_manager;
+
+    contracts.set_price(bnb, to_price(300)).await;
+    contracts.vault.set_bnb_config(bnb).await;
+    contracts.set_price(btc, to_price(60000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    swap_manager
+        .connect_acc(user0.clone())
+        .set_max_usdo_amount(bnb, expand_decimals(50000, 18))
+        .await
+        .assert_revert_str("Vault: forbidden");
+    swap_manager
+        .set_max_usdo_amount(bnb, expand_decimals(50000, 18))
+        .await
+        .unwrap();
+    assert_eq!(
+        swap_manager.get_max_usdo_mint(bnb).await.unwrap(),
+        expand_decimals(50000, 18)
+    );
+
+    contracts
+        .tokens
+        .mint_bnb(user0.address(), expand_decimals(200, 18))
+        .await;
+    contracts
+        .tokens
+        .bnb
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), expand_decimals(200, 18))
+        .await
+        .unwrap();
+
+    // 200 * 300 - 0.3% fee => 59,820 USDO
+    swap_manager
+        .connect_acc(user0.clone())
+        .buy_usdo(bnb, user0.address())
+        .await
+        .assert_revert_str("Vault: max USDO amount exceeded");
+
+    swap_manager
+        .set_max_usdo_amount(bnb, U256::zero())
+        .await
+        .unwrap();
+    assert_eq!(
+        swap_manager.get_max_usdo_mint(bnb).await.unwrap(),
+        U256::MAX
+    );
+    swap_manager
+        .connect_acc(user0.clone())
+        .buy_usdo(bnb, user0.address())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, 8))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), expand_decimals(1, 8))
+        .await
+        .unwrap();
+    swap_manager
+        .connect_acc(user0.clone())
+        .buy_usdo(btc, user0.address())
+        .await
+        .unwrap();
+
+    // 1 BTC - 0.3% fee => 0.997 BTC in the pool, keep all but 0.001 BTC
+    swap_manager
+        .connect_acc(user0.clone())
+        .set_buffer_amount(btc, U256::from(99_600_000))
+        .await
+        .assert_revert_str("Vault: forbidden");
+    swap_manager
+        .set_buffer_amount(btc, U256::from(99_600_000))
+        .await
+        .unwrap();
+    assert_eq!(
+        swap_manager.buffer_amount(btc).await.unwrap(),
+        U256::from(99_600_000)
+    );
+    assert_eq!(
+        swap_manager.get_max_swap_out(btc).await.unwrap(),
+        U256::from(100_000)
+    );
+
+    // 1 BNB => 0.005 BTC
+    contracts
+        .tokens
+        .mint_bnb(user0.address(), expand_decimals(1, 18))
+        .await;
+    contracts
+        .tokens
+        .bnb
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), expand_decimals(1, 18))
+        .await
+        .unwrap();
+    swap_manager
+        .connect_acc(user0.clone())
+        .swap(bnb, btc, user0.address())
+        .await
+        .assert_revert_str("Vault: pool_amount < buffer");
+}
+
+#[tokio::test]
+async fn test_swap_quotes() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let bnb = contracts.tokens.bnb.address();
+    let btc = contracts.tokens.btc.address();
+    let usdo = contracts.tokens.usdo.address();
+    let swap_manager = &contracts.vault.swap_manager;
+
+    contracts.set_price(bnb, to_price(300)).await;
+    contracts.vault.set_bnb_config(bnb).await;
+    contracts.set_price(btc, to_price(60000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    // 200 * 300 - 0.3% fee => 59,820 USDO
+    assert_eq!(
+        swap_manager
+            .get_amount_out(bnb, usdo, expand_decimals(200, 18))
+            .await
+            .unwrap(),
+        (
+            expand_decimals(60000, 18),
+            expand_decimals(59820, 18),
+            U256::from(30)
+        )
+    );
+    contracts
+        .tokens
+        .mint_bnb(user0.address(), expand_decimals(200, 18))
+        .await;
+    contracts
+        .tokens
+        .bnb
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), expand_decimals(200, 18))
+        .await
+        .unwrap();
+    swap_manager
+        .connect_acc(user0.clone())
+        .buy_usdo(bnb, user0.address())
+        .await
+        .unwrap();
+    assert_eq!(
+        contracts
+            .tokens
+            .usdo
+            .balance_of(user0.address())
+            .await
+            .unwrap(),
+        expand_decimals(59820, 18)
+    );
+
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, 8))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), expand_decimals(1, 8))
+        .await
+        .unwrap();
+    swap_manager
+        .connect_acc(user0.clone())
+        .buy_usdo(btc, user0.address())
+        .await
+        .unwrap();
+
+    // 1 BNB => 0.005 BTC - 0.3% fee
+    let (amount_out, amount_out_after_fees, fee_basis_points) = swap_manager
+        .get_amount_out(bnb, btc, expand_decimals(1, 18))
+        .await
+        .unwrap();
+    assert_eq!(amount_out, U256::from(500_000));
+    assert_eq!(amount_out_after_fees, U256::from(498_500));
+    assert_eq!(fee_basis_points, U256::from(30));
+    assert_eq!(
+        contracts
+            .router
+            .swap
+            .get_amount_out(vec![bnb, btc], expand_decimals(1, 18))
+            .await
+            .unwrap(),
+        amount_out_after_fees
+    );
+
+    let (amount_in, _, _) = swap_manager
+        .get_amount_in(bnb, btc, amount_out_after_fees)
+        .await
+        .unwrap();
+    assert_eq!(amount_in, expand_decimals(1, 18));
+
+    contracts.tokens.mint_bnb(user0.address(), amount_in).await;
+    contracts
+        .tokens
+        .bnb
+        .connect_acc(user0.clone())
+        .transfer(contracts.vault.vault.address(), amount_in)
+        .await
+        .unwrap();
+    swap_manager
+        .connect_acc(user0.clone())
+        .swap(bnb, btc, user0.address())
+        .await
+        .unwrap();
+    assert_eq!(
+        contracts
+            .tokens
+            .btc
+            .balance_of(user0.address())
+            .await
+            .unwrap(),
+        amount_out_after_fees
+    );
+
+    // multi-hop quotes chain the direct quotes
+    let (_, usdo_out, _) = swap_manager
+        .get_amount_out(bnb, usdo, expand_decimals(1, 18))
+        .await
+        .unwrap();
+    let (_, btc_out, _) = swap_manager
+        .get_amount_out(usdo, btc, usdo_out)
+        .await
+        .unwrap();
+    assert_eq!(
+        contracts
+            .router
+            .swap
+            .get_amount_out(vec![bnb, usdo, btc], expand_decimals(1, 18))
+            .await
+            .unwrap(),
+        btc_out
+    );
+    let amount_in = contracts
+        .router
+        .swap
+        .get_amount_in(vec![bnb, usdo, btc], btc_out)
+        .await
+        .unwrap();
+    assert!(
+        contracts
+            .router
+            .swap
+            .get_amount_out(vec![bnb, usdo, btc], amount_in)
+            .await
+            .unwrap()
+            >= btc_out
+    );
+}
+
+#[tokio::test]
+async fn test_swap_multi_hop() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let bnb = contracts.tokens.bnb.address();
+    let btc = contracts.tokens.btc.address();
+    let usdc = contracts.tokens.usdc.address();
+    let swap_router = &contracts.router.swap;
+
+    contracts.set_price(bnb, to_price(300)).await;
+    contracts.vault.set_bnb_config(bnb).await;
+    contracts.set_price(btc, to_price(60000)).await;
+    contracts.vault.set_btc_config(btc).await;
+    contracts.set_price(usdc, to_price(1)).await;
+    contracts.vault.set_usdc_config(usdc).await;
+
+    contracts
+        .deposit_to_vault(btc, expand_decimals(10, BTC_DECIMALS))
+        .await;
+    contracts
+        .deposit_to_vault(usdc, expand_decimals(100000, USDC_DECIMALS))
+        .await;
+
+    contracts
+        .tokens
+        .mint_bnb(user0.address(), expand_decimals(10, 18))
+        .await;
+    contracts
+        .tokens
+        .bnb
+        .connect_acc(user0.clone())
+        .approve(swap_router.address(), expand_decimals(10, 18))
+        .await
+        .unwrap();
+
+    let path = vec![bnb, btc, usdc];
+
+    // exact input
+    let amount_out = swap_router
+        .get_amount_out(path.clone(), expand_decimals(1, 18))
+        .await
+        .unwrap();
+    swap_router
+        .connect_acc(user0.clone())
+        .swap(
+            path.clone(),
+            expand_decimals(1, 18),
+            amount_out,
+            user0.address(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(contracts.balance(usdc, user0.address()).await, amount_out);
+    assert_eq!(
+        contracts.balance(btc, swap_router.address()).await,
+        U256::zero()
+    );
+
+    // exact output
+    let amount_out = expand_decimals(100, USDC_DECIMALS);
+    let amount_in = swap_router
+        .get_amount_in(path.clone(), amount_out)
+        .await
+        .unwrap();
+    swap_router
+        .connect_acc(user0.clone())
+        .swap_exact_out(
+            path.clone(),
+            amount_out,
+            amount_in - U256::one(),
+            user0.address(),
+        )
+        .await
+        .assert_revert_str("Router: excessive amount in");
+
+    let bnb_balance = contracts.balance(bnb, user0.address()).await;
+    let usdc_balance = contracts.balance(usdc, user0.address()).await;
+    swap_router
+        .connect_acc(user0.clone())
+        .swap_exact_out(path.clone(), amount_out, amount_in, user0.address())
+        .await
+        .unwrap();
+    assert_eq!(
+        contracts.balance(bnb, user0.address()).await,
+        bnb_balance - amount_in
+    );
+    assert!(contracts.balance(usdc, user0.address()).await >= usdc_balance + amount_out);
+
+    // path length
+    assert_eq!(swap_router.max_path_length().await.unwrap(), U256::from(4));
+    swap_router
+        .connect_acc(user0.clone())
+        .set_max_path_length(U256::from(2))
+        .await
+        .assert_revert_str("Router: forbidden");
+    swap_router
+        .set_max_path_length(U256::one())
+        .await
+        .assert_revert_str("Router: invalid path");
+    swap_router
+        .set_max_path_length(U256::from(2))
+        .await
+        .unwrap();
+    swap_router
+        .get_amount_out(path.clone(), expand_decimals(1, 18))
+        .await
+        .assert_revert_str("Router: invalid path");
+    swap_router
+        .connect_acc(user0.clone())
+        .swap(path, expand_decimals(1, 18), U256::zero(), user0.address())
+        .await
+        .assert_revert_str("Router: invalid path");
+}
+
+#[tokio::test]
+async fn test_increase_position_multi_hop() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let bnb = contracts.tokens.bnb.address();
+    let dai = contracts.tokens.dai.address();
+    let usdc = contracts.tokens.usdc.address();
+    let btc = contracts.tokens.btc.address();
+    let positions_increase = &contracts.router.positions_increase;
+
+    contracts.set_price(bnb, to_price(300)).await;
+    contracts.vault.set_bnb_config(bnb).await;
+    contracts.set_price(dai, to_price(1)).await;
+    contracts.vault.set_dai_config(dai).await;
+    contracts.set_price(usdc, to_price(1)).await;
+    contracts.vault.set_usdc_config(usdc).await;
+    contracts.set_price(btc, to_price(60000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .deposit_to_vault(dai, expand_decimals(100000, 18))
+        .await;
+    contracts
+        .deposit_to_vault(usdc, expand_decimals(100000, USDC_DECIMALS))
+        .await;
+    contracts
+        .deposit_to_vault(btc, expand_decimals(10, BTC_DECIMALS))
+        .await;
+
+    contracts
+        .tokens
+        .mint_bnb(user0.address(), expand_decimals(1, 18))
+        .await;
+    contracts
+        .tokens
+        .bnb
+        .connect_acc(user0.clone())
+        .approve(positions_increase.address(), expand_decimals(1, 18))
+        .await
+        .unwrap();
+
+    // the collateral path is no longer limited to one intermediate token
+    let path = vec![bnb, dai, usdc, btc];
+    let min_out = contracts
+        .router
+        .swap
+        .get_amount_out(path.clone(), expand_decimals(1, 18))
+        .await
+        .unwrap();
+
+    positions_increase
+        .connect_acc(user0.clone())
+        .increase_position(
+            path,
+            btc,
+            expand_decimals(1, 18),
+            min_out,
+            to_price(600),
+            true,
+            to_price(60000),
+        )
+        .await
+        .unwrap();
+
+    let (size, ..) = contracts
+        .vault
+        .positions_manager
+        .position(user0.address(), btc, btc, true)
+        .await
+        .unwrap();
+    assert_eq!(size, to_price(600));
+    assert_eq!(contracts.balance(bnb, user0.address()).await, U256::zero());
+    for token in [dai, usdc, btc] {
+        assert_eq!(
+            contracts.balance(token, positions_increase.address()).await,
+            U256::zero()
+        );
+        assert_eq!(
+            contracts
+                .balance(token, contracts.router.swap.address())
+                .await,
+            U256::zero()
+        );
+    }
+}
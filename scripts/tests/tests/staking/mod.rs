@@ -1 +1,3 @@
+pub mod test_olp_vault;
 pub mod test_reward_router;
+pub mod test_staked_olp;
@@ -0,0 +1,267 @@
+use std::sync::Arc;
+
+use ethers::types::U256;
+
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS},
+    contracts::{olp_vault, reward_router, ContractAddresses},
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+/// mints olp for the account directly through the olp manager, returns the minted amount
+async fn add_liquidity(contracts: &Contracts, account: Arc<TestClient>) -> U256 {
+    let btc = contracts.tokens.btc.address();
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .staking
+        .olp_manager
+        .set_in_private_mode(false)
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .mint_btc(account.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(account.clone())
+        .approve(contracts.staking.olp_manager.address(), U256::MAX)
+        .await
+        .unwrap();
+    contracts
+        .staking
+        .olp_manager
+        .connect_acc(account.clone())
+        .add_liquidity(btc, U256::from(250000), U256::zero(), U256::zero())
+        .await
+        .unwrap();
+
+    contracts
+        .tokens
+        .olp
+        .balance_of(account.address())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_deposit_redeem() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let olp_vault = &contracts.staking.olp_vault;
+    let olp_amount = add_liquidity(&contracts, user0.clone()).await;
+    assert!(olp_amount > U256::zero());
+
+    // freshly minted olp can not skip the redemption cooldown through the vault
+    olp_vault
+        .connect_acc(user0.clone())
+        .deposit(olp_amount, user0.address())
+        .await
+        .assert_revert(olp_vault::CooldownNotPassed {});
+
+    let cooldown_duration = contracts
+        .staking
+        .olp_manager
+        .cooldown_duration()
+        .await
+        .unwrap();
+    gov.advance_block_timestamp(cooldown_duration.as_u64() + 1);
+
+    olp_vault
+        .connect_acc(user0.clone())
+        .deposit(olp_amount, user0.address())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        olp_vault.balance_of(user0.address()).await.unwrap(),
+        olp_amount
+    );
+    assert_eq!(olp_vault.total_assets().await.unwrap(), olp_amount);
+    assert_eq!(
+        contracts
+            .staking
+            .staked_olp_tracker_staking
+            .staked_amount(olp_vault.address())
+            .await
+            .unwrap(),
+        olp_amount
+    );
+    assert_eq!(
+        contracts
+            .tokens
+            .olp
+            .balance_of(user0.address())
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+
+    olp_vault
+        .connect_acc(user0.clone())
+        .redeem(olp_amount, user0.address(), user0.address())
+        .await
+        .unwrap();
+
+    assert_eq!(olp_vault.total_supply().await.unwrap(), U256::zero());
+    assert_eq!(olp_vault.total_assets().await.unwrap(), U256::zero());
+    assert_eq!(
+        contracts
+            .tokens
+            .olp
+            .balance_of(user0.address())
+            .await
+            .unwrap(),
+        olp_amount
+    );
+}
+
+#[tokio::test]
+async fn test_router_olp_staking_is_vault_only() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    let olp_amount = add_liquidity(&contracts, user0.clone()).await;
+
+    // staking held olp through the router would let it leave through a cooldown exempt handler
+    contracts
+        .staking
+        .reward_router
+        .connect_acc(user0.clone())
+        .stake_olp(olp_amount)
+        .await
+        .assert_revert(reward_router::Forbidden {});
+    contracts
+        .staking
+        .reward_router
+        .connect_acc(user0.clone())
+        .unstake_olp(olp_amount)
+        .await
+        .assert_revert(reward_router::Forbidden {});
+    contracts
+        .staking
+        .reward_router
+        .connect_acc(user0.clone())
+        .set_olp_vault(user0.address())
+        .await
+        .assert_revert(reward_router::Forbidden {});
+}
+
+#[tokio::test]
+async fn test_harvest_is_permissionless() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+
+    // nothing to compound yet
+    assert_eq!(
+        contracts
+            .staking
+            .olp_vault
+            .connect_acc(user0.clone())
+            .harvest()
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+}
+
+#[tokio::test]
+async fn test_deposit_compounds_pending_fees() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+
+    let olp_vault = &contracts.staking.olp_vault;
+    let olp_amount0 = add_liquidity(&contracts, user0.clone()).await;
+    let olp_amount1 = add_liquidity(&contracts, user1.clone()).await;
+
+    let cooldown_duration = contracts
+        .staking
+        .olp_manager
+        .cooldown_duration()
+        .await
+        .unwrap();
+    gov.advance_block_timestamp(cooldown_duration.as_u64() + 1);
+
+    olp_vault
+        .connect_acc(user0.clone())
+        .deposit(olp_amount0, user0.address())
+        .await
+        .unwrap();
+
+    let weth = contracts.tokens.weth.address();
+    contracts.set_price(weth, to_price(2000)).await;
+    contracts.vault.set_eth_config(weth).await;
+
+    contracts
+        .tokens
+        .mint_weth(
+            contracts.staking.fee_olp_distributor.address(),
+            expand_decimals(1, ETH_DECIMALS),
+        )
+        .await;
+    contracts
+        .staking
+        .fee_olp_distributor
+        .set_tokens_per_interval(U256::from_dec_str("1000000000000").unwrap())
+        .await
+        .unwrap();
+    gov.advance_block_timestamp(24 * 60 * 60);
+
+    // the weth fees earned before the deposit are compounded into the share price
+    olp_vault
+        .connect_acc(user1.clone())
+        .deposit(olp_amount1, user1.address())
+        .await
+        .unwrap();
+
+    let total_assets = olp_vault.total_assets().await.unwrap();
+    assert!(total_assets > olp_amount0 + olp_amount1);
+    assert!(olp_vault.balance_of(user1.address()).await.unwrap() < olp_amount1);
+    assert_eq!(
+        contracts
+            .tokens
+            .weth
+            .balance_of(olp_vault.address())
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+}
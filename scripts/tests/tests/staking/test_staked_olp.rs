@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use ethers::types::{Address, U256};
+
+use log::LevelFilter;
+use omx_tests::{
+    constants::{BTC_DECIMALS, ETH_DECIMALS},
+    contracts::{staked_olp, ContractAddresses},
+    init::{Contracts, ContractsInitArgs},
+    stylus_testing::provider::{TestClient, TestProvider},
+    utils::{
+        errors::ContractRevertExt,
+        logs::configure_logs,
+        prices::{expand_decimals, to_price},
+        test_helpers::{create_gov, create_user, ConnectAcc},
+    },
+};
+
+pub async fn init() -> (Contracts, Arc<TestClient>) {
+    configure_logs(LevelFilter::Info);
+
+    let gov = create_gov();
+
+    let addresses = ContractAddresses::deploy_contracts(gov.clone()).await;
+
+    gov.mint_eth(gov.address(), expand_decimals(1000, ETH_DECIMALS));
+
+    let contracts = ContractsInitArgs {
+        min_profit_time: U256::from(60 * 60),
+        gov: gov.address(),
+    }
+    .init(gov.clone(), &addresses)
+    .await;
+
+    (contracts, gov)
+}
+
+#[tokio::test]
+async fn test_transfer_staked_olp() {
+    let (contracts, gov) = init().await;
+
+    let user0 = create_user(gov.clone(), 0, 0).await;
+    let user1 = create_user(gov.clone(), 1, 0).await;
+    let user2 = create_user(gov.clone(), 2, 0).await;
+
+    let btc = contracts.tokens.btc.address();
+    let staked_olp = &contracts.staking.staked_olp;
+
+    contracts.set_price(btc, to_price(40000)).await;
+    contracts.vault.set_btc_config(btc).await;
+
+    contracts
+        .tokens
+        .mint_btc(user0.address(), expand_decimals(1, BTC_DECIMALS))
+        .await;
+    contracts
+        .tokens
+        .btc
+        .connect_acc(user0.clone())
+        .approve(contracts.staking.olp_manager.address(), U256::MAX)
+        .await
+        .unwrap();
+    contracts
+        .staking
+        .reward_router
+        .connect_acc(user0.clone())
+        .mint_and_stake_olp(btc, U256::from(250000), U256::zero(), U256::zero())
+        .await
+        .unwrap();
+
+    let olp_amount = staked_olp.balance_of(user0.address()).await.unwrap();
+    assert!(olp_amount > U256::zero());
+    assert_eq!(staked_olp.total_supply().await.unwrap(), olp_amount);
+
+    // freshly minted olp can not be moved before the cooldown
+    staked_olp
+        .connect_acc(user0.clone())
+        .transfer(user1.address(), olp_amount)
+        .await
+        .assert_revert(staked_olp::CooldownNotPassed {});
+
+    let cooldown_duration = contracts
+        .staking
+        .olp_manager
+        .cooldown_duration()
+        .await
+        .unwrap();
+    gov.advance_block_timestamp(cooldown_duration.as_u64() + 1);
+
+    staked_olp
+        .connect_acc(user0.clone())
+        .transfer(Address::zero(), olp_amount)
+        .await
+        .assert_revert(staked_olp::TransferToZeroAddress {});
+
+    let half = olp_amount / 2;
+    staked_olp
+        .connect_acc(user0.clone())
+        .transfer(user1.address(), half)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        staked_olp.balance_of(user0.address()).await.unwrap(),
+        olp_amount - half
+    );
+    assert_eq!(staked_olp.balance_of(user1.address()).await.unwrap(), half);
+    assert_eq!(staked_olp.total_supply().await.unwrap(), olp_amount);
+    assert_eq!(
+        contracts
+            .staking
+            .fee_olp_tracker_staking
+            .staked_amount(user1.address())
+            .await
+            .unwrap(),
+        half
+    );
+
+    staked_olp
+        .connect_acc(user2.clone())
+        .transfer_from(user1.address(), user2.address(), half)
+        .await
+        .assert_revert(staked_olp::InsufficientAllowance {});
+
+    staked_olp
+        .connect_acc(user1.clone())
+        .approve(user2.address(), half)
+        .await
+        .unwrap();
+    staked_olp
+        .connect_acc(user2.clone())
+        .transfer_from(user1.address(), user2.address(), half)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        staked_olp
+            .allowance(user1.address(), user2.address())
+            .await
+            .unwrap(),
+        U256::zero()
+    );
+    assert_eq!(
+        staked_olp.balance_of(user1.address()).await.unwrap(),
+        U256::zero()
+    );
+    assert_eq!(staked_olp.balance_of(user2.address()).await.unwrap(), half);
+}